@@ -0,0 +1,461 @@
+use std::path::PathBuf;
+
+use crate::KathaError;
+use crate::config::DataRoots;
+use crate::export::ExportFormat;
+use crate::search::MatchMode;
+
+/// 解析済みのコマンドライン引数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cli {
+    pub command: Command,
+    /// --claude-dir / --codex-dir で指定されたディレクトリ
+    pub roots: DataRoots,
+}
+
+/// コマンドライン引数で指定された動作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// TUI を起動
+    Tui,
+    /// 履歴の件数を表示（デバッグ用）
+    CountSessions,
+    /// セッション一覧
+    List(ListArgs),
+    /// セッションの内容を表示
+    Show(ShowArgs),
+    /// セッションを検索
+    Search(SearchArgs),
+    /// セッションをファイルに出力
+    Export(ExportArgs),
+    /// 全セッションの集計
+    Stats(StatsArgs),
+    /// 今月の予算の消化状況
+    Budget(BudgetArgs),
+    /// 使い方を表示
+    Help,
+    /// バージョンを表示
+    Version,
+}
+
+/// list サブコマンドの引数
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListArgs {
+    pub json: bool,
+    /// 表示件数の上限
+    pub limit: Option<usize>,
+    /// プロジェクト名・パスの部分一致
+    pub project: Option<String>,
+}
+
+/// show サブコマンドの引数
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShowArgs {
+    /// セッション ID（一意な前方一致、"<ディレクトリ>:<ID>" でも可）
+    pub session_id: String,
+    pub json: bool,
+    /// thinking ブロックを含めるか
+    pub thinking: bool,
+}
+
+/// search サブコマンドの引数
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchArgs {
+    /// 検索クエリ（TUI の検索構文と同じ）
+    pub query: String,
+    pub json: bool,
+    /// 本文を検索するか（false の場合はタイトル）
+    pub content: bool,
+    pub match_mode: MatchMode,
+    /// 表示件数の上限
+    pub limit: Option<usize>,
+}
+
+/// export サブコマンドの引数
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportArgs {
+    /// セッション ID（一意な前方一致、"<ディレクトリ>:<ID>" でも可）
+    pub session_id: String,
+    /// 形式（未指定なら設定ファイルの既定値）
+    pub format: Option<ExportFormat>,
+    /// 出力先（ディレクトリ・ファイル、"-" は標準出力）
+    pub output: Option<PathBuf>,
+    /// thinking ブロックを含めるか
+    pub thinking: bool,
+    pub json: bool,
+}
+
+/// stats サブコマンドの引数
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsArgs {
+    pub json: bool,
+}
+
+/// budget サブコマンドの引数
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BudgetArgs {
+    /// 超過した予算があれば失敗として終了するか
+    pub check: bool,
+    pub json: bool,
+}
+
+/// 使い方
+pub const USAGE: &str = "\
+Usage: katha [COMMAND]
+
+Without a command, katha starts the TUI.
+
+Commands:
+  list [--project NAME] [--limit N] [--json]
+      List sessions, newest first
+  show <SESSION_ID> [--thinking] [--json]
+      Print a session as Markdown (or JSON)
+  search <QUERY>... [--content] [--mode substring|regex|fuzzy] [--limit N] [--json]
+      Search session titles (or message bodies with --content)
+  export <SESSION_ID> [--format md|json] [--output PATH|-] [--thinking] [--json]
+      Write a session to a file (default: export.dir or current directory)
+  stats [--json]
+      Show totals across all sessions (and models missing from the pricing table)
+  budget [--check] [--json]
+      Show month-to-date spend for each [[budget]] in config.toml
+      (with --check, exit with status 1 if any budget is exceeded)
+
+Options:
+  --claude-dir DIR  Read Claude Code history from DIR (repeatable)
+  --codex-dir DIR   Read Codex history from DIR (repeatable)
+  -h, --help        Show this help
+  -V, --version     Show version
+
+SESSION_ID may be a unique prefix of the full id. When the same id exists in
+several data directories, pick one with DIR:SESSION_ID.";
+
+/// 引数（プログラム名を除く）を解析
+pub fn parse_args(args: &[String]) -> Result<Cli, KathaError> {
+    let (roots, args) = extract_data_roots(args)?;
+    Ok(Cli {
+        command: parse_command(&args)?,
+        roots,
+    })
+}
+
+/// どの位置にも書けるデータディレクトリ指定を取り出す
+fn extract_data_roots(args: &[String]) -> Result<(DataRoots, Vec<String>), KathaError> {
+    let mut roots = DataRoots::default();
+    let mut rest = Vec::new();
+    let mut args = ArgIter::new(args);
+    while let Some(arg) = args.next() {
+        let (option, inline_value) = match arg.split_once('=') {
+            Some((option, value)) => (option, Some(value)),
+            None => (arg, None),
+        };
+        let dirs = match option {
+            "--claude-dir" => &mut roots.claude_dirs,
+            "--codex-dir" => &mut roots.codex_dirs,
+            _ => {
+                rest.push(arg.to_string());
+                continue;
+            }
+        };
+        let value = match inline_value {
+            Some(value) => value,
+            None => args.value(option)?,
+        };
+        dirs.push(PathBuf::from(value));
+    }
+    Ok((roots, rest))
+}
+
+fn parse_command(args: &[String]) -> Result<Command, KathaError> {
+    let Some((command, rest)) = args.split_first() else {
+        return Ok(Command::Tui);
+    };
+    let mut args = ArgIter::new(rest);
+
+    let command = match command.as_str() {
+        "--count-sessions" => Command::CountSessions,
+        "-h" | "--help" | "help" => Command::Help,
+        "-V" | "--version" => Command::Version,
+        "list" => {
+            let mut list = ListArgs::default();
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--json" => list.json = true,
+                    "--limit" | "-n" => list.limit = Some(args.number(arg)?),
+                    "--project" | "-p" => list.project = Some(args.value(arg)?.to_string()),
+                    _ => return Err(unexpected(arg)),
+                }
+            }
+            Command::List(list)
+        }
+        "show" => {
+            let mut show = ShowArgs::default();
+            let mut positional = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--json" => show.json = true,
+                    "--thinking" => show.thinking = true,
+                    _ if arg.starts_with('-') => return Err(unexpected(arg)),
+                    _ => positional.push(arg),
+                }
+            }
+            show.session_id = single_session_id(&positional)?;
+            Command::Show(show)
+        }
+        "search" => {
+            let mut search = SearchArgs::default();
+            let mut terms = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--json" => search.json = true,
+                    "--content" => search.content = true,
+                    "--limit" | "-n" => search.limit = Some(args.number(arg)?),
+                    "--mode" => {
+                        search.match_mode = match args.value(arg)? {
+                            "substring" => MatchMode::Substring,
+                            "regex" => MatchMode::Regex,
+                            "fuzzy" => MatchMode::Fuzzy,
+                            other => {
+                                return Err(KathaError::InvalidArgument(format!(
+                                    "Unknown match mode '{}' (expected substring, regex or fuzzy)",
+                                    other
+                                )));
+                            }
+                        }
+                    }
+                    // 否定語（-word）は検索構文としてそのまま渡す
+                    _ if arg.starts_with("--") => return Err(unexpected(arg)),
+                    _ => terms.push(arg),
+                }
+            }
+            if terms.is_empty() {
+                return Err(KathaError::InvalidArgument(
+                    "search requires a query".to_string(),
+                ));
+            }
+            if search.content && search.match_mode != MatchMode::Substring {
+                return Err(KathaError::InvalidArgument(
+                    "--content only supports substring matching".to_string(),
+                ));
+            }
+            search.query = terms.join(" ");
+            Command::Search(search)
+        }
+        "export" => {
+            let mut export = ExportArgs::default();
+            let mut positional = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--json" => export.json = true,
+                    "--thinking" => export.thinking = true,
+                    "--format" | "-f" => {
+                        export.format = match args.value(arg)? {
+                            "md" | "markdown" => Some(ExportFormat::Markdown),
+                            "json" => Some(ExportFormat::Json),
+                            other => {
+                                return Err(KathaError::InvalidArgument(format!(
+                                    "Unknown export format '{}' (expected md or json)",
+                                    other
+                                )));
+                            }
+                        }
+                    }
+                    "--output" | "-o" => export.output = Some(PathBuf::from(args.value(arg)?)),
+                    _ if arg.starts_with('-') => return Err(unexpected(arg)),
+                    _ => positional.push(arg),
+                }
+            }
+            export.session_id = single_session_id(&positional)?;
+            Command::Export(export)
+        }
+        "stats" => {
+            let mut stats = StatsArgs::default();
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--json" => stats.json = true,
+                    _ => return Err(unexpected(arg)),
+                }
+            }
+            Command::Stats(stats)
+        }
+        "budget" => {
+            let mut budget = BudgetArgs::default();
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--check" => budget.check = true,
+                    "--json" => budget.json = true,
+                    _ => return Err(unexpected(arg)),
+                }
+            }
+            Command::Budget(budget)
+        }
+        other => {
+            return Err(KathaError::InvalidArgument(format!(
+                "Unknown command '{}' (see katha --help)",
+                other
+            )));
+        }
+    };
+    Ok(command)
+}
+
+/// 値付きオプションを読み進めるイテレータ
+struct ArgIter<'a> {
+    args: std::slice::Iter<'a, String>,
+}
+
+impl<'a> ArgIter<'a> {
+    fn new(args: &'a [String]) -> Self {
+        Self { args: args.iter() }
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.args.next().map(String::as_str)
+    }
+
+    /// オプションの値
+    fn value(&mut self, option: &str) -> Result<&'a str, KathaError> {
+        self.next()
+            .ok_or_else(|| KathaError::InvalidArgument(format!("{} requires a value", option)))
+    }
+
+    /// オプションの数値
+    fn number(&mut self, option: &str) -> Result<usize, KathaError> {
+        let value = self.value(option)?;
+        value.parse().map_err(|_| {
+            KathaError::InvalidArgument(format!("{} expects a number, got '{}'", option, value))
+        })
+    }
+}
+
+fn unexpected(arg: &str) -> KathaError {
+    KathaError::InvalidArgument(format!("Unexpected argument '{}'", arg))
+}
+
+fn single_session_id(positional: &[&str]) -> Result<String, KathaError> {
+    match positional {
+        [id] => Ok(id.to_string()),
+        [] => Err(KathaError::InvalidArgument(
+            "A session id is required".to_string(),
+        )),
+        [_, extra, ..] => Err(unexpected(extra)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, KathaError> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        parse_args(&args).map(|cli| cli.command)
+    }
+
+    #[test]
+    fn test_parse_args_commands() {
+        assert_eq!(parse(&[]).unwrap(), Command::Tui);
+        assert_eq!(
+            parse(&["--count-sessions"]).unwrap(),
+            Command::CountSessions
+        );
+        assert_eq!(
+            parse(&["list", "--json", "--limit", "5"]).unwrap(),
+            Command::List(ListArgs {
+                json: true,
+                limit: Some(5),
+                project: None,
+            })
+        );
+        assert_eq!(
+            parse(&["search", "cargo", "-test", "--mode", "regex"]).unwrap(),
+            Command::Search(SearchArgs {
+                query: "cargo -test".to_string(),
+                json: false,
+                content: false,
+                match_mode: MatchMode::Regex,
+                limit: None,
+            })
+        );
+        assert_eq!(
+            parse(&["search", "cargo", "--content", "--mode", "substring"]).unwrap(),
+            Command::Search(SearchArgs {
+                query: "cargo".to_string(),
+                json: false,
+                content: true,
+                match_mode: MatchMode::Substring,
+                limit: None,
+            })
+        );
+        assert_eq!(
+            parse(&["export", "abc", "--format", "json", "-o", "-"]).unwrap(),
+            Command::Export(ExportArgs {
+                session_id: "abc".to_string(),
+                format: Some(ExportFormat::Json),
+                output: Some(PathBuf::from("-")),
+                thinking: false,
+                json: false,
+            })
+        );
+        assert_eq!(
+            parse(&["budget", "--check"]).unwrap(),
+            Command::Budget(BudgetArgs {
+                check: true,
+                json: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_args_data_roots() {
+        let args: Vec<String> = [
+            "--claude-dir",
+            "/a",
+            "list",
+            "--codex-dir=/c",
+            "--json",
+            "--claude-dir",
+            "/b",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+        let cli = parse_args(&args).unwrap();
+        assert_eq!(
+            cli.roots,
+            DataRoots {
+                claude_dirs: vec![PathBuf::from("/a"), PathBuf::from("/b")],
+                codex_dirs: vec![PathBuf::from("/c")],
+            }
+        );
+        assert_eq!(
+            cli.command,
+            Command::List(ListArgs {
+                json: true,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        for args in [
+            &["frobnicate"][..],
+            &["list", "--limit"],
+            &["list", "--limit", "many"],
+            &["show"],
+            &["show", "a", "b"],
+            &["search"],
+            &["search", "x", "--content", "--mode", "fuzzy"],
+            &["export", "abc", "--format", "pdf"],
+            &["list", "--claude-dir"],
+            &["budget", "--fail"],
+        ] {
+            let error = parse(args).unwrap_err();
+            assert!(
+                matches!(error, KathaError::InvalidArgument(_)),
+                "{:?}",
+                args
+            );
+            assert_eq!(error.exit_code(), 64);
+        }
+    }
+}
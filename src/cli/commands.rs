@@ -0,0 +1,712 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tracing::warn;
+
+use crate::KathaError;
+use crate::data::SessionCatalog;
+use crate::domain::{Budget, BudgetReport, SessionStats};
+use crate::export::{
+    ExportFormat, Exporter, JsonExporter, MarkdownExporter, generate_filename, write_to_file,
+};
+use crate::search::{
+    FilterCriteria, QueryContext, SearchEngine, SearchIndex, SearchQuery, SearchScope,
+};
+use crate::tea::{SessionListItem, SessionSource};
+
+use super::args::{BudgetArgs, ExportArgs, ListArgs, SearchArgs, ShowArgs, StatsArgs};
+
+/// 一覧表示でのメッセージの最大文字数
+const MESSAGE_MAX_CHARS: usize = 80;
+/// stats で表示するモデル・ツールの件数
+const TOP_ENTRIES: usize = 5;
+
+/// JSON 出力用のセッション概要
+#[derive(Debug, Serialize)]
+struct SessionSummary<'a> {
+    session_id: &'a str,
+    source: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    root: Option<&'a str>,
+    project_name: &'a str,
+    project_path: &'a str,
+    latest_user_message: &'a str,
+    timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<i64>,
+}
+
+impl<'a> SessionSummary<'a> {
+    fn new(item: &'a SessionListItem, score: Option<i64>) -> Self {
+        Self {
+            session_id: &item.session_id,
+            source: source_name(item.source),
+            root: item.root.as_deref(),
+            project_name: &item.project_name,
+            project_path: &item.project_path,
+            latest_user_message: &item.latest_user_message,
+            timestamp: item.datetime.to_rfc3339(),
+            score,
+        }
+    }
+}
+
+/// JSON 出力用のエクスポート結果
+#[derive(Debug, Serialize)]
+struct ExportSummary<'a> {
+    session_id: &'a str,
+    format: &'static str,
+    path: String,
+}
+
+/// JSON 出力用の集計
+#[derive(Debug, Default, Serialize)]
+struct StatsSummary {
+    sessions: usize,
+    projects: usize,
+    claude_sessions: usize,
+    codex_sessions: usize,
+    messages: usize,
+    total_tokens: u64,
+    cost_usd: f64,
+    models: Vec<NameCount>,
+    tools: Vec<NameCount>,
+    /// 料金表になくコストに含まれていないモデル
+    unpriced_models: Vec<NameCount>,
+}
+
+/// JSON 出力用の予算の消化状況
+#[derive(Debug, Serialize)]
+struct BudgetSummary {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    monthly_usd: f64,
+    spent_usd: f64,
+    percent: f64,
+    status: &'static str,
+    /// 料金表になくコストに含まれていないモデル
+    unpriced_models: Vec<String>,
+}
+
+/// JSON 出力用の今月の予算
+#[derive(Debug, Serialize)]
+struct BudgetMonth {
+    month: String,
+    budgets: Vec<BudgetSummary>,
+}
+
+/// 名前ごとの出現セッション数
+#[derive(Debug, Serialize)]
+struct NameCount {
+    name: String,
+    sessions: usize,
+}
+
+fn source_name(source: SessionSource) -> &'static str {
+    match source {
+        SessionSource::Claude => "claude",
+        SessionSource::Codex => "codex",
+    }
+}
+
+fn write_json<W: Write, T: Serialize + ?Sized>(out: &mut W, value: &T) -> Result<(), KathaError> {
+    let json =
+        serde_json::to_string_pretty(value).map_err(|e| KathaError::JsonError(e.to_string()))?;
+    writeln!(out, "{}", json)?;
+    Ok(())
+}
+
+/// セッション一覧の 1 行（日時・出所・プロジェクト・ID・メッセージ）
+fn write_session_line<W: Write>(out: &mut W, item: &SessionListItem) -> Result<(), KathaError> {
+    let first_line = item.latest_user_message.lines().next().unwrap_or_default();
+    let mut message: String = first_line.chars().take(MESSAGE_MAX_CHARS).collect();
+    if first_line.chars().count() > MESSAGE_MAX_CHARS {
+        message.push('…');
+    }
+    let project = match &item.root {
+        Some(root) => format!("{} [{}]", item.project_name, root),
+        None => item.project_name.clone(),
+    };
+    writeln!(
+        out,
+        "{}  {:<6}  {}  {}  {}",
+        item.formatted_time,
+        source_name(item.source),
+        project,
+        item.session_id,
+        message
+    )?;
+    Ok(())
+}
+
+/// セッション一覧を新しい順に表示
+pub fn list<W: Write>(
+    out: &mut W,
+    catalog: &SessionCatalog,
+    args: &ListArgs,
+) -> Result<(), KathaError> {
+    let mut sessions = catalog.sessions();
+    if let Some(project) = &args.project {
+        let project = project.to_lowercase();
+        sessions.retain(|item| {
+            item.project_name.to_lowercase().contains(&project)
+                || item.project_path.to_lowercase().contains(&project)
+        });
+    }
+    sessions.sort_by_key(|item| std::cmp::Reverse(item.datetime));
+    sessions.truncate(args.limit.unwrap_or(usize::MAX));
+
+    if args.json {
+        let summaries: Vec<SessionSummary> = sessions
+            .iter()
+            .map(|item| SessionSummary::new(item, None))
+            .collect();
+        return write_json(out, &summaries);
+    }
+    for item in &sessions {
+        write_session_line(out, item)?;
+    }
+    Ok(())
+}
+
+/// セッションの内容を Markdown（または JSON）で表示
+pub fn show<W: Write>(
+    out: &mut W,
+    catalog: &SessionCatalog,
+    args: &ShowArgs,
+) -> Result<(), KathaError> {
+    let item = catalog.find_session(&args.session_id)?;
+    let session = catalog.read_session(&item)?;
+    let content = if args.json {
+        JsonExporter::new()
+            .include_thinking(args.thinking)
+            .export(&session)
+    } else {
+        MarkdownExporter::new()
+            .include_thinking(args.thinking)
+            .export(&session)
+    };
+    write!(out, "{}", content)?;
+    if !content.ends_with('\n') {
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// 検索インデックスを読み込んで対象セッションに合わせて更新
+/// index_file が None の場合は保存せずにその場で作成
+fn load_search_index(
+    catalog: &SessionCatalog,
+    sessions: &[SessionListItem],
+    index_file: Option<&Path>,
+) -> SearchIndex {
+    let mut index = match index_file {
+        Some(path) => SearchIndex::load(path).unwrap_or_else(|e| {
+            warn!("Rebuilding search index: {}", e);
+            SearchIndex::new()
+        }),
+        None => SearchIndex::new(),
+    };
+
+    let stats = index.update(&catalog.content_search_targets(sessions));
+    if stats.is_changed()
+        && let Some(path) = index_file
+        && let Err(e) = index.save(path)
+    {
+        warn!("Failed to save search index: {}", e);
+    }
+    index
+}
+
+/// TUI と同じ検索構文でセッションを検索（スコアの高い順、同点は新しい順）
+pub fn search<W: Write>(
+    out: &mut W,
+    catalog: &SessionCatalog,
+    index_file: Option<&Path>,
+    args: &SearchArgs,
+) -> Result<(), KathaError> {
+    let query = SearchQuery {
+        text: args.query.clone(),
+        scope: if args.content {
+            SearchScope::Content
+        } else {
+            SearchScope::Metadata
+        },
+        match_mode: args.match_mode,
+        ..Default::default()
+    };
+    let compiled = query.compile().map_err(|error| {
+        KathaError::InvalidArgument(format!(
+            "{} (at column {})",
+            error.message,
+            error.position + 1
+        ))
+    })?;
+
+    let sessions = catalog.sessions();
+    let index = load_search_index(catalog, &sessions, index_file);
+    let stats = index.stats_map();
+    let content = (args.content && !query.content_terms().is_empty()).then(|| index.search(&query));
+    let context = QueryContext {
+        stats: &stats,
+        scope: query.scope,
+        content: content.as_ref(),
+    };
+
+    let mut ranked =
+        SearchEngine::rank_and_filter(&sessions, &compiled, context, &FilterCriteria::default());
+    ranked.sort_by_key(|(index, score)| {
+        (
+            std::cmp::Reverse(*score),
+            std::cmp::Reverse(sessions[*index].datetime),
+        )
+    });
+    ranked.truncate(args.limit.unwrap_or(usize::MAX));
+
+    if args.json {
+        let summaries: Vec<SessionSummary> = ranked
+            .iter()
+            .map(|(index, score)| SessionSummary::new(&sessions[*index], Some(*score)))
+            .collect();
+        return write_json(out, &summaries);
+    }
+    for (index, _) in &ranked {
+        write_session_line(out, &sessions[*index])?;
+    }
+    Ok(())
+}
+
+/// 出力先のパス（ディレクトリ指定時はファイル名を自動生成）
+fn write_export(
+    content: &str,
+    filename: &str,
+    output: Option<&Path>,
+) -> Result<PathBuf, KathaError> {
+    match output {
+        Some(path) if !path.is_dir() => {
+            if path.exists() {
+                return Err(KathaError::FileExists(path.to_path_buf()));
+            }
+            std::fs::write(path, content)?;
+            Ok(path.to_path_buf())
+        }
+        directory => write_to_file(content, filename, directory),
+    }
+}
+
+/// セッションをファイル（または標準出力）に書き出す
+pub fn export<W: Write>(
+    out: &mut W,
+    catalog: &SessionCatalog,
+    args: &ExportArgs,
+) -> Result<(), KathaError> {
+    let item = catalog.find_session(&args.session_id)?;
+    let session = catalog.read_session(&item)?;
+    let format = args.format.unwrap_or_default();
+    let content = match format {
+        ExportFormat::Markdown => MarkdownExporter::new()
+            .include_thinking(args.thinking)
+            .export(&session),
+        ExportFormat::Json => JsonExporter::new()
+            .include_thinking(args.thinking)
+            .export(&session),
+    };
+
+    if args.output.as_deref() == Some(Path::new("-")) {
+        write!(out, "{}", content)?;
+        return Ok(());
+    }
+
+    let filename = generate_filename(&session, format);
+    let path = write_export(&content, &filename, args.output.as_deref())?;
+    if args.json {
+        return write_json(
+            out,
+            &ExportSummary {
+                session_id: &session.id,
+                format: format.extension(),
+                path: path.display().to_string(),
+            },
+        );
+    }
+    writeln!(out, "Exported to {}", path.display())?;
+    Ok(())
+}
+
+/// 出現セッション数の多い順に並べる
+fn top_counts<'a>(values: impl Iterator<Item = &'a String>) -> Vec<NameCount> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in values {
+        *counts.entry(value.as_str()).or_default() += 1;
+    }
+    let mut counts: Vec<NameCount> = counts
+        .into_iter()
+        .map(|(name, sessions)| NameCount {
+            name: name.to_string(),
+            sessions,
+        })
+        .collect();
+    counts.sort_by(|a, b| {
+        b.sessions
+            .cmp(&a.sessions)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    counts.truncate(TOP_ENTRIES);
+    counts
+}
+
+/// 全セッションの件数・トークン・コストを集計
+pub fn stats<W: Write>(
+    out: &mut W,
+    catalog: &SessionCatalog,
+    index_file: Option<&Path>,
+    args: &StatsArgs,
+) -> Result<(), KathaError> {
+    let sessions = catalog.sessions();
+    let index = load_search_index(catalog, &sessions, index_file);
+    let stats_map = index.stats_map();
+    let session_stats: Vec<&SessionStats> = sessions
+        .iter()
+        .filter_map(|item| stats_map.get(&item.key()))
+        .collect();
+
+    let summary = StatsSummary {
+        sessions: sessions.len(),
+        projects: catalog.project_groups.len(),
+        claude_sessions: sessions
+            .iter()
+            .filter(|item| item.source == SessionSource::Claude)
+            .count(),
+        codex_sessions: sessions
+            .iter()
+            .filter(|item| item.source == SessionSource::Codex)
+            .count(),
+        messages: session_stats.iter().map(|stats| stats.message_count).sum(),
+        total_tokens: session_stats.iter().map(|stats| stats.total_tokens).sum(),
+        cost_usd: session_stats
+            .iter()
+            .filter_map(|stats| stats.cost_usd)
+            .sum(),
+        models: top_counts(session_stats.iter().flat_map(|stats| stats.models.iter())),
+        tools: top_counts(session_stats.iter().flat_map(|stats| stats.tools.iter())),
+        unpriced_models: top_counts(
+            session_stats
+                .iter()
+                .flat_map(|stats| stats.unpriced_models.iter()),
+        ),
+    };
+
+    if args.json {
+        return write_json(out, &summary);
+    }
+    writeln!(
+        out,
+        "Sessions: {} (claude {}, codex {})",
+        summary.sessions, summary.claude_sessions, summary.codex_sessions
+    )?;
+    writeln!(out, "Projects: {}", summary.projects)?;
+    writeln!(out, "Messages: {}", summary.messages)?;
+    writeln!(out, "Tokens:   {}", summary.total_tokens)?;
+    writeln!(out, "Cost:     ${:.2}", summary.cost_usd)?;
+    for (label, counts) in [
+        ("Models", &summary.models),
+        ("Tools", &summary.tools),
+        ("Unpriced models", &summary.unpriced_models),
+    ] {
+        if counts.is_empty() {
+            continue;
+        }
+        writeln!(out, "{} (sessions):", label)?;
+        for count in counts {
+            writeln!(out, "  {:<32} {}", count.name, count.sessions)?;
+        }
+    }
+    Ok(())
+}
+
+/// 今月の予算ごとの支出を表示（--check では超過時にエラー）
+pub fn budget<W: Write>(
+    out: &mut W,
+    catalog: &SessionCatalog,
+    budgets: &[Budget],
+    args: &BudgetArgs,
+) -> Result<(), KathaError> {
+    if budgets.is_empty() {
+        writeln!(out, "No budgets configured (add [[budget]] to config.toml)")?;
+        return Ok(());
+    }
+    let report = catalog.budget_report(budgets, chrono::Local::now().date_naive());
+    write_budget_report(out, &report, args)
+}
+
+/// 予算の消化状況を書き出す
+fn write_budget_report<W: Write>(
+    out: &mut W,
+    report: &BudgetReport,
+    args: &BudgetArgs,
+) -> Result<(), KathaError> {
+    if args.json {
+        let month = BudgetMonth {
+            month: report.month.format("%Y-%m").to_string(),
+            budgets: report
+                .statuses
+                .iter()
+                .map(|status| BudgetSummary {
+                    name: status.budget.label(),
+                    project: status
+                        .budget
+                        .project
+                        .as_ref()
+                        .map(|path| path.display().to_string()),
+                    model: status.budget.model.clone(),
+                    monthly_usd: status.budget.monthly_usd,
+                    spent_usd: status.spent.usd,
+                    percent: status.ratio() * 100.0,
+                    status: status.level().label(),
+                    unpriced_models: status.spent.unpriced_models.clone(),
+                })
+                .collect(),
+        };
+        write_json(out, &month)?;
+    } else {
+        writeln!(
+            out,
+            "Month-to-date spend ({}):",
+            report.month.format("%Y-%m")
+        )?;
+        for status in &report.statuses {
+            writeln!(
+                out,
+                "  {:<32} {:>10} / {:<10} {:>4.0}%  {}",
+                status.budget.label(),
+                format!("${:.2}", status.spent.usd),
+                format!("${:.2}", status.budget.monthly_usd),
+                status.ratio() * 100.0,
+                status.level().label()
+            )?;
+            if !status.spent.unpriced_models.is_empty() {
+                writeln!(
+                    out,
+                    "    not counted (unpriced): {}",
+                    status.spent.unpriced_models.join(", ")
+                )?;
+            }
+        }
+        if report.failed_sessions > 0 {
+            writeln!(
+                out,
+                "Skipped {} unreadable sessions",
+                report.failed_sessions
+            )?;
+        }
+    }
+
+    let exceeded: Vec<String> = report
+        .exceeded()
+        .map(|status| status.budget.label())
+        .collect();
+    if args.check && !exceeded.is_empty() {
+        return Err(KathaError::BudgetExceeded(exceeded.join(", ")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ClaudePaths;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_catalog() -> (TempDir, SessionCatalog) {
+        let dir = TempDir::new().unwrap();
+        let history = [
+            r#"{"display":"fix the build","timestamp":1000,"project":"/work/demo","sessionId":"aaaa-1111"}"#,
+            r#"{"display":"add tests","timestamp":2000,"project":"/work/other","sessionId":"bbbb-2222"}"#,
+        ]
+        .join("\n");
+        fs::write(dir.path().join("history.jsonl"), history).unwrap();
+        let session_dir = dir.path().join("projects").join("-work-demo");
+        fs::create_dir_all(&session_dir).unwrap();
+        fs::write(
+            session_dir.join("aaaa-1111.jsonl"),
+            r#"{"type":"user","sessionId":"aaaa-1111","timestamp":"2025-01-01T00:00:00Z","message":{"role":"user","content":"fix the build please"}}"#,
+        )
+        .unwrap();
+
+        let paths = ClaudePaths::from_base_dir(dir.path().to_path_buf()).unwrap();
+        let catalog = SessionCatalog::from_paths(paths, None).unwrap();
+        (dir, catalog)
+    }
+
+    fn output(f: impl FnOnce(&mut Vec<u8>) -> Result<(), KathaError>) -> String {
+        let mut out = Vec::new();
+        f(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_list_command() {
+        let (_dir, catalog) = create_catalog();
+
+        let text = output(|out| list(out, &catalog, &ListArgs::default()));
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("bbbb-2222"));
+        assert!(lines[1].ends_with("demo  aaaa-1111  fix the build"));
+
+        let args = ListArgs {
+            json: true,
+            project: Some("DEMO".to_string()),
+            ..Default::default()
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&output(|out| list(out, &catalog, &args))).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 1);
+        assert_eq!(json[0]["session_id"], "aaaa-1111");
+        assert_eq!(json[0]["source"], "claude");
+    }
+
+    #[test]
+    fn test_show_and_export_commands() {
+        let (dir, catalog) = create_catalog();
+
+        let args = ShowArgs {
+            session_id: "aaaa".to_string(),
+            ..Default::default()
+        };
+        let text = output(|out| show(out, &catalog, &args));
+        assert!(text.contains("fix the build please"));
+
+        let args = ShowArgs {
+            session_id: "bbbb".to_string(),
+            ..Default::default()
+        };
+        let error = show(&mut Vec::new(), &catalog, &args).unwrap_err();
+        assert_eq!(error.exit_code(), 66);
+
+        let path = dir.path().join("out.json");
+        let args = ExportArgs {
+            session_id: "aaaa-1111".to_string(),
+            format: Some(ExportFormat::Json),
+            output: Some(path.clone()),
+            json: true,
+            ..Default::default()
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&output(|out| export(out, &catalog, &args))).unwrap();
+        assert_eq!(json["format"], "json");
+        assert!(
+            fs::read_to_string(&path)
+                .unwrap()
+                .contains("fix the build please")
+        );
+
+        // 既存ファイルは上書きしない
+        let error = export(&mut Vec::new(), &catalog, &args).unwrap_err();
+        assert!(matches!(error, KathaError::FileExists(_)));
+        assert_eq!(error.exit_code(), 73);
+    }
+
+    #[test]
+    fn test_search_and_stats_commands() {
+        let (dir, catalog) = create_catalog();
+        let index_path = dir.path().join("cache").join("search-index.json");
+        let index_file = Some(index_path.as_path());
+        let search_ids = |args: SearchArgs| -> Vec<String> {
+            let args = SearchArgs { json: true, ..args };
+            let json: serde_json::Value =
+                serde_json::from_str(&output(|out| search(out, &catalog, index_file, &args)))
+                    .unwrap();
+            json.as_array()
+                .unwrap()
+                .iter()
+                .map(|item| item["session_id"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let args = SearchArgs {
+            query: "please".to_string(),
+            ..Default::default()
+        };
+        assert!(search_ids(args.clone()).is_empty());
+        let content = SearchArgs {
+            content: true,
+            ..args
+        };
+        assert_eq!(search_ids(content.clone()), vec!["aaaa-1111"]);
+        assert!(index_path.exists());
+        // 保存したインデックスから再検索
+        assert_eq!(search_ids(content), vec!["aaaa-1111"]);
+        assert_eq!(
+            search_ids(SearchArgs {
+                query: "-please".to_string(),
+                content: true,
+                ..Default::default()
+            }),
+            vec!["bbbb-2222"]
+        );
+
+        let text = output(|out| stats(out, &catalog, index_file, &StatsArgs::default()));
+        assert!(text.starts_with("Sessions: 2 (claude 2, codex 0)"));
+        assert!(text.contains("Messages: 1"));
+        let json: serde_json::Value = serde_json::from_str(&output(|out| {
+            stats(out, &catalog, None, &StatsArgs { json: true })
+        }))
+        .unwrap();
+        assert_eq!(json["projects"], 2);
+        assert_eq!(json["messages"], 1);
+    }
+
+    #[test]
+    fn test_budget_command() {
+        let (_dir, catalog) = create_catalog();
+        let text = output(|out| budget(out, &catalog, &[], &BudgetArgs::default()));
+        assert!(text.starts_with("No budgets configured"));
+
+        let budgets: Vec<Budget> = [None, Some("/work/demo")]
+            .into_iter()
+            .map(|project| Budget {
+                name: None,
+                project: project.map(PathBuf::from),
+                model: None,
+                monthly_usd: 10.0,
+                warn_percent: 80.0,
+            })
+            .collect();
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        let mut report = catalog.budget_report(&budgets, today);
+        assert_eq!(report.statuses[0].spent.usd, 0.0);
+        report.statuses[1].spent.usd = 12.5;
+
+        let args = BudgetArgs {
+            check: true,
+            json: false,
+        };
+        let mut out = Vec::new();
+        let error = write_budget_report(&mut out, &report, &args).unwrap_err();
+        assert_eq!(error.exit_code(), 1);
+        assert_eq!(error.user_message(), "Budget exceeded: /work/demo");
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("Month-to-date spend (2025-06):"));
+        assert!(text.contains("$12.50 / $10.00"));
+        assert!(text.contains("125%  exceeded"));
+
+        report.statuses[1].spent.usd = 8.0;
+        let args = BudgetArgs {
+            check: true,
+            json: true,
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&output(|out| write_budget_report(out, &report, &args))).unwrap();
+        assert_eq!(json["month"], "2025-06");
+        assert_eq!(json["budgets"][0]["name"], "global");
+        assert_eq!(json["budgets"][1]["project"], "/work/demo");
+        assert_eq!(json["budgets"][1]["status"], "warning");
+    }
+}
@@ -0,0 +1,76 @@
+pub mod args;
+pub mod commands;
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use tracing::warn;
+
+use crate::KathaError;
+use crate::config::{CachePaths, Config};
+use crate::data::SessionCatalog;
+
+pub use args::{
+    BudgetArgs, Cli, Command, ExportArgs, ListArgs, SearchArgs, ShowArgs, StatsArgs, USAGE,
+    parse_args,
+};
+
+/// 非対話のサブコマンドを実行して標準出力に書き出す
+/// TUI・ヘルプなど履歴を読まないコマンドは何もしない
+pub fn run(command: &Command, config: &Config) -> Result<(), KathaError> {
+    let catalog = match command {
+        Command::List(_)
+        | Command::Show(_)
+        | Command::Search(_)
+        | Command::Export(_)
+        | Command::Stats(_)
+        | Command::Budget(_) => SessionCatalog::load(&config.data)?,
+        _ => return Ok(()),
+    };
+
+    // エクスポートの既定値は設定ファイルから
+    let mut command = command.clone();
+    if let Command::Export(args) = &mut command {
+        args.format.get_or_insert(config.export.format);
+        if args.output.is_none() {
+            args.output = config.export.dir.clone();
+        }
+    }
+
+    // 検索インデックスは TUI と同じキャッシュディレクトリに保存
+    let cache_paths = CachePaths::new()
+        .inspect_err(|e| warn!("Search index cache disabled: {}", e))
+        .ok();
+    let index_file = cache_paths
+        .as_ref()
+        .map(|paths| paths.search_index_file.as_path());
+
+    let mut out = io::stdout().lock();
+    let result =
+        execute(&mut out, &catalog, index_file, config, &command).and_then(|_| Ok(out.flush()?));
+    match result {
+        // パイプ先が先に閉じた場合（katha list | head など）は正常終了扱い
+        Err(KathaError::IoError(e)) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+/// サブコマンドを実行
+/// index_file は search / stats が使う検索インデックスのファイル（None なら保存しない）
+pub fn execute<W: Write>(
+    out: &mut W,
+    catalog: &SessionCatalog,
+    index_file: Option<&Path>,
+    config: &Config,
+    command: &Command,
+) -> Result<(), KathaError> {
+    match command {
+        Command::List(args) => commands::list(out, catalog, args),
+        Command::Show(args) => commands::show(out, catalog, args),
+        Command::Search(args) => commands::search(out, catalog, index_file, args),
+        Command::Export(args) => commands::export(out, catalog, args),
+        Command::Stats(args) => commands::stats(out, catalog, index_file, args),
+        Command::Budget(args) => commands::budget(out, catalog, &config.budgets, args),
+        Command::Tui | Command::CountSessions | Command::Help | Command::Version => Ok(()),
+    }
+}
@@ -0,0 +1,518 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use tracing::warn;
+
+use crate::KathaError;
+use crate::config::paths::{ConfigPaths, expand_home};
+use crate::config::roots::DataRoots;
+use crate::domain::billing::DEFAULT_JPY_PER_USD;
+use crate::domain::{Budget, Currency, ExchangeRates, PricingTable};
+use crate::export::ExportFormat;
+use crate::search::{DatePreset, SortMode};
+use crate::tea::DEFAULT_LIST_WIDTH_PERCENT;
+use crate::theme::{AUTO_THEME, ColorSupport, NamedTheme, ThemeCycle, load_themes};
+use crate::tui::{KeybindingsConfig, Keymap};
+
+/// テーマを指定する環境変数（ui.theme より優先）
+pub const THEME_ENV: &str = "KATHA_THEME";
+
+/// 設定ファイル（config.toml）の内容
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// 表示
+    pub ui: UiConfig,
+    /// コスト表示
+    pub currency: CurrencyConfig,
+    /// セッション一覧
+    pub list: ListConfig,
+    /// エクスポート
+    pub export: ExportConfig,
+    /// 履歴を読み込むディレクトリ
+    pub data: DataRoots,
+    /// キーバインド（画面名 → キー → 操作名）
+    pub keybindings: KeybindingsConfig,
+    /// 月ごとの予算（[[budget]] の一覧）
+    #[serde(rename = "budget")]
+    pub budgets: Vec<Budget>,
+    /// 設定ディレクトリの themes/*.toml から読み込んだテーマ
+    #[serde(skip)]
+    pub user_themes: Vec<NamedTheme>,
+    /// 同梱の料金表に設定ディレクトリの pricing.toml を重ねた料金表
+    #[serde(skip)]
+    pub pricing: PricingTable,
+    /// 円換算レート・currency.rates・為替レートファイルをまとめたレート表
+    #[serde(skip)]
+    pub exchange_rates: ExchangeRates,
+}
+
+/// 表示の設定
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    /// テーマ名（KATHA_THEME が優先、auto は端末の色数に応じて選択）
+    pub theme: String,
+    /// 端末の色数（未指定なら環境変数から推定）
+    pub colors: Option<ColorSupport>,
+    /// 入力待ちのタイムアウト（ミリ秒）
+    pub poll_interval_ms: u64,
+    /// 2 ペイン表示での一覧の幅（%）
+    pub list_width_percent: u16,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            theme: AUTO_THEME.to_string(),
+            colors: None,
+            poll_interval_ms: 100,
+            list_width_percent: DEFAULT_LIST_WIDTH_PERCENT,
+        }
+    }
+}
+
+impl UiConfig {
+    /// 入力待ちのタイムアウト
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+
+    /// 端末の色数（設定 > 推定）
+    pub fn color_support(&self) -> ColorSupport {
+        self.colors.unwrap_or_else(ColorSupport::detect)
+    }
+}
+
+/// コスト表示の設定
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CurrencyConfig {
+    /// 起動時の表示通貨
+    pub default: Currency,
+    /// 円換算レート
+    pub jpy_per_usd: f64,
+    /// 通貨コード → 1 USD あたりの金額
+    pub rates: BTreeMap<Currency, f64>,
+    /// 為替レートファイル（[[rate]] の一覧、適用開始日を指定可能）
+    pub rates_file: Option<PathBuf>,
+    /// `u` で切り替える通貨（未指定なら USD とレートのある通貨）
+    pub cycle: Vec<Currency>,
+}
+
+impl Default for CurrencyConfig {
+    fn default() -> Self {
+        Self {
+            default: Currency::USD,
+            jpy_per_usd: DEFAULT_JPY_PER_USD,
+            rates: BTreeMap::new(),
+            rates_file: None,
+            cycle: Vec::new(),
+        }
+    }
+}
+
+impl CurrencyConfig {
+    /// レート表を作成（為替レートファイル < jpy_per_usd < rates の順に優先）
+    fn exchange_rates(&self) -> Result<ExchangeRates, String> {
+        let mut rates = match &self.rates_file {
+            Some(path) => std::fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| ExchangeRates::parse(&text))
+                .map_err(|e| format!("currency.rates_file: {}: {}", path.display(), e))?,
+            None => ExchangeRates::empty(),
+        };
+        rates.insert(Currency::JPY, self.jpy_per_usd, None);
+        for (&currency, &per_usd) in &self.rates {
+            rates.insert(currency, per_usd, None);
+        }
+        Ok(rates)
+    }
+}
+
+/// セッション一覧の設定
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ListConfig {
+    /// 並び順（`s` で切り替えた後は前回の並び順を優先）
+    pub sort: SortMode,
+    /// フィルタパネルの日付プリセット（"today", "7d", "2w" など）
+    pub date_presets: Vec<DatePreset>,
+}
+
+impl Default for ListConfig {
+    fn default() -> Self {
+        Self {
+            sort: SortMode::default(),
+            date_presets: DatePreset::defaults(),
+        }
+    }
+}
+
+/// エクスポートの設定
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportConfig {
+    /// 既定の形式
+    pub format: ExportFormat,
+    /// 出力先ディレクトリ（未指定ならカレントディレクトリ）
+    pub dir: Option<PathBuf>,
+}
+
+impl Config {
+    /// デフォルトの設定ファイルとテーマを読み込み（存在しない場合はデフォルト値）
+    pub fn load() -> Result<Self, KathaError> {
+        let paths = ConfigPaths::new()?;
+        let mut config = Self::from_file(&paths.config_file, &paths.themes_dir)?;
+        config.pricing =
+            PricingTable::load(&paths.pricing_file).map_err(KathaError::ConfigError)?;
+        Ok(config)
+    }
+
+    /// 指定ファイルとテーマディレクトリを読み込み（存在しない場合はデフォルト値）
+    pub fn from_file(path: &Path, themes_dir: &Path) -> Result<Self, KathaError> {
+        let user_themes = load_themes(themes_dir).map_err(KathaError::ConfigError)?;
+        let text = if path.exists() {
+            std::fs::read_to_string(path)?
+        } else {
+            String::new()
+        };
+        Self::parse_with_themes(&text, user_themes, path.parent())
+            .map_err(|e| KathaError::ConfigError(format!("{}: {}", path.display(), e)))
+    }
+
+    /// TOML 文字列を解析して検証
+    pub fn parse(text: &str) -> Result<Self, String> {
+        Self::parse_with_themes(text, Vec::new(), None)
+    }
+
+    /// ユーザー定義テーマを含めて解析・検証
+    /// 相対パスは base_dir（設定ファイルのディレクトリ）を基準に解決する
+    fn parse_with_themes(
+        text: &str,
+        user_themes: Vec<NamedTheme>,
+        base_dir: Option<&Path>,
+    ) -> Result<Self, String> {
+        let mut config: Self = toml::from_str(text).map_err(|e| e.message().to_string())?;
+        config.user_themes = user_themes;
+        config.validate()?;
+        let resolve = |path: &Path| match base_dir {
+            Some(base_dir) => base_dir.join(expand_home(path)),
+            None => expand_home(path),
+        };
+        config.export.dir = config.export.dir.as_deref().map(resolve);
+        config.currency.rates_file = config.currency.rates_file.as_deref().map(resolve);
+        for (i, budget) in config.budgets.iter_mut().enumerate() {
+            budget.project = budget.project.as_deref().map(expand_home);
+            // 履歴のプロジェクトパスは絶対パスなので相対パスは一致しない
+            if budget.project.as_deref().is_some_and(Path::is_relative) {
+                return Err(format!("budget[{}].project: must be an absolute path", i));
+            }
+        }
+        config.exchange_rates = config.currency.exchange_rates()?;
+        config.validate_currencies()?;
+        Ok(config)
+    }
+
+    /// 値の範囲を検証
+    fn validate(&self) -> Result<(), String> {
+        let mut themes = ThemeCycle::new(self.user_themes.clone(), ColorSupport::TrueColor);
+        if !themes.select(&self.ui.theme) {
+            return Err(format!(
+                "ui.theme: unknown theme '{}' (expected {}, {})",
+                self.ui.theme,
+                AUTO_THEME,
+                themes.names().collect::<Vec<_>>().join(", ")
+            ));
+        }
+        if !(10..=1000).contains(&self.ui.poll_interval_ms) {
+            return Err(format!(
+                "ui.poll_interval_ms: {} is out of range (10-1000)",
+                self.ui.poll_interval_ms
+            ));
+        }
+        if !(20..=80).contains(&self.ui.list_width_percent) {
+            return Err(format!(
+                "ui.list_width_percent: {} is out of range (20-80)",
+                self.ui.list_width_percent
+            ));
+        }
+        if !(self.currency.jpy_per_usd.is_finite() && self.currency.jpy_per_usd > 0.0) {
+            return Err(format!(
+                "currency.jpy_per_usd: {} must be a positive number",
+                self.currency.jpy_per_usd
+            ));
+        }
+        for (currency, per_usd) in &self.currency.rates {
+            if !(per_usd.is_finite() && *per_usd > 0.0) {
+                return Err(format!(
+                    "currency.rates.{}: {} must be a positive number",
+                    currency.label().to_lowercase(),
+                    per_usd
+                ));
+            }
+        }
+        for (i, budget) in self.budgets.iter().enumerate() {
+            budget
+                .validate()
+                .map_err(|e| format!("budget[{}].{}", i, e))?;
+        }
+        Keymap::from_config(&self.keybindings)?;
+        Ok(())
+    }
+
+    /// 表示通貨にレートがあるか検証
+    fn validate_currencies(&self) -> Result<(), String> {
+        let check = |key: &str, currency: Currency| {
+            if self.exchange_rates.contains(currency) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{}: no exchange rate for '{}' (add it to currency.rates or currency.rates_file)",
+                    key,
+                    currency.label().to_lowercase()
+                ))
+            }
+        };
+        check("currency.default", self.currency.default)?;
+        for &currency in &self.currency.cycle {
+            check("currency.cycle", currency)?;
+        }
+        Ok(())
+    }
+
+    /// `u` で切り替える通貨（currency.cycle、未指定なら USD とレートのある通貨）
+    pub fn currencies(&self) -> Vec<Currency> {
+        if !self.currency.cycle.is_empty() {
+            let mut currencies = Vec::new();
+            for &currency in &self.currency.cycle {
+                if !currencies.contains(&currency) {
+                    currencies.push(currency);
+                }
+            }
+            return currencies;
+        }
+        let mut currencies = vec![Currency::USD];
+        currencies.extend(self.exchange_rates.currencies());
+        currencies
+    }
+
+    /// 切り替え可能なテーマ（初期テーマは KATHA_THEME > ui.theme）
+    pub fn theme_cycle(&self) -> ThemeCycle {
+        let mut themes = ThemeCycle::new(self.user_themes.clone(), self.ui.color_support());
+        if let Some(name) = std::env::var(THEME_ENV)
+            .ok()
+            .filter(|name| !name.trim().is_empty())
+        {
+            if themes.select(name.trim()) {
+                return themes;
+            }
+            warn!("Unknown {} value: {}", THEME_ENV, name);
+        }
+        themes.select(&self.ui.theme);
+        themes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse(
+            r#"
+[ui]
+theme = "light"
+colors = "256"
+list_width_percent = 40
+
+[currency]
+default = "jpy"
+jpy_per_usd = 155.5
+rates = { eur = 0.92, GBP = 0.79 }
+cycle = ["usd", "eur", "jpy"]
+
+[list]
+sort = "oldest"
+date_presets = ["today", "3d", "2w"]
+
+[export]
+format = "json"
+dir = "/tmp/exports"
+
+[data]
+claude_dirs = ["~/.claude", "/archive/ci/.claude"]
+
+[keybindings.list]
+"x" = "quit"
+"q" = "none"
+
+[[budget]]
+monthly_usd = 200.0
+
+[[budget]]
+name = "katha"
+project = "/work/katha"
+model = "claude-opus"
+monthly_usd = 50
+warn_percent = 90
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.ui.theme, "light");
+        assert_eq!(config.ui.colors, Some(ColorSupport::Ansi256));
+        assert_eq!(config.ui.list_width_percent, 40);
+        assert_eq!(config.ui.poll_interval(), Duration::from_millis(100));
+        assert_eq!(config.currency.default, Currency::JPY);
+        assert_eq!(config.currency.jpy_per_usd, 155.5);
+        assert_eq!(
+            config.exchange_rates.per_usd(Currency::JPY, None),
+            Some(155.5)
+        );
+        assert_eq!(
+            config.exchange_rates.per_usd(Currency::GBP, None),
+            Some(0.79)
+        );
+        assert_eq!(
+            config.currencies(),
+            vec![Currency::USD, Currency::EUR, Currency::JPY]
+        );
+        assert_eq!(config.list.sort, SortMode::Oldest);
+        assert_eq!(
+            config.list.date_presets,
+            vec![
+                DatePreset::Today,
+                DatePreset::LastDays(3),
+                DatePreset::LastDays(14)
+            ]
+        );
+        assert_eq!(config.export.format, ExportFormat::Json);
+        assert_eq!(config.export.dir, Some(PathBuf::from("/tmp/exports")));
+        assert_eq!(
+            config.data.claude_dirs,
+            vec![
+                PathBuf::from("~/.claude"),
+                PathBuf::from("/archive/ci/.claude")
+            ]
+        );
+        assert!(config.data.codex_dirs.is_empty());
+        assert_eq!(config.keybindings["list"]["x"], "quit");
+        assert_eq!(config.budgets.len(), 2);
+        assert_eq!(config.budgets[0].label(), "global");
+        assert_eq!(config.budgets[0].warn_percent, 80.0);
+        assert_eq!(
+            config.budgets[1].project,
+            Some(PathBuf::from("/work/katha"))
+        );
+        assert_eq!(config.budgets[1].model.as_deref(), Some("claude-opus"));
+        assert_eq!(config.budgets[1].monthly_usd, 50.0);
+
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert_eq!(
+            Config::default().currencies(),
+            vec![Currency::USD, Currency::JPY]
+        );
+    }
+
+    #[test]
+    fn test_parse_config_rates_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rates.toml");
+        std::fs::write(
+            &path,
+            "[[rate]]\ncurrency = \"eur\"\nper_usd = 0.9\nfrom = \"2025-01-01\"\n",
+        )
+        .unwrap();
+        let config = Config::parse(&format!(
+            "[currency]\ndefault = \"eur\"\nrates_file = {:?}",
+            path.display().to_string()
+        ))
+        .unwrap();
+        assert_eq!(config.currency.default, Currency::EUR);
+        assert_eq!(
+            config.exchange_rates.per_usd(Currency::EUR, None),
+            Some(0.9)
+        );
+        assert_eq!(
+            config.currencies(),
+            vec![Currency::USD, Currency::EUR, Currency::JPY]
+        );
+
+        let missing = dir.path().join("missing.toml");
+        let error = Config::parse(&format!(
+            "[currency]\nrates_file = {:?}",
+            missing.display().to_string()
+        ))
+        .unwrap_err();
+        assert!(error.contains("currency.rates_file"), "{}", error);
+    }
+
+    #[test]
+    fn test_config_relative_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_file = dir.path().join("config.toml");
+        std::fs::write(
+            dir.path().join("rates.toml"),
+            "[[rate]]\ncurrency = \"eur\"\nper_usd = 0.9\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &config_file,
+            "[currency]\nrates_file = \"rates.toml\"\n[export]\ndir = \"exports\"\n",
+        )
+        .unwrap();
+
+        // 起動したディレクトリではなく設定ファイルのディレクトリを基準にする
+        let config = Config::from_file(&config_file, &dir.path().join("themes")).unwrap();
+        assert_eq!(
+            config.currency.rates_file,
+            Some(dir.path().join("rates.toml"))
+        );
+        assert_eq!(config.export.dir, Some(dir.path().join("exports")));
+        assert_eq!(
+            config.exchange_rates.per_usd(Currency::EUR, None),
+            Some(0.9)
+        );
+
+        std::fs::write(
+            &config_file,
+            "[[budget]]\nproject = \"work/app\"\nmonthly_usd = 5.0\n",
+        )
+        .unwrap();
+        let Err(KathaError::ConfigError(error)) =
+            Config::from_file(&config_file, &dir.path().join("themes"))
+        else {
+            panic!("relative budget project should be rejected");
+        };
+        assert!(error.contains("budget[0].project"), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_config_errors() {
+        for text in [
+            "[data]\nclaude_dir = \"x\"",
+            "[ui]\ntheme = \"neon\"",
+            "[ui]\npoll_interval_ms = 0",
+            "[ui]\ncolors = \"88\"",
+            "[ui]\nlist_width_percent = 95",
+            "[currency]\ndefault = \"eur\"",
+            "[currency]\njpy_per_usd = -1.0",
+            "[currency]\ndefault = \"euro\"",
+            "[currency]\nrates = { eur = 0.0 }",
+            "[currency]\ncycle = [\"usd\", \"gbp\"]",
+            "[list]\ndate_presets = [\"yesterday\"]",
+            "[export]\nformat = \"pdf\"",
+            "[keybindings.list]\n\"ctrl+t\" = \"quit\"",
+            "[[budget]]\nproject = \"/work\"",
+            "[[budget]]\nmonthly_usd = -5.0",
+            "[[budget]]\nmonthly_usd = 5.0\nwarn_percent = 120",
+            "[[budget]]\nmonthly_usd = 5.0\nprojects = \"/work\"",
+        ] {
+            assert!(Config::parse(text).is_err(), "{}", text);
+        }
+    }
+}
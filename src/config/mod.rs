@@ -1,3 +1,9 @@
+pub mod file;
 pub mod paths;
+pub mod roots;
+pub mod state;
 
-pub use paths::{ClaudePaths, CodexPaths};
+pub use file::Config;
+pub use paths::{CachePaths, ClaudePaths, CodexPaths, ConfigPaths, display_path, expand_home};
+pub use roots::DataRoots;
+pub use state::UiState;
@@ -1,6 +1,6 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use directories::BaseDirs;
+use directories::{BaseDirs, ProjectDirs};
 
 use crate::KathaError;
 
@@ -91,7 +91,7 @@ impl ClaudePaths {
     /// Claude Code が使用するディレクトリ名の形式に変換
     /// 注意: この変換は非可逆（'/', '.', '_' がすべて '-' になる）
     pub fn encode_project_path_for_fs(path: &str) -> String {
-        path.replace('/', "-").replace('.', "-").replace('_', "-")
+        path.replace(['/', '.', '_'], "-")
     }
 
     /// ファイルシステムからのデコード（Claude Code 形式）
@@ -149,6 +149,88 @@ impl CodexPaths {
     }
 }
 
+/// katha のキャッシュパス管理
+#[derive(Debug, Clone)]
+pub struct CachePaths {
+    pub cache_dir: PathBuf,
+    pub search_index_file: PathBuf,
+    /// 前回の UI 状態（並び順など）
+    pub state_file: PathBuf,
+}
+
+impl CachePaths {
+    /// デフォルトパスで初期化（OS 標準のキャッシュディレクトリ配下）
+    pub fn new() -> Result<Self, KathaError> {
+        let project_dirs = ProjectDirs::from("", "", "katha")
+            .ok_or_else(|| KathaError::ConfigError("Cannot find cache directory".into()))?;
+
+        Ok(Self::from_cache_dir(project_dirs.cache_dir().to_path_buf()))
+    }
+
+    /// 指定ディレクトリで初期化（ディレクトリは書き込み時に作成）
+    pub fn from_cache_dir(cache_dir: PathBuf) -> Self {
+        Self {
+            search_index_file: cache_dir.join("search-index.json"),
+            state_file: cache_dir.join("state.json"),
+            cache_dir,
+        }
+    }
+}
+
+/// katha の設定ファイルパス管理
+#[derive(Debug, Clone)]
+pub struct ConfigPaths {
+    pub config_dir: PathBuf,
+    pub config_file: PathBuf,
+    /// ユーザー定義テーマ（*.toml）のディレクトリ
+    pub themes_dir: PathBuf,
+    /// 同梱の料金表を上書きする料金ファイル
+    pub pricing_file: PathBuf,
+}
+
+impl ConfigPaths {
+    /// デフォルトパスで初期化（OS 標準の設定ディレクトリ配下）
+    pub fn new() -> Result<Self, KathaError> {
+        let project_dirs = ProjectDirs::from("", "", "katha")
+            .ok_or_else(|| KathaError::ConfigError("Cannot find config directory".into()))?;
+
+        Ok(Self::from_config_dir(
+            project_dirs.config_dir().to_path_buf(),
+        ))
+    }
+
+    /// 指定ディレクトリで初期化
+    pub fn from_config_dir(config_dir: PathBuf) -> Self {
+        Self {
+            config_file: config_dir.join("config.toml"),
+            themes_dir: config_dir.join("themes"),
+            pricing_file: config_dir.join("pricing.toml"),
+            config_dir,
+        }
+    }
+}
+
+/// 先頭の `~` をホームディレクトリに展開
+pub fn expand_home(path: &Path) -> PathBuf {
+    let Ok(rest) = path.strip_prefix("~") else {
+        return path.to_path_buf();
+    };
+    match BaseDirs::new() {
+        Some(base_dirs) => base_dirs.home_dir().join(rest),
+        None => path.to_path_buf(),
+    }
+}
+
+/// 表示用のパス（ホームディレクトリ配下は `~` で省略）
+pub fn display_path(path: &Path) -> String {
+    if let Some(base_dirs) = BaseDirs::new()
+        && let Ok(rest) = path.strip_prefix(base_dirs.home_dir())
+    {
+        return Path::new("~").join(rest).display().to_string();
+    }
+    path.display().to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,132 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::KathaError;
+use crate::config::paths::{ClaudePaths, CodexPaths, expand_home};
+
+/// 複数の Claude ディレクトリを指定する環境変数（パス区切りで列挙）
+pub const CLAUDE_DIRS_ENV: &str = "KATHA_CLAUDE_DIRS";
+/// 複数の Codex ディレクトリを指定する環境変数（パス区切りで列挙）
+pub const CODEX_DIRS_ENV: &str = "KATHA_CODEX_DIRS";
+/// Claude Code 自身が参照する設定ディレクトリの環境変数
+pub const CLAUDE_CONFIG_DIR_ENV: &str = "CLAUDE_CONFIG_DIR";
+/// Codex 自身が参照するホームディレクトリの環境変数
+pub const CODEX_HOME_ENV: &str = "CODEX_HOME";
+
+/// 履歴を読み込むデータディレクトリ（空の場合は ~/.claude, ~/.codex）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DataRoots {
+    /// Claude Code のディレクトリ
+    pub claude_dirs: Vec<PathBuf>,
+    /// Codex のディレクトリ
+    pub codex_dirs: Vec<PathBuf>,
+}
+
+impl DataRoots {
+    /// コマンドライン > 環境変数 > 設定ファイルの優先順で決定
+    pub fn resolve(cli: &DataRoots, config: &DataRoots) -> Self {
+        Self::resolve_with(cli, config, |key| std::env::var_os(key))
+    }
+
+    fn resolve_with(
+        cli: &DataRoots,
+        config: &DataRoots,
+        env: impl Fn(&str) -> Option<OsString>,
+    ) -> Self {
+        let from_env = |list_key: &str, single_key: &str| -> Vec<PathBuf> {
+            if let Some(value) = env(list_key) {
+                let dirs: Vec<PathBuf> = std::env::split_paths(&value)
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .collect();
+                if !dirs.is_empty() {
+                    return dirs;
+                }
+            }
+            env(single_key)
+                .filter(|value| !value.is_empty())
+                .map(|value| vec![PathBuf::from(value)])
+                .unwrap_or_default()
+        };
+        let pick = |candidates: [Vec<PathBuf>; 3]| -> Vec<PathBuf> {
+            candidates
+                .into_iter()
+                .find(|dirs| !dirs.is_empty())
+                .unwrap_or_default()
+                .iter()
+                .map(|dir| expand_home(dir))
+                .collect()
+        };
+
+        Self {
+            claude_dirs: pick([
+                cli.claude_dirs.clone(),
+                from_env(CLAUDE_DIRS_ENV, CLAUDE_CONFIG_DIR_ENV),
+                config.claude_dirs.clone(),
+            ]),
+            codex_dirs: pick([
+                cli.codex_dirs.clone(),
+                from_env(CODEX_DIRS_ENV, CODEX_HOME_ENV),
+                config.codex_dirs.clone(),
+            ]),
+        }
+    }
+
+    /// Claude のパス一覧（未指定ならデフォルトの ~/.claude）
+    pub fn claude_paths(&self) -> Result<Vec<ClaudePaths>, KathaError> {
+        if self.claude_dirs.is_empty() {
+            return Ok(vec![ClaudePaths::new()?]);
+        }
+        self.claude_dirs
+            .iter()
+            .map(|dir| ClaudePaths::from_base_dir(dir.clone()))
+            .collect()
+    }
+
+    /// Codex のパス一覧（未指定ならデフォルトの ~/.codex が存在する場合のみ）
+    pub fn codex_paths(&self) -> Result<Vec<CodexPaths>, KathaError> {
+        if self.codex_dirs.is_empty() {
+            return Ok(CodexPaths::new().ok().into_iter().collect());
+        }
+        self.codex_dirs
+            .iter()
+            .map(|dir| CodexPaths::from_base_dir(dir.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_resolve_data_roots_precedence() {
+        let roots = |claude: &[&str], codex: &[&str]| DataRoots {
+            claude_dirs: claude.iter().map(PathBuf::from).collect(),
+            codex_dirs: codex.iter().map(PathBuf::from).collect(),
+        };
+        let config = roots(&["/config/claude"], &["/config/codex"]);
+        let env: HashMap<&str, &str> = HashMap::from([
+            (CLAUDE_DIRS_ENV, "/env/a:/env/b"),
+            (CLAUDE_CONFIG_DIR_ENV, "/env/single"),
+            (CODEX_HOME_ENV, "/env/codex"),
+        ]);
+        let lookup = |key: &str| env.get(key).map(OsString::from);
+
+        // 環境変数は設定ファイルより優先（一覧指定は単一指定より優先）
+        let resolved = DataRoots::resolve_with(&DataRoots::default(), &config, lookup);
+        assert_eq!(resolved, roots(&["/env/a", "/env/b"], &["/env/codex"]));
+
+        // コマンドラインは環境変数より優先（種類ごとに判定）
+        let cli = roots(&["/cli/claude"], &[]);
+        let resolved = DataRoots::resolve_with(&cli, &config, lookup);
+        assert_eq!(resolved, roots(&["/cli/claude"], &["/env/codex"]));
+
+        // 何も指定がなければ設定ファイル
+        let resolved = DataRoots::resolve_with(&DataRoots::default(), &config, |_| None);
+        assert_eq!(resolved, config);
+    }
+}
@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::KathaError;
+use crate::search::SortMode;
+
+/// 実行をまたいで保持する UI 状態（キャッシュディレクトリの state.json）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    /// 最後に選んだ並び順（未選択なら設定ファイルの list.sort）
+    pub sort: Option<SortMode>,
+    /// sort を保存したときの設定ファイルの list.sort
+    pub config_sort: Option<SortMode>,
+}
+
+impl UiState {
+    /// 起動時の並び順
+    /// 保存後に設定ファイルの list.sort が変わっていれば設定を優先する
+    pub fn sort_mode(&self, config_sort: SortMode) -> SortMode {
+        match self.sort {
+            Some(sort) if self.config_sort == Some(config_sort) => sort,
+            _ => config_sort,
+        }
+    }
+
+    /// 状態ファイルを読み込み（存在しない場合は既定値）
+    pub fn load(path: &Path) -> Result<Self, KathaError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let bytes = fs::read(path)?;
+        serde_json::from_slice(&bytes).map_err(|e| KathaError::JsonError(e.to_string()))
+    }
+
+    /// 状態ファイルを保存（一時ファイルに書いてから置き換える）
+    pub fn save(&self, path: &Path) -> Result<(), KathaError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let bytes = serde_json::to_vec(self).map_err(|e| KathaError::JsonError(e.to_string()))?;
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ui_state_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("state.json");
+        assert_eq!(UiState::load(&path).unwrap(), UiState::default());
+
+        let state = UiState {
+            sort: Some(SortMode::Cost),
+            config_sort: Some(SortMode::Newest),
+        };
+        state.save(&path).unwrap();
+        assert_eq!(UiState::load(&path).unwrap(), state);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            r#"{"sort":"cost","config_sort":"newest"}"#
+        );
+
+        fs::write(&path, "{").unwrap();
+        assert!(UiState::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_ui_state_sort_mode() {
+        let state = UiState {
+            sort: Some(SortMode::Cost),
+            config_sort: Some(SortMode::Newest),
+        };
+        assert_eq!(state.sort_mode(SortMode::Newest), SortMode::Cost);
+        // 設定ファイルの list.sort を変えたら保存した並び順は使わない
+        assert_eq!(state.sort_mode(SortMode::Oldest), SortMode::Oldest);
+
+        let legacy = UiState {
+            sort: Some(SortMode::Cost),
+            config_sort: None,
+        };
+        assert_eq!(legacy.sort_mode(SortMode::Newest), SortMode::Newest);
+        assert_eq!(
+            UiState::default().sort_mode(SortMode::Tokens),
+            SortMode::Tokens
+        );
+    }
+}
@@ -0,0 +1,507 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use tracing::debug;
+
+use crate::KathaError;
+use crate::config::{ClaudePaths, CodexPaths, DataRoots, display_path};
+use crate::data::{
+    CodexHistoryReader, CodexSessionInfo, CodexSessionReader, HistoryReader, SessionReader,
+};
+use crate::domain::{Budget, BudgetReport, Session, SessionSource};
+use crate::search::ContentSearchTarget;
+use crate::tea::{ProjectGroup, SessionListItem};
+
+#[derive(Debug, Clone)]
+struct HistoryItem {
+    session_id: String,
+    project_path: String,
+    display: String,
+    timestamp: i64,
+    source: SessionSource,
+    root: Option<String>,
+}
+
+fn datetime_from_millis(timestamp_ms: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(timestamp_ms)
+        .single()
+        .unwrap_or(DateTime::<Utc>::UNIX_EPOCH)
+}
+
+/// Claude Code のデータディレクトリ
+#[derive(Debug, Clone)]
+pub struct ClaudeRoot {
+    /// 表示用ラベル
+    pub label: String,
+    pub paths: ClaudePaths,
+}
+
+/// Codex のデータディレクトリ
+#[derive(Debug, Clone)]
+pub struct CodexRoot {
+    /// 表示用ラベル
+    pub label: String,
+    pub paths: CodexPaths,
+    /// セッション ID ごとのセッションファイル
+    pub sessions: HashMap<String, CodexSessionInfo>,
+}
+
+/// Claude Code / Codex の履歴から作成したセッション一覧
+#[derive(Debug, Clone, Default)]
+pub struct SessionCatalog {
+    /// Claude のデータディレクトリ
+    pub claude_roots: Vec<ClaudeRoot>,
+    /// Codex のデータディレクトリ
+    pub codex_roots: Vec<CodexRoot>,
+    /// プロジェクトごとのセッション（最新セッションの新しい順）
+    pub project_groups: Vec<ProjectGroup>,
+}
+
+impl SessionCatalog {
+    /// 指定されたデータディレクトリから読み込み
+    pub fn load(roots: &DataRoots) -> Result<Self, KathaError> {
+        Self::from_roots(roots.claude_paths()?, roots.codex_paths()?)
+    }
+
+    /// 単一の Claude / Codex ディレクトリから読み込み
+    pub fn from_paths(
+        paths: ClaudePaths,
+        codex_paths: Option<CodexPaths>,
+    ) -> Result<Self, KathaError> {
+        Self::from_roots(vec![paths], codex_paths.into_iter().collect())
+    }
+
+    /// 複数のディレクトリから読み込み
+    /// 同じ種類のディレクトリが複数ある場合、各セッションにディレクトリのラベルを付ける
+    pub fn from_roots(
+        claude_paths: Vec<ClaudePaths>,
+        codex_paths: Vec<CodexPaths>,
+    ) -> Result<Self, KathaError> {
+        let mut history_items: HashMap<String, Vec<HistoryItem>> = HashMap::new();
+
+        let label_claude = claude_paths.len() > 1;
+        let mut claude_roots = Vec::new();
+        for paths in claude_paths {
+            let label = display_path(&paths.base_dir);
+            let root = label_claude.then(|| label.clone());
+            if paths.history_exists() {
+                let entries = HistoryReader::read_all(&paths.history_file)?;
+                for entry in entries {
+                    // is_valid() でフィルタ済みなので session_id は常に存在
+                    if let Some(session_id) = entry.session_id.clone() {
+                        let project = entry.project().to_string();
+                        history_items
+                            .entry(project.clone())
+                            .or_default()
+                            .push(HistoryItem {
+                                session_id,
+                                project_path: project,
+                                display: entry.display().to_string(),
+                                timestamp: entry.timestamp.unwrap_or(0),
+                                source: SessionSource::Claude,
+                                root: root.clone(),
+                            });
+                    }
+                }
+            }
+            claude_roots.push(ClaudeRoot { label, paths });
+        }
+
+        let label_codex = codex_paths.len() > 1;
+        let mut codex_roots = Vec::new();
+        for paths in codex_paths {
+            let label = display_path(&paths.base_dir);
+            let root = label_codex.then(|| label.clone());
+            let sessions = CodexSessionReader::build_session_index(&paths.sessions_dir)?;
+
+            if paths.history_exists() {
+                let entries = CodexHistoryReader::read_all(&paths.history_file)?;
+                for entry in entries {
+                    // is_valid() でフィルタ済みなので session_id は常に存在
+                    if let Some(session_id) = entry.session_id.clone() {
+                        let project_path = sessions
+                            .get(&session_id)
+                            .and_then(|info: &CodexSessionInfo| info.cwd.clone())
+                            .unwrap_or_else(|| "Codex".to_string());
+                        history_items
+                            .entry(project_path.clone())
+                            .or_default()
+                            .push(HistoryItem {
+                                session_id,
+                                project_path,
+                                display: entry.text().to_string(),
+                                timestamp: entry.ts() * 1000,
+                                source: SessionSource::Codex,
+                                root: root.clone(),
+                            });
+                    }
+                }
+            }
+            codex_roots.push(CodexRoot {
+                label,
+                paths,
+                sessions,
+            });
+        }
+
+        Ok(Self {
+            claude_roots,
+            codex_roots,
+            project_groups: build_project_groups(history_items),
+        })
+    }
+
+    /// 全セッション（プロジェクト順）
+    pub fn sessions(&self) -> Vec<SessionListItem> {
+        self.project_groups
+            .iter()
+            .flat_map(|group| group.sessions.iter().cloned())
+            .collect()
+    }
+
+    /// セッション ID（または一意な前方一致）でセッションを検索
+    /// 複数のディレクトリに同じ ID がある場合は "<ディレクトリ>:<ID>" で指定する
+    pub fn find_session(&self, id: &str) -> Result<SessionListItem, KathaError> {
+        let mut sessions = self.sessions();
+        let id = match id.rsplit_once(':') {
+            Some((root, id)) => {
+                // シェルが ~ を展開した指定も一覧のラベルと同じ表記にそろえる
+                let root = display_path(Path::new(root));
+                sessions.retain(|session| self.root_label(session) == Some(root.as_str()));
+                if sessions.is_empty() {
+                    return Err(KathaError::InvalidArgument(format!(
+                        "Unknown data directory '{}'",
+                        root
+                    )));
+                }
+                id
+            }
+            None => id,
+        };
+
+        let exact: Vec<&SessionListItem> = sessions
+            .iter()
+            .filter(|session| session.session_id == id)
+            .collect();
+        let candidates = if exact.is_empty() {
+            sessions
+                .iter()
+                .filter(|session| !id.is_empty() && session.session_id.starts_with(id))
+                .collect()
+        } else {
+            exact
+        };
+        let Some(first) = candidates.first() else {
+            return Err(KathaError::SessionNotFound(id.to_string()));
+        };
+        if candidates
+            .iter()
+            .any(|session| session.session_id != first.session_id)
+        {
+            return Err(KathaError::InvalidArgument(format!(
+                "Session id '{}' is ambiguous",
+                id
+            )));
+        }
+
+        // 同じセッションの別ディレクトリのコピーは黙って選ばない
+        let mut roots: Vec<&str> = Vec::new();
+        for root in candidates
+            .iter()
+            .filter_map(|session| self.root_label(session))
+        {
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        if roots.len() > 1 {
+            return Err(KathaError::InvalidArgument(format!(
+                "Session '{}' exists in several data directories ({}); specify one as <directory>:{}",
+                first.session_id,
+                roots.join(", "),
+                first.session_id
+            )));
+        }
+        Ok((*first).clone())
+    }
+
+    /// セッションの読み込み元ディレクトリのラベル
+    fn root_label(&self, item: &SessionListItem) -> Option<&str> {
+        match item.source {
+            SessionSource::Claude => find_root(&self.claude_roots, item, |root| &root.label)
+                .map(|root| root.label.as_str()),
+            SessionSource::Codex => find_root(&self.codex_roots, item, |root| &root.label)
+                .map(|root| root.label.as_str()),
+        }
+    }
+
+    /// セッションファイルのパス
+    pub fn session_file_path(&self, item: &SessionListItem) -> Option<PathBuf> {
+        match item.source {
+            SessionSource::Claude => {
+                let root = find_root(&self.claude_roots, item, |root| &root.label)?;
+                Some(SessionReader::session_file_path(
+                    &root.paths.projects_dir,
+                    &item.project_path,
+                    &item.session_id,
+                ))
+            }
+            SessionSource::Codex => {
+                let root = find_root(&self.codex_roots, item, |root| &root.label)?;
+                Some(root.sessions.get(&item.session_id)?.path.clone())
+            }
+        }
+    }
+
+    /// セッションファイルを読み込み
+    pub fn read_session(&self, item: &SessionListItem) -> Result<Session, KathaError> {
+        let path = self
+            .session_file_path(item)
+            .ok_or_else(|| KathaError::SessionNotFound(item.session_id.clone()))?;
+        if !path.exists() {
+            return Err(KathaError::SessionNotFound(path.display().to_string()));
+        }
+
+        match item.source {
+            SessionSource::Claude => {
+                SessionReader::read_session(&path, &item.session_id, &item.project_path)
+            }
+            SessionSource::Codex => {
+                CodexSessionReader::read_session(&path, &item.session_id, &item.project_path)
+            }
+        }
+    }
+
+    /// 本文検索の対象セッションファイル一覧
+    pub fn content_search_targets(&self, sessions: &[SessionListItem]) -> Vec<ContentSearchTarget> {
+        sessions
+            .iter()
+            .filter_map(|item| {
+                Some(ContentSearchTarget {
+                    source: item.source,
+                    root: item.root.clone(),
+                    session_id: item.session_id.clone(),
+                    path: self.session_file_path(item)?,
+                })
+            })
+            .collect()
+    }
+
+    /// today を含む月の予算ごとの支出を集計（今月に更新のないセッションは読まない）
+    pub fn budget_report(&self, budgets: &[Budget], today: NaiveDate) -> BudgetReport {
+        let mut report = BudgetReport::new(budgets, today);
+        if budgets.is_empty() {
+            return report;
+        }
+        for item in self.sessions() {
+            if !report.may_include(item.datetime) {
+                continue;
+            }
+            match self.read_session(&item) {
+                Ok(session) => report.add_session(&item.project_path, &session),
+                Err(e) => {
+                    debug!("Skipping session {} in budget: {}", item.session_id, e);
+                    report.failed_sessions += 1;
+                }
+            }
+        }
+        report
+    }
+}
+
+/// セッションの読み込み元ディレクトリ（ラベルがなければ唯一のディレクトリ）
+fn find_root<'a, R>(
+    roots: &'a [R],
+    item: &SessionListItem,
+    label: impl Fn(&R) -> &String,
+) -> Option<&'a R> {
+    match &item.root {
+        Some(root) => roots.iter().find(|r| label(r) == root),
+        None => roots.first(),
+    }
+}
+
+/// 履歴エントリをプロジェクトごとにまとめる
+fn build_project_groups(history_items: HashMap<String, Vec<HistoryItem>>) -> Vec<ProjectGroup> {
+    let mut project_groups: Vec<ProjectGroup> = Vec::new();
+
+    for (project, mut entries) in history_items {
+        entries.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+        let project_name = project.rsplit('/').next().unwrap_or(&project).to_string();
+
+        // 最も古いエントリがセッション最初のプロンプト
+        let mut first_prompts: HashMap<(SessionSource, Option<String>, String), &str> =
+            HashMap::new();
+        for entry in &entries {
+            first_prompts.insert(
+                (entry.source, entry.root.clone(), entry.session_id.clone()),
+                &entry.display,
+            );
+        }
+
+        // 全エントリを SessionListItem に変換
+        // history.jsonl is newest-first; keep only the latest entry per session.
+        let mut seen_session_keys: HashSet<(SessionSource, Option<String>, String)> =
+            HashSet::new();
+        let sessions: Vec<SessionListItem> = entries
+            .iter()
+            .filter(|entry| {
+                seen_session_keys.insert((
+                    entry.source,
+                    entry.root.clone(),
+                    entry.session_id.clone(),
+                ))
+            })
+            .map(|entry| {
+                let key = (entry.source, entry.root.clone(), entry.session_id.clone());
+                let first_user_message = first_prompts
+                    .get(&key)
+                    .map_or_else(|| entry.display.clone(), |display| display.to_string());
+                let datetime = datetime_from_millis(entry.timestamp);
+                let formatted_time = datetime.format("%Y-%m-%d %H:%M").to_string();
+
+                SessionListItem {
+                    session_id: entry.session_id.clone(),
+                    source: entry.source,
+                    root: entry.root.clone(),
+                    project_name: project_name.clone(),
+                    project_path: entry.project_path.clone(),
+                    latest_user_message: entry.display.clone(),
+                    first_user_message,
+                    formatted_time,
+                    datetime,
+                }
+            })
+            .collect();
+
+        if !sessions.is_empty() {
+            project_groups.push(ProjectGroup {
+                project_path: project.clone(),
+                project_name,
+                sessions,
+            });
+        }
+    }
+
+    // 各プロジェクトの最新セッションの時刻でソート（新しい順）
+    project_groups.sort_by(|a, b| {
+        let a_latest = a.sessions.first().map(|s| &s.datetime);
+        let b_latest = b.sessions.first().map(|s| &s.datetime);
+        b_latest.cmp(&a_latest)
+    });
+
+    project_groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_catalog(history: &str) -> (TempDir, SessionCatalog) {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("history.jsonl"), history).unwrap();
+        let paths = ClaudePaths::from_base_dir(dir.path().to_path_buf()).unwrap();
+        let catalog = SessionCatalog::from_paths(paths, None).unwrap();
+        (dir, catalog)
+    }
+
+    #[test]
+    fn test_catalog_groups_and_find_session() {
+        let history = [
+            r#"{"display":"first","timestamp":1000,"project":"/work/a","sessionId":"abc-111"}"#,
+            r#"{"display":"second","timestamp":3000,"project":"/work/b","sessionId":"abd-222"}"#,
+            r#"{"display":"again","timestamp":2000,"project":"/work/a","sessionId":"abc-111"}"#,
+        ]
+        .join("\n");
+        let (_dir, catalog) = create_catalog(&history);
+
+        let projects: Vec<&str> = catalog
+            .project_groups
+            .iter()
+            .map(|group| group.project_name.as_str())
+            .collect();
+        assert_eq!(projects, vec!["b", "a"]);
+        assert_eq!(catalog.project_groups[1].sessions.len(), 1);
+        assert_eq!(
+            catalog.project_groups[1].sessions[0].latest_user_message,
+            "again"
+        );
+        assert_eq!(
+            catalog.project_groups[1].sessions[0].first_user_message,
+            "first"
+        );
+
+        assert_eq!(catalog.find_session("abd").unwrap().session_id, "abd-222");
+        assert!(matches!(
+            catalog.find_session("ab"),
+            Err(KathaError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            catalog.find_session("zzz"),
+            Err(KathaError::SessionNotFound(_))
+        ));
+        assert!(matches!(
+            catalog.read_session(&catalog.find_session("abc-111").unwrap()),
+            Err(KathaError::SessionNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_catalog_labels_sessions_by_root() {
+        let dirs: Vec<TempDir> = (0..2).map(|_| TempDir::new().unwrap()).collect();
+        for (dir, display) in dirs.iter().zip(["from a", "from b"]) {
+            let history = format!(
+                r#"{{"display":"{}","timestamp":1000,"project":"/work/app","sessionId":"same-id"}}"#,
+                display
+            );
+            fs::write(dir.path().join("history.jsonl"), history).unwrap();
+        }
+        let paths = dirs
+            .iter()
+            .map(|dir| ClaudePaths::from_base_dir(dir.path().to_path_buf()).unwrap())
+            .collect();
+        let catalog = SessionCatalog::from_roots(paths, vec![]).unwrap();
+
+        // 同じプロジェクト・同じ ID でもディレクトリごとに別セッション
+        assert_eq!(catalog.project_groups.len(), 1);
+        let sessions = &catalog.project_groups[0].sessions;
+        assert_eq!(sessions.len(), 2);
+        for session in sessions {
+            let root = session.root.as_deref().unwrap();
+            let expected = dirs
+                .iter()
+                .find(|dir| display_path(dir.path()) == root)
+                .unwrap();
+            let path = catalog.session_file_path(session).unwrap();
+            assert!(path.starts_with(expected.path()));
+        }
+
+        // 同じ ID はディレクトリを指定するまで選ばない
+        let labels: Vec<String> = dirs.iter().map(|dir| display_path(dir.path())).collect();
+        let Err(KathaError::InvalidArgument(message)) = catalog.find_session("same-id") else {
+            panic!("expected an ambiguity error");
+        };
+        assert!(labels.iter().all(|label| message.contains(label.as_str())));
+        assert!(matches!(
+            catalog.find_session("same"),
+            Err(KathaError::InvalidArgument(_))
+        ));
+        let picked = catalog
+            .find_session(&format!("{}:same-id", labels[1]))
+            .unwrap();
+        assert_eq!(picked.root.as_deref(), Some(labels[1].as_str()));
+        assert_eq!(picked.latest_user_message, "from b");
+        assert!(matches!(
+            catalog.find_session("/nowhere:same-id"),
+            Err(KathaError::InvalidArgument(_))
+        ));
+
+        // ディレクトリが 1 つならラベルなし
+        let (_dir, single) =
+            create_catalog(r#"{"display":"x","timestamp":1,"project":"/p","sessionId":"s"}"#);
+        assert_eq!(single.project_groups[0].sessions[0].root, None);
+    }
+}
@@ -71,7 +71,7 @@ impl CodexHistoryReader {
             }
         }
 
-        entries.sort_by(|a, b| b.ts().cmp(&a.ts()));
+        entries.sort_by_key(|e| std::cmp::Reverse(e.ts()));
         Ok(entries)
     }
 }
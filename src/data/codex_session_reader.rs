@@ -159,16 +159,13 @@ impl CodexSessionReader {
             };
 
             if parsed.line_type.as_deref() == Some("event_msg") {
-                if let Some(usage) = parse_token_count_usage(&parsed.payload) {
-                    if let Some(idx) = last_assistant_index {
-                        if let Some(entry) = entries.get_mut(idx) {
-                            if let Some(message) = entry.message.as_mut() {
-                                if message.usage.is_none() {
-                                    message.usage = Some(usage);
-                                }
-                            }
-                        }
-                    }
+                if let Some(usage) = parse_token_count_usage(&parsed.payload)
+                    && let Some(idx) = last_assistant_index
+                    && let Some(entry) = entries.get_mut(idx)
+                    && let Some(message) = entry.message.as_mut()
+                    && message.usage.is_none()
+                {
+                    message.usage = Some(usage);
                 }
                 continue;
             }
@@ -299,6 +296,10 @@ fn parse_token_count_usage(payload: &Value) -> Option<Usage> {
         return None;
     }
 
+    // Codex の input_tokens は cached_input_tokens を含むので、キャッシュ分を除いて数える
+    let input_tokens =
+        input_tokens.map(|input| input.saturating_sub(cached_input_tokens.unwrap_or(0)));
+
     Some(Usage {
         input_tokens,
         output_tokens,
@@ -38,7 +38,7 @@ impl HistoryReader {
             }
         }
 
-        entries.sort_by(|a, b| b.datetime().cmp(&a.datetime()));
+        entries.sort_by_key(|e| std::cmp::Reverse(e.datetime()));
         Ok(entries)
     }
 
@@ -1,8 +1,10 @@
+pub mod catalog;
 pub mod codex_history_reader;
 pub mod codex_session_reader;
 pub mod history_reader;
 pub mod session_reader;
 
+pub use catalog::SessionCatalog;
 pub use codex_history_reader::{CodexHistoryEntry, CodexHistoryReader};
 pub use codex_session_reader::{CodexSessionInfo, CodexSessionReader};
 pub use history_reader::HistoryReader;
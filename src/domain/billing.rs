@@ -1,6 +1,11 @@
+use chrono::{DateTime, Utc};
+
+pub use crate::domain::currency::Currency;
 use crate::domain::message::Usage;
+use crate::domain::pricing;
 
-const JPY_PER_USD: f64 = 150.0;
+/// 円換算レートの既定値
+pub const DEFAULT_JPY_PER_USD: f64 = 150.0;
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct UsageSummary {
@@ -11,147 +16,114 @@ pub struct UsageSummary {
     pub has_data: bool,
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct CostSummary {
     pub usd: f64,
     pub has_unknown: bool,
     pub has_data: bool,
+    /// トークンの種類ごとの内訳
+    pub breakdown: CostBreakdown,
+    /// 料金表にないモデル（出現順、重複なし）
+    pub unpriced_models: Vec<String>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Currency {
-    Usd,
-    Jpy,
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostRate {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+    /// キャッシュ書き込みトークンの入力料金に対する倍率
+    pub cache_write_multiplier: f64,
+    /// キャッシュ読み込みトークン（Codex の cached input）の入力料金に対する倍率
+    pub cache_read_multiplier: f64,
 }
 
-impl Currency {
-    pub fn toggle(self) -> Self {
-        match self {
-            Currency::Usd => Currency::Jpy,
-            Currency::Jpy => Currency::Usd,
+impl CostRate {
+    /// キャッシュの割増・割引のない料金
+    pub fn new(input_per_million: f64, output_per_million: f64) -> Self {
+        Self {
+            input_per_million,
+            output_per_million,
+            cache_write_multiplier: 1.0,
+            cache_read_multiplier: 1.0,
         }
     }
 
-    pub fn label(self) -> &'static str {
-        match self {
-            Currency::Usd => "USD",
-            Currency::Jpy => "JPY",
-        }
-    }
-
-    pub fn format_cost(self, usd: f64) -> String {
-        match self {
-            Currency::Usd => format!("${usd:.4}"),
-            Currency::Jpy => format!("¥{:.0}", usd * JPY_PER_USD),
+    /// トークンの種類ごとのコスト（USD）
+    pub fn cost(&self, usage: &Usage) -> CostBreakdown {
+        let per_token = |tokens: Option<u64>, per_million: f64| {
+            tokens.unwrap_or(0) as f64 * per_million / 1_000_000.0
+        };
+        CostBreakdown {
+            input: per_token(usage.input_tokens, self.input_per_million),
+            cache_write: per_token(
+                usage.cache_creation_input_tokens,
+                self.input_per_million * self.cache_write_multiplier,
+            ),
+            cache_read: per_token(
+                usage.cache_read_input_tokens,
+                self.input_per_million * self.cache_read_multiplier,
+            ),
+            output: per_token(usage.output_tokens, self.output_per_million),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct CostRate {
-    pub input_per_million: f64,
-    pub output_per_million: f64,
+/// トークンの種類ごとのコスト（USD）
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CostBreakdown {
+    /// キャッシュされていない入力
+    pub input: f64,
+    /// キャッシュ書き込み
+    pub cache_write: f64,
+    /// キャッシュ読み込み
+    pub cache_read: f64,
+    /// 出力
+    pub output: f64,
 }
 
-pub fn cost_rate_for_model(model: &str) -> Option<CostRate> {
-    let model = model.to_lowercase();
-    // Claude 4.x (Opus 4/4.5)
-    if model.contains("claude-4-5-opus")
-        || model.contains("claude-4-opus")
-        || model.contains("opus-4-5")
-        || model.contains("opus-4")
-    {
-        return Some(CostRate {
-            input_per_million: 5.0,
-            output_per_million: 25.0,
-        });
-    }
-    // Claude 3.x
-    if model.contains("claude-3-5-sonnet") || model.contains("claude-3-7-sonnet") {
-        return Some(CostRate {
-            input_per_million: 3.0,
-            output_per_million: 15.0,
-        });
-    }
-    if model.contains("claude-3-5-haiku") || model.contains("claude-3-haiku") {
-        return Some(CostRate {
-            input_per_million: 0.25,
-            output_per_million: 1.25,
-        });
-    }
-    if model.contains("claude-3-opus") {
-        return Some(CostRate {
-            input_per_million: 15.0,
-            output_per_million: 75.0,
-        });
-    }
-    if model.contains("claude-3-sonnet") {
-        return Some(CostRate {
-            input_per_million: 3.0,
-            output_per_million: 15.0,
-        });
-    }
-    // Codex / GPT-5.x
-    if model.contains("gpt-5.2-pro") {
-        return Some(CostRate {
-            input_per_million: 21.0,
-            output_per_million: 168.0,
-        });
-    }
-    if model.contains("gpt-5-2-pro") {
-        return Some(CostRate {
-            input_per_million: 21.0,
-            output_per_million: 168.0,
-        });
+impl CostBreakdown {
+    /// キャッシュ（書き込み + 読み込み）
+    pub fn cache(&self) -> f64 {
+        self.cache_write + self.cache_read
     }
-    if model.contains("gpt-5-pro") {
-        return Some(CostRate {
-            input_per_million: 15.0,
-            output_per_million: 120.0,
-        });
-    }
-    if model.contains("gpt-5.2") || model.contains("gpt-5-2") {
-        return Some(CostRate {
-            input_per_million: 1.75,
-            output_per_million: 14.0,
-        });
-    }
-    if model.contains("gpt-5-mini") {
-        return Some(CostRate {
-            input_per_million: 0.25,
-            output_per_million: 2.0,
-        });
-    }
-    if model.contains("gpt-5-nano") {
-        return Some(CostRate {
-            input_per_million: 0.05,
-            output_per_million: 0.4,
-        });
+
+    /// 合計
+    pub fn total(&self) -> f64 {
+        self.input + self.cache() + self.output
     }
-    if model.contains("gpt-5") {
-        return Some(CostRate {
-            input_per_million: 1.25,
-            output_per_million: 10.0,
-        });
+
+    /// 加算
+    pub fn add(&mut self, other: &CostBreakdown) {
+        self.input += other.input;
+        self.cache_write += other.cache_write;
+        self.cache_read += other.cache_read;
+        self.output += other.output;
     }
-    None
 }
 
-pub fn estimate_cost_usd(model: &str, usage: &Usage) -> Option<f64> {
-    let rate = cost_rate_for_model(model)?;
-    let input_tokens = usage.total_input_tokens() as f64;
-    let output_tokens = usage.total_output_tokens() as f64;
-    let input_cost = input_tokens * rate.input_per_million / 1_000_000.0;
-    let output_cost = output_tokens * rate.output_per_million / 1_000_000.0;
-    Some(input_cost + output_cost)
+/// 現在の料金表でのモデルの料金
+pub fn cost_rate_for_model(model: &str) -> Option<CostRate> {
+    pricing::current().rate(model, None)
+}
+
+/// 指定日時に適用される料金でトークンの種類ごとのコストを見積もり
+pub fn estimate_cost_breakdown(
+    model: &str,
+    usage: &Usage,
+    at: Option<DateTime<Utc>>,
+) -> Option<CostBreakdown> {
+    Some(pricing::current().rate(model, at)?.cost(usage))
+}
+
+/// 指定日時に適用される料金でコストを見積もり
+pub fn estimate_cost_usd(model: &str, usage: &Usage, at: Option<DateTime<Utc>>) -> Option<f64> {
+    estimate_cost_breakdown(model, usage, at).map(|breakdown| breakdown.total())
 }
 
 pub fn format_tokens(count: u64) -> String {
     let s = count.to_string();
     let mut result = String::with_capacity(s.len() + s.len() / 3);
-    let mut chars = s.chars().rev().enumerate().peekable();
-
-    while let Some((i, ch)) = chars.next() {
+    for (i, ch) in s.chars().rev().enumerate() {
         if i > 0 && i % 3 == 0 {
             result.push(',');
         }
@@ -165,121 +137,40 @@ pub fn format_tokens(count: u64) -> String {
 mod tests {
     use super::*;
 
+    fn claude(input: f64, output: f64) -> CostRate {
+        CostRate {
+            cache_write_multiplier: 1.25,
+            cache_read_multiplier: 0.1,
+            ..CostRate::new(input, output)
+        }
+    }
+
+    fn openai(input: f64, output: f64, cache_read: f64) -> CostRate {
+        CostRate {
+            cache_read_multiplier: cache_read,
+            ..CostRate::new(input, output)
+        }
+    }
+
     #[test]
     fn test_cost_rate_for_model_variants() {
         let cases = [
-            (
-                "claude-4-5-opus",
-                CostRate {
-                    input_per_million: 5.0,
-                    output_per_million: 25.0,
-                },
-            ),
-            (
-                "claude-4-opus",
-                CostRate {
-                    input_per_million: 5.0,
-                    output_per_million: 25.0,
-                },
-            ),
-            (
-                "gpt-5.2-pro",
-                CostRate {
-                    input_per_million: 21.0,
-                    output_per_million: 168.0,
-                },
-            ),
-            (
-                "gpt-5-2-pro",
-                CostRate {
-                    input_per_million: 21.0,
-                    output_per_million: 168.0,
-                },
-            ),
-            (
-                "gpt-5-pro",
-                CostRate {
-                    input_per_million: 15.0,
-                    output_per_million: 120.0,
-                },
-            ),
-            (
-                "gpt-5.2",
-                CostRate {
-                    input_per_million: 1.75,
-                    output_per_million: 14.0,
-                },
-            ),
-            (
-                "gpt-5-2",
-                CostRate {
-                    input_per_million: 1.75,
-                    output_per_million: 14.0,
-                },
-            ),
-            (
-                "gpt-5",
-                CostRate {
-                    input_per_million: 1.25,
-                    output_per_million: 10.0,
-                },
-            ),
-            (
-                "gpt-5-mini",
-                CostRate {
-                    input_per_million: 0.25,
-                    output_per_million: 2.0,
-                },
-            ),
-            (
-                "gpt-5-nano",
-                CostRate {
-                    input_per_million: 0.05,
-                    output_per_million: 0.4,
-                },
-            ),
-            (
-                "claude-3-5-sonnet",
-                CostRate {
-                    input_per_million: 3.0,
-                    output_per_million: 15.0,
-                },
-            ),
-            (
-                "claude-3-7-sonnet",
-                CostRate {
-                    input_per_million: 3.0,
-                    output_per_million: 15.0,
-                },
-            ),
-            (
-                "claude-3-5-haiku",
-                CostRate {
-                    input_per_million: 0.25,
-                    output_per_million: 1.25,
-                },
-            ),
-            (
-                "claude-3-haiku",
-                CostRate {
-                    input_per_million: 0.25,
-                    output_per_million: 1.25,
-                },
-            ),
-            (
-                "claude-3-opus",
-                CostRate {
-                    input_per_million: 15.0,
-                    output_per_million: 75.0,
-                },
-            ),
-            (
-                "claude-3-sonnet",
-                CostRate {
-                    input_per_million: 3.0,
-                    output_per_million: 15.0,
-                },
-            ),
+            ("claude-4-5-opus", claude(5.0, 25.0)),
+            ("claude-4-opus", claude(15.0, 75.0)),
+            ("gpt-5.2-pro", openai(21.0, 168.0, 1.0)),
+            ("gpt-5-2-pro", openai(21.0, 168.0, 1.0)),
+            ("gpt-5-pro", openai(15.0, 120.0, 1.0)),
+            ("gpt-5.2", openai(1.75, 14.0, 0.1)),
+            ("gpt-5-2", openai(1.75, 14.0, 0.1)),
+            ("gpt-5", openai(1.25, 10.0, 0.1)),
+            ("gpt-5-mini", openai(0.25, 2.0, 0.1)),
+            ("gpt-5-nano", openai(0.05, 0.4, 0.1)),
+            ("claude-3-5-sonnet", claude(3.0, 15.0)),
+            ("claude-3-7-sonnet", claude(3.0, 15.0)),
+            ("claude-3-5-haiku", claude(0.8, 4.0)),
+            ("claude-3-haiku", claude(0.25, 1.25)),
+            ("claude-3-opus", claude(15.0, 75.0)),
+            ("claude-3-sonnet", claude(3.0, 15.0)),
         ];
 
         for (model, expected) in cases {
@@ -287,4 +178,32 @@ mod tests {
             assert_eq!(actual, Some(expected));
         }
     }
+
+    #[test]
+    fn test_estimate_cost_by_token_class() {
+        let usage = Usage {
+            input_tokens: Some(1_000_000),
+            output_tokens: Some(100_000),
+            cache_creation_input_tokens: Some(1_000_000),
+            cache_read_input_tokens: Some(10_000_000),
+        };
+        let breakdown = estimate_cost_breakdown("claude-sonnet-4-5", &usage, None).unwrap();
+        assert!((breakdown.input - 3.0).abs() < 1e-9);
+        assert!((breakdown.cache_write - 3.75).abs() < 1e-9);
+        assert!((breakdown.cache_read - 3.0).abs() < 1e-9);
+        assert!((breakdown.output - 1.5).abs() < 1e-9);
+        let total = estimate_cost_usd("claude-sonnet-4-5", &usage, None).unwrap();
+        assert!((total - 11.25).abs() < 1e-9);
+
+        // Codex の cached input
+        let usage = Usage {
+            input_tokens: Some(200_000),
+            output_tokens: Some(0),
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: Some(800_000),
+        };
+        let breakdown = estimate_cost_breakdown("gpt-5-codex", &usage, None).unwrap();
+        assert!((breakdown.input - 0.25).abs() < 1e-9);
+        assert!((breakdown.cache() - 0.1).abs() < 1e-9);
+    }
 }
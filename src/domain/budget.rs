@@ -0,0 +1,340 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, Utc};
+use serde::Deserialize;
+
+use super::billing::CostSummary;
+use super::session::Session;
+
+/// 警告を出す使用率（%）の既定値
+pub const DEFAULT_WARN_PERCENT: f64 = 80.0;
+
+fn default_warn_percent() -> f64 {
+    DEFAULT_WARN_PERCENT
+}
+
+/// 月ごとの予算（config.toml の [[budget]]）
+/// project・model を両方省略すると全体の予算
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Budget {
+    /// 表示名（未指定ならプロジェクト・モデルから作成）
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 対象プロジェクトのパス（配下のディレクトリを含む）
+    #[serde(default)]
+    pub project: Option<PathBuf>,
+    /// 対象モデル（前方一致）
+    #[serde(default)]
+    pub model: Option<String>,
+    /// 月の予算（USD）
+    pub monthly_usd: f64,
+    /// 警告を出す使用率（%）
+    #[serde(default = "default_warn_percent")]
+    pub warn_percent: f64,
+}
+
+impl Budget {
+    /// 表示名
+    pub fn label(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        match (&self.project, &self.model) {
+            (Some(project), Some(model)) => format!("{} ({})", project.display(), model),
+            (Some(project), None) => project.display().to_string(),
+            (None, Some(model)) => model.clone(),
+            (None, None) => "global".to_string(),
+        }
+    }
+
+    /// 値の範囲を検証
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.monthly_usd.is_finite() && self.monthly_usd > 0.0) {
+            return Err(format!(
+                "monthly_usd: {} must be a positive number",
+                self.monthly_usd
+            ));
+        }
+        if !(self.warn_percent > 0.0 && self.warn_percent <= 100.0) {
+            return Err(format!(
+                "warn_percent: {} is out of range (0-100)",
+                self.warn_percent
+            ));
+        }
+        if self.model.as_deref().is_some_and(str::is_empty) {
+            return Err("model: must not be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// プロジェクトのパスが対象か
+    pub fn matches_project(&self, project_path: &str) -> bool {
+        self.project
+            .as_deref()
+            .is_none_or(|project| Path::new(project_path).starts_with(project))
+    }
+
+    /// モデルが対象か（モデル指定の予算はモデル不明のエントリを含めない）
+    pub fn matches_model(&self, model: Option<&str>) -> bool {
+        match (&self.model, model) {
+            (None, _) => true,
+            (Some(prefix), Some(model)) => model.to_lowercase().starts_with(&prefix.to_lowercase()),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// 予算の消化状況
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BudgetLevel {
+    /// 警告の使用率未満
+    Ok,
+    /// 警告の使用率以上
+    Warning,
+    /// 予算超過
+    Exceeded,
+}
+
+impl BudgetLevel {
+    /// 表示名
+    pub fn label(self) -> &'static str {
+        match self {
+            BudgetLevel::Ok => "ok",
+            BudgetLevel::Warning => "warning",
+            BudgetLevel::Exceeded => "exceeded",
+        }
+    }
+}
+
+/// 予算ごとの今月の支出
+#[derive(Debug, Clone)]
+pub struct BudgetStatus {
+    /// 予算
+    pub budget: Budget,
+    /// 今月のコスト
+    pub spent: CostSummary,
+}
+
+impl BudgetStatus {
+    /// 予算に対する使用率（1.0 で予算どおり）
+    pub fn ratio(&self) -> f64 {
+        self.spent.usd / self.budget.monthly_usd
+    }
+
+    /// 消化状況
+    pub fn level(&self) -> BudgetLevel {
+        let percent = self.ratio() * 100.0;
+        if percent > 100.0 {
+            BudgetLevel::Exceeded
+        } else if percent >= self.budget.warn_percent {
+            BudgetLevel::Warning
+        } else {
+            BudgetLevel::Ok
+        }
+    }
+}
+
+/// 月初（ローカル時刻の日付）
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// 予算ごとの今月の支出の集計
+#[derive(Debug, Clone)]
+pub struct BudgetReport {
+    /// 集計対象の月の初日
+    pub month: NaiveDate,
+    /// 予算ごとの支出（設定の順）
+    pub statuses: Vec<BudgetStatus>,
+    /// 読み込めなかったセッション数
+    pub failed_sessions: usize,
+}
+
+impl BudgetReport {
+    /// today を含む月の集計を作成
+    pub fn new(budgets: &[Budget], today: NaiveDate) -> Self {
+        Self {
+            month: month_start(today),
+            statuses: budgets
+                .iter()
+                .map(|budget| BudgetStatus {
+                    budget: budget.clone(),
+                    spent: CostSummary::default(),
+                })
+                .collect(),
+            failed_sessions: 0,
+        }
+    }
+
+    /// 最終更新日時から今月のエントリを含みうるセッションか
+    pub fn may_include(&self, datetime: DateTime<Utc>) -> bool {
+        // 月をまたいで続いたセッションのため 1 日余裕を持たせる
+        let date = datetime.with_timezone(&Local).date_naive();
+        date.succ_opt().unwrap_or(date) >= self.month
+    }
+
+    /// セッションのうち今月のエントリのコストを対象の予算に加算
+    pub fn add_session(&mut self, project_path: &str, session: &Session) {
+        let statuses: Vec<&mut BudgetStatus> = self
+            .statuses
+            .iter_mut()
+            .filter(|status| status.budget.matches_project(project_path))
+            .collect();
+        if statuses.is_empty() {
+            return;
+        }
+        let month = self.month;
+        let entries: Vec<_> = session
+            .entries
+            .iter()
+            .filter(|entry| entry.is_assistant())
+            .filter(|entry| {
+                entry
+                    .datetime()
+                    .is_some_and(|dt| month_start(dt.with_timezone(&Local).date_naive()) == month)
+            })
+            .collect();
+        for status in statuses {
+            for entry in &entries {
+                let model = entry
+                    .message
+                    .as_ref()
+                    .and_then(|message| message.model.as_deref());
+                if status.budget.matches_model(model) {
+                    status.spent.add_entry(entry);
+                }
+            }
+        }
+    }
+
+    /// 予算を超過しているもの
+    pub fn exceeded(&self) -> impl Iterator<Item = &BudgetStatus> {
+        self.statuses
+            .iter()
+            .filter(|status| status.level() == BudgetLevel::Exceeded)
+    }
+
+    /// 使用率が最も高い予算
+    pub fn most_used(&self) -> Option<&BudgetStatus> {
+        self.statuses
+            .iter()
+            .max_by(|a, b| a.ratio().total_cmp(&b.ratio()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::message::{Message, MessageContent, Usage};
+    use crate::domain::session::SessionEntry;
+
+    fn entry(timestamp: &str, model: &str, tokens: u64) -> SessionEntry {
+        SessionEntry {
+            entry_type: Some("assistant".to_string()),
+            timestamp: Some(timestamp.to_string()),
+            message: Some(Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Text("hi".to_string()),
+                model: Some(model.to_string()),
+                usage: Some(Usage {
+                    input_tokens: Some(tokens),
+                    output_tokens: Some(0),
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
+                }),
+                id: None,
+                stop_reason: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn budget(project: Option<&str>, model: Option<&str>, monthly_usd: f64) -> Budget {
+        Budget {
+            name: None,
+            project: project.map(PathBuf::from),
+            model: model.map(str::to_string),
+            monthly_usd,
+            warn_percent: DEFAULT_WARN_PERCENT,
+        }
+    }
+
+    #[test]
+    fn test_budget_matching() {
+        let project = budget(Some("/work/app"), None, 10.0);
+        assert!(project.matches_project("/work/app"));
+        assert!(project.matches_project("/work/app/crates/core"));
+        assert!(!project.matches_project("/work/application"));
+        assert_eq!(project.label(), "/work/app");
+
+        let model = budget(None, Some("claude-sonnet"), 10.0);
+        assert!(model.matches_project("/anywhere"));
+        assert!(model.matches_model(Some("Claude-Sonnet-4-5")));
+        assert!(!model.matches_model(Some("gpt-5")));
+        assert!(!model.matches_model(None));
+        assert_eq!(budget(None, None, 1.0).label(), "global");
+
+        assert!(budget(None, None, 0.0).validate().is_err());
+        assert!(budget(None, Some(""), 1.0).validate().is_err());
+        assert!(budget(None, None, 1.0).validate().is_ok());
+    }
+
+    #[test]
+    fn test_budget_report_month_to_date() {
+        // 月の途中の日時（タイムゾーンによらず同じ月になる）
+        let this_month = "2025-06-15T12:00:00Z";
+        let last_month = "2025-05-15T12:00:00Z";
+        let today = chrono::DateTime::parse_from_rfc3339(this_month)
+            .unwrap()
+            .with_timezone(&Local)
+            .date_naive();
+        let session = Session::from_entries(
+            "s".to_string(),
+            "/work/app".to_string(),
+            vec![
+                // sonnet-4-5 の入力は 100 万トークンで $3
+                entry(this_month, "claude-sonnet-4-5", 1_000_000),
+                entry(this_month, "gpt-5", 0),
+                entry(last_month, "claude-sonnet-4-5", 10_000_000),
+            ],
+        );
+        let budgets = vec![
+            budget(None, None, 100.0),
+            budget(Some("/work/app"), None, 3.5),
+            budget(None, Some("claude-sonnet"), 2.0),
+            budget(Some("/work/other"), None, 1.0),
+        ];
+
+        let mut report = BudgetReport::new(&budgets, today);
+        report.add_session("/work/app", &session);
+
+        assert_eq!(report.month, NaiveDate::from_ymd_opt(2025, 6, 1).unwrap());
+        let spent: Vec<f64> = report
+            .statuses
+            .iter()
+            .map(|status| (status.spent.usd * 100.0).round() / 100.0)
+            .collect();
+        assert_eq!(spent, vec![3.0, 3.0, 3.0, 0.0]);
+        let levels: Vec<BudgetLevel> = report.statuses.iter().map(BudgetStatus::level).collect();
+        assert_eq!(
+            levels,
+            vec![
+                BudgetLevel::Ok,
+                BudgetLevel::Warning,
+                BudgetLevel::Exceeded,
+                BudgetLevel::Ok
+            ]
+        );
+        assert_eq!(report.exceeded().count(), 1);
+        assert_eq!(
+            report.most_used().unwrap().budget.model.as_deref(),
+            Some("claude-sonnet")
+        );
+
+        let old = chrono::DateTime::parse_from_rfc3339("2025-04-30T12:00:00Z").unwrap();
+        assert!(!report.may_include(old.with_timezone(&Utc)));
+        let recent = chrono::DateTime::parse_from_rfc3339(this_month).unwrap();
+        assert!(report.may_include(recent.with_timezone(&Utc)));
+    }
+}
@@ -0,0 +1,352 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Deserializer};
+
+use crate::domain::billing::DEFAULT_JPY_PER_USD;
+
+/// 表示通貨（ISO 4217 の通貨コード）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Currency([u8; 3]);
+
+impl Currency {
+    pub const USD: Currency = Currency(*b"USD");
+    pub const JPY: Currency = Currency(*b"JPY");
+    pub const EUR: Currency = Currency(*b"EUR");
+    pub const GBP: Currency = Currency(*b"GBP");
+
+    /// 通貨コード
+    pub fn label(&self) -> &str {
+        std::str::from_utf8(&self.0).unwrap_or("???")
+    }
+
+    /// 表示形式
+    fn format(self) -> CurrencyFormat {
+        let (symbol, position, group, decimal, minor_units) = match self.label() {
+            "USD" => ("$", SymbolPosition::Prefix, ",", ".", 2),
+            "EUR" => ("€", SymbolPosition::Suffix, ".", ",", 2),
+            "GBP" => ("£", SymbolPosition::Prefix, ",", ".", 2),
+            "JPY" => ("¥", SymbolPosition::Prefix, ",", ".", 0),
+            "CNY" => ("CN¥", SymbolPosition::Prefix, ",", ".", 2),
+            "KRW" => ("₩", SymbolPosition::Prefix, ",", ".", 0),
+            "INR" => ("₹", SymbolPosition::Prefix, ",", ".", 2),
+            "CHF" => ("CHF ", SymbolPosition::Prefix, "'", ".", 2),
+            "CAD" => ("CA$", SymbolPosition::Prefix, ",", ".", 2),
+            "AUD" => ("A$", SymbolPosition::Prefix, ",", ".", 2),
+            "NZD" => ("NZ$", SymbolPosition::Prefix, ",", ".", 2),
+            "HKD" => ("HK$", SymbolPosition::Prefix, ",", ".", 2),
+            "SGD" => ("S$", SymbolPosition::Prefix, ",", ".", 2),
+            "BRL" => ("R$", SymbolPosition::Prefix, ".", ",", 2),
+            "SEK" | "NOK" | "DKK" => ("kr", SymbolPosition::Suffix, " ", ",", 2),
+            _ => ("", SymbolPosition::Code, ",", ".", 2),
+        };
+        CurrencyFormat {
+            symbol,
+            position,
+            group,
+            decimal,
+            minor_units,
+        }
+    }
+
+    /// USD のコストをこの通貨で表示
+    /// 為替レートがない場合は USD で表示
+    pub fn format_cost(self, usd: f64, rates: &ExchangeRates, at: Option<DateTime<Utc>>) -> String {
+        match rates.per_usd(self, at) {
+            Some(rate) => self.format_amount(usd * rate),
+            None => Currency::USD.format_amount(usd),
+        }
+    }
+
+    /// 金額を通貨の書式で表示
+    /// 1 セント未満のコストも読めるよう、補助単位のある通貨は 2 桁多く表示
+    pub fn format_amount(self, amount: f64) -> String {
+        let format = self.format();
+        let decimals = if format.minor_units == 0 {
+            0
+        } else {
+            format.minor_units + 2
+        };
+        let text = format!("{:.*}", decimals, amount.abs());
+        let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+
+        let mut number = String::new();
+        for (i, ch) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                number.push_str(format.group);
+            }
+            number.push(ch);
+        }
+        if !fraction.is_empty() {
+            number.push_str(format.decimal);
+            number.push_str(fraction);
+        }
+
+        let sign = if amount < 0.0 && text.chars().any(|c| c != '0' && c != '.') {
+            "-"
+        } else {
+            ""
+        };
+        match format.position {
+            SymbolPosition::Prefix => format!("{}{}{}", sign, format.symbol, number),
+            SymbolPosition::Suffix => format!("{}{} {}", sign, number, format.symbol),
+            SymbolPosition::Code => format!("{}{} {}", sign, self.label(), number),
+        }
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::USD
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = String;
+
+    /// 3 文字の英字（大文字小文字を区別しない）
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s.trim().to_ascii_uppercase();
+        match code.as_bytes() {
+            [a, b, c] if code.bytes().all(|b| b.is_ascii_uppercase()) => Ok(Currency([*a, *b, *c])),
+            _ => Err(format!(
+                "invalid currency '{}' (expected an ISO 4217 code such as usd or eur)",
+                s
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// 通貨記号の位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SymbolPosition {
+    /// 数値の前（$1.00）
+    Prefix,
+    /// 数値の後（1,00 €）
+    Suffix,
+    /// 記号がない通貨は通貨コードを前に付ける（PLN 1.00）
+    Code,
+}
+
+/// 通貨の書式
+#[derive(Debug, Clone, Copy)]
+struct CurrencyFormat {
+    symbol: &'static str,
+    position: SymbolPosition,
+    /// 3 桁区切り
+    group: &'static str,
+    /// 小数点
+    decimal: &'static str,
+    /// 補助単位の桁数
+    minor_units: usize,
+}
+
+/// 為替レート（1 USD あたりの金額、適用開始日つき）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeRate {
+    pub currency: Currency,
+    pub per_usd: f64,
+    /// 適用開始日（なしなら最初から）
+    pub from: Option<NaiveDate>,
+}
+
+/// 為替レート表
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeRates {
+    rates: Vec<ExchangeRate>,
+}
+
+impl Default for ExchangeRates {
+    /// 円換算レートの既定値のみ
+    fn default() -> Self {
+        let mut rates = Self::empty();
+        rates.insert(Currency::JPY, DEFAULT_JPY_PER_USD, None);
+        rates
+    }
+}
+
+/// 為替レートファイルの内容
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RatesFile {
+    #[serde(default)]
+    rate: Vec<RateEntry>,
+}
+
+/// 為替レートファイルの [[rate]] エントリ
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RateEntry {
+    currency: Currency,
+    per_usd: f64,
+    /// 適用開始日（YYYY-MM-DD）
+    #[serde(default)]
+    from: Option<String>,
+}
+
+impl ExchangeRates {
+    /// 空のレート表
+    pub fn empty() -> Self {
+        Self { rates: Vec::new() }
+    }
+
+    /// レートを追加（同じ通貨・適用開始日のレートは置き換え）
+    pub fn insert(&mut self, currency: Currency, per_usd: f64, from: Option<NaiveDate>) {
+        self.rates
+            .retain(|rate| !(rate.currency == currency && rate.from == from));
+        self.rates.push(ExchangeRate {
+            currency,
+            per_usd,
+            from,
+        });
+    }
+
+    /// 別のレート表を重ねる
+    pub fn merge(&mut self, other: ExchangeRates) {
+        for rate in other.rates {
+            self.insert(rate.currency, rate.per_usd, rate.from);
+        }
+    }
+
+    /// 為替レートファイルの TOML を解析
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let file: RatesFile = toml::from_str(text).map_err(|e| e.message().to_string())?;
+        let mut rates = Self::empty();
+        for (index, entry) in file.rate.into_iter().enumerate() {
+            let error = |message: String| format!("rate[{}]: {}", index, message);
+            if !(entry.per_usd.is_finite() && entry.per_usd > 0.0) {
+                return Err(error(format!(
+                    "per_usd: {} must be a positive number",
+                    entry.per_usd
+                )));
+            }
+            let from = entry
+                .from
+                .map(|value| {
+                    NaiveDate::parse_from_str(&value, "%Y-%m-%d").map_err(|_| {
+                        error(format!(
+                            "from: invalid date '{}' (expected YYYY-MM-DD)",
+                            value
+                        ))
+                    })
+                })
+                .transpose()?;
+            rates.insert(entry.currency, entry.per_usd, from);
+        }
+        Ok(rates)
+    }
+
+    /// 指定日時に適用される 1 USD あたりの金額（日時が不明なら最新のレート）
+    /// 最初の適用開始日より前は最も古いレートを使う
+    pub fn per_usd(&self, currency: Currency, at: Option<DateTime<Utc>>) -> Option<f64> {
+        if currency == Currency::USD {
+            return Some(1.0);
+        }
+        let date = at.unwrap_or_else(Utc::now).date_naive();
+        let mut rates: Vec<&ExchangeRate> = self
+            .rates
+            .iter()
+            .filter(|rate| rate.currency == currency)
+            .collect();
+        rates.sort_by_key(|rate| rate.from);
+        rates
+            .iter()
+            .rev()
+            .find(|rate| rate.from.is_none_or(|from| from <= date))
+            .or(rates.first())
+            .map(|rate| rate.per_usd)
+    }
+
+    /// レートのある通貨（USD を含む）
+    pub fn contains(&self, currency: Currency) -> bool {
+        currency == Currency::USD || self.rates.iter().any(|rate| rate.currency == currency)
+    }
+
+    /// レートのある通貨（コード順、重複なし）
+    pub fn currencies(&self) -> Vec<Currency> {
+        let mut currencies: Vec<Currency> = self.rates.iter().map(|rate| rate.currency).collect();
+        currencies.sort();
+        currencies.dedup();
+        currencies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_currency() {
+        assert_eq!("usd".parse::<Currency>(), Ok(Currency::USD));
+        assert_eq!(" Eur ".parse::<Currency>(), Ok(Currency::EUR));
+        assert_eq!("PLN".parse::<Currency>().unwrap().label(), "PLN");
+        assert!("euro".parse::<Currency>().is_err());
+        assert!("u$d".parse::<Currency>().is_err());
+    }
+
+    #[test]
+    fn test_format_amount_per_currency() {
+        assert_eq!(Currency::USD.format_amount(0.1234), "$0.1234");
+        assert_eq!(Currency::USD.format_amount(1234.5), "$1,234.5000");
+        assert_eq!(Currency::EUR.format_amount(1234.5), "1.234,5000 €");
+        assert_eq!(Currency::GBP.format_amount(12.0), "£12.0000");
+        assert_eq!(Currency::JPY.format_amount(1234567.4), "¥1,234,567");
+        let chf: Currency = "chf".parse().unwrap();
+        assert_eq!(chf.format_amount(1234.0), "CHF 1'234.0000");
+        let pln: Currency = "pln".parse().unwrap();
+        assert_eq!(pln.format_amount(1.5), "PLN 1.5000");
+    }
+
+    #[test]
+    fn test_exchange_rates_by_date() {
+        let mut rates = ExchangeRates::parse(
+            r#"
+[[rate]]
+currency = "eur"
+per_usd = 0.95
+from = "2025-01-01"
+
+[[rate]]
+currency = "EUR"
+per_usd = 0.90
+from = "2025-06-01"
+"#,
+        )
+        .unwrap();
+        let at = |month| Some(Utc.with_ymd_and_hms(2025, month, 15, 0, 0, 0).unwrap());
+        assert_eq!(rates.per_usd(Currency::EUR, at(3)), Some(0.95));
+        assert_eq!(rates.per_usd(Currency::EUR, at(7)), Some(0.90));
+        assert_eq!(
+            rates.per_usd(
+                Currency::EUR,
+                Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+            ),
+            Some(0.95)
+        );
+        assert_eq!(rates.per_usd(Currency::USD, None), Some(1.0));
+        assert_eq!(rates.per_usd(Currency::GBP, None), None);
+        assert_eq!(Currency::GBP.format_cost(2.0, &rates, None), "$2.0000");
+
+        rates.merge(ExchangeRates::default());
+        assert_eq!(rates.currencies(), vec![Currency::EUR, Currency::JPY]);
+        assert_eq!(Currency::JPY.format_cost(2.0, &rates, None), "¥300");
+
+        assert!(ExchangeRates::parse("[[rate]]\ncurrency = \"eur\"\nper_usd = 0").is_err());
+        assert!(ExchangeRates::parse("[[rate]]\ncurrency = \"euro\"\nper_usd = 1").is_err());
+    }
+}
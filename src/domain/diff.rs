@@ -0,0 +1,193 @@
+use serde_json::Value;
+use similar::{ChangeTag, TextDiff};
+
+/// 差分の前後に表示する文脈行数
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// 差分行の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    /// ファイルヘッダー（--- / +++）
+    FileHeader,
+    /// ハンクヘッダー（@@ ... @@）
+    HunkHeader,
+    /// 変更なしの文脈行
+    Context,
+    /// 追加行
+    Added,
+    /// 削除行
+    Removed,
+}
+
+/// 差分の 1 行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// 記号（+ / - / 空白）を含む行テキスト
+    pub text: String,
+}
+
+impl DiffLine {
+    fn new(kind: DiffLineKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+        }
+    }
+}
+
+/// ファイル変更ツール（Edit / MultiEdit / Write）の差分
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    /// 対象ファイルのパス
+    pub path: String,
+    /// unified diff の行
+    pub lines: Vec<DiffLine>,
+}
+
+impl FileDiff {
+    /// ツール呼び出しの入力から差分を作成（ファイル変更ツール以外は None）
+    /// ハンクの行番号は old_string / new_string 内での相対位置
+    pub fn from_tool_use(name: &str, input: &Value) -> Option<Self> {
+        let path = string_field(input, "file_path")?;
+        let edits: Vec<(&str, &str)> = match name {
+            "Edit" => vec![(
+                string_field(input, "old_string")?,
+                string_field(input, "new_string")?,
+            )],
+            "MultiEdit" => input
+                .get("edits")?
+                .as_array()?
+                .iter()
+                .filter_map(|edit| {
+                    Some((
+                        string_field(edit, "old_string")?,
+                        string_field(edit, "new_string")?,
+                    ))
+                })
+                .collect(),
+            "Write" => vec![("", string_field(input, "content")?)],
+            _ => return None,
+        };
+
+        let old_label = if name == "Write" { "/dev/null" } else { path };
+        let mut lines = vec![
+            DiffLine::new(DiffLineKind::FileHeader, format!("--- {}", old_label)),
+            DiffLine::new(DiffLineKind::FileHeader, format!("+++ {}", path)),
+        ];
+        let header_len = lines.len();
+
+        for (old, new) in edits {
+            let diff = TextDiff::from_lines(old, new);
+            let mut unified = diff.unified_diff();
+            unified.context_radius(DIFF_CONTEXT_LINES);
+            for hunk in unified.iter_hunks() {
+                lines.push(DiffLine::new(
+                    DiffLineKind::HunkHeader,
+                    hunk.header().to_string(),
+                ));
+                for change in hunk.iter_changes() {
+                    let (kind, sign) = match change.tag() {
+                        ChangeTag::Equal => (DiffLineKind::Context, ' '),
+                        ChangeTag::Insert => (DiffLineKind::Added, '+'),
+                        ChangeTag::Delete => (DiffLineKind::Removed, '-'),
+                    };
+                    let value = change.value().trim_end_matches(['\n', '\r']);
+                    lines.push(DiffLine::new(kind, format!("{}{}", sign, value)));
+                }
+            }
+        }
+
+        if lines.len() == header_len {
+            return None;
+        }
+        Some(Self {
+            path: path.to_string(),
+            lines,
+        })
+    }
+
+    /// unified diff 形式の文字列
+    pub fn to_unified_string(&self) -> String {
+        let mut output = String::new();
+        for line in &self.lines {
+            output.push_str(&line.text);
+            output.push('\n');
+        }
+        output
+    }
+}
+
+fn string_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn texts(diff: &FileDiff) -> Vec<&str> {
+        diff.lines.iter().map(|line| line.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_edit_diff() {
+        let input = json!({
+            "file_path": "/src/main.rs",
+            "old_string": "fn main() {\n    println!(\"hi\");\n}",
+            "new_string": "fn main() {\n    println!(\"hello\");\n}",
+        });
+        let diff = FileDiff::from_tool_use("Edit", &input).unwrap();
+        assert_eq!(diff.path, "/src/main.rs");
+        assert_eq!(
+            texts(&diff),
+            vec![
+                "--- /src/main.rs",
+                "+++ /src/main.rs",
+                "@@ -1,3 +1,3 @@",
+                " fn main() {",
+                "-    println!(\"hi\");",
+                "+    println!(\"hello\");",
+                " }",
+            ]
+        );
+        assert_eq!(diff.lines[4].kind, DiffLineKind::Removed);
+        assert_eq!(diff.lines[5].kind, DiffLineKind::Added);
+        assert!(diff.to_unified_string().ends_with(" }\n"));
+    }
+
+    #[test]
+    fn test_multi_edit_and_write_diff() {
+        let input = json!({
+            "file_path": "/a.txt",
+            "edits": [
+                {"old_string": "one", "new_string": "1"},
+                {"old_string": "two", "new_string": "2"},
+            ],
+        });
+        let diff = FileDiff::from_tool_use("MultiEdit", &input).unwrap();
+        let hunks = diff
+            .lines
+            .iter()
+            .filter(|line| line.kind == DiffLineKind::HunkHeader)
+            .count();
+        assert_eq!(hunks, 2);
+
+        let input = json!({"file_path": "/b.txt", "content": "a\nb\n"});
+        let diff = FileDiff::from_tool_use("Write", &input).unwrap();
+        assert_eq!(
+            texts(&diff),
+            vec!["--- /dev/null", "+++ /b.txt", "@@ -0,0 +1,2 @@", "+a", "+b"]
+        );
+    }
+
+    #[test]
+    fn test_non_edit_tool_has_no_diff() {
+        let input = json!({"file_path": "/a.txt"});
+        assert!(FileDiff::from_tool_use("Read", &input).is_none());
+
+        let input = json!({"file_path": "/a.txt", "old_string": "x", "new_string": "x"});
+        assert!(FileDiff::from_tool_use("Edit", &input).is_none());
+    }
+}
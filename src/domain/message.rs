@@ -103,6 +103,23 @@ impl Usage {
     }
 }
 
+impl ToolResultContent {
+    /// テキスト部分を結合して取得
+    pub fn text(&self) -> String {
+        match self {
+            ToolResultContent::Text(text) => text.clone(),
+            ToolResultContent::Blocks(blocks) => blocks
+                .iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
 impl Message {
     /// 最初のテキストコンテンツを取得
     pub fn text_content(&self) -> Option<String> {
@@ -141,6 +158,22 @@ impl Message {
         }
     }
 
+    /// thinking ブロックのテキストを取得（空のものは除外）
+    pub fn thinking_texts(&self) -> Vec<&str> {
+        match &self.content {
+            MessageContent::Text(_) => vec![],
+            MessageContent::Blocks(blocks) => blocks
+                .iter()
+                .filter_map(|b| match b {
+                    ContentBlock::Thinking { thinking } if !thinking.trim().is_empty() => {
+                        Some(thinking.trim())
+                    }
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
     /// ツール呼び出しを取得
     pub fn tool_uses(&self) -> Vec<(&str, &str, &Value)> {
         match &self.content {
@@ -228,6 +261,30 @@ mod tests {
         assert_eq!(msg.all_text_content(), "First\nSecond");
     }
 
+    #[test]
+    fn test_message_thinking_texts() {
+        let msg = Message {
+            role: "assistant".to_string(),
+            content: MessageContent::Blocks(vec![
+                ContentBlock::Thinking {
+                    thinking: "  Let me check.\n".to_string(),
+                },
+                ContentBlock::Thinking {
+                    thinking: " ".to_string(),
+                },
+                ContentBlock::Text {
+                    text: "Done".to_string(),
+                },
+            ]),
+            model: None,
+            id: None,
+            stop_reason: None,
+            usage: None,
+        };
+        assert_eq!(msg.thinking_texts(), vec!["Let me check."]);
+        assert_eq!(msg.all_text_content(), "Done");
+    }
+
     #[test]
     fn test_clean_text_removes_command_tags() {
         let input = "<command-message>init</command-message>\n<command-name>/init</command-name>";
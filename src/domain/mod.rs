@@ -1,9 +1,23 @@
 pub mod billing;
+pub mod budget;
+pub mod currency;
+pub mod diff;
 pub mod history;
 pub mod message;
+pub mod pricing;
 pub mod session;
+pub mod snippet;
+pub mod tool;
+pub mod usage_report;
 
-pub use billing::{CostSummary, Currency, UsageSummary};
+pub use billing::{CostSummary, UsageSummary};
+pub use budget::{Budget, BudgetLevel, BudgetReport, BudgetStatus};
+pub use currency::{Currency, ExchangeRates};
+pub use diff::{DiffLine, DiffLineKind, FileDiff};
 pub use history::{HistoryEntry, PastedContent};
 pub use message::{ContentBlock, Message, MessageContent};
-pub use session::{Session, SessionEntry};
+pub use pricing::PricingTable;
+pub use session::{Session, SessionEntry, SessionKey, SessionSource, SessionStats};
+pub use snippet::{CodeSnippet, extract_fenced_code_blocks};
+pub use tool::{ToolResult, ToolResults, collect_tool_results, summarize_tool_input};
+pub use usage_report::{UsageReport, UsageTotals};
@@ -0,0 +1,365 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::domain::billing::CostRate;
+
+/// 同梱の料金表
+const BUNDLED_PRICING: &str = include_str!("pricing.toml");
+
+/// 起動時に読み込んだ料金表
+static PRICING: OnceLock<PricingTable> = OnceLock::new();
+
+/// 料金ファイル（pricing.toml）の内容
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PricingFile {
+    #[serde(default)]
+    price: Vec<PriceEntry>,
+}
+
+/// 料金ファイルの [[price]] エントリ
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PriceEntry {
+    /// モデル ID に含まれる文字列（大文字小文字を区別しない）
+    models: Vec<String>,
+    /// 入力 100 万トークンあたりの USD
+    input: f64,
+    /// 出力 100 万トークンあたりの USD
+    output: f64,
+    /// キャッシュ書き込みの入力料金に対する倍率
+    #[serde(default = "default_multiplier")]
+    cache_write: f64,
+    /// キャッシュ読み込み（cached input）の入力料金に対する倍率
+    #[serde(default = "default_multiplier")]
+    cache_read: f64,
+    /// 適用開始日（YYYY-MM-DD）
+    #[serde(default)]
+    from: Option<String>,
+    /// 適用終了日（YYYY-MM-DD、この日は含まない）
+    #[serde(default)]
+    until: Option<String>,
+}
+
+fn default_multiplier() -> f64 {
+    1.0
+}
+
+/// モデルの料金（適用期間つき）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelPrice {
+    /// モデル ID のパターン（小文字）
+    pub patterns: Vec<String>,
+    /// 料金
+    pub rate: CostRate,
+    /// 適用開始日
+    pub from: Option<NaiveDate>,
+    /// 適用終了日（この日は含まない）
+    pub until: Option<NaiveDate>,
+}
+
+impl ModelPrice {
+    /// 指定日に適用される料金か
+    fn in_force(&self, date: NaiveDate) -> bool {
+        self.from.is_none_or(|from| from <= date) && self.until.is_none_or(|until| date < until)
+    }
+
+    /// モデル ID に一致する最長のパターンの長さ
+    fn match_len(&self, model: &str) -> Option<usize> {
+        self.patterns
+            .iter()
+            .filter(|pattern| model.contains(pattern.as_str()))
+            .map(|pattern| pattern.len())
+            .max()
+    }
+}
+
+/// モデル料金表（同梱の料金表 + ユーザーの上書き）
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingTable {
+    /// 料金一覧
+    prices: Vec<ModelPrice>,
+    /// ユーザー定義の料金（同梱の料金より優先）
+    overrides: Vec<ModelPrice>,
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
+
+impl PricingTable {
+    /// 同梱の料金表
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_PRICING).expect("bundled pricing.toml is valid")
+    }
+
+    /// 料金ファイルの TOML を解析
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let file: PricingFile = toml::from_str(text).map_err(|e| e.message().to_string())?;
+        let prices = file
+            .price
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                ModelPrice::try_from(entry).map_err(|e| format!("price[{}]: {}", index, e))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            prices,
+            overrides: Vec::new(),
+        })
+    }
+
+    /// 同梱の料金表にユーザーの料金ファイルを重ねて読み込み（存在しない場合は同梱のみ）
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let table = Self::bundled();
+        if !path.exists() {
+            return Ok(table);
+        }
+        let user = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| Self::parse(&text))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(table.with_overrides(user))
+    }
+
+    /// 別の料金表を優先して重ねる
+    pub fn with_overrides(mut self, table: PricingTable) -> Self {
+        let mut overrides = table.overrides;
+        overrides.extend(table.prices);
+        overrides.append(&mut self.overrides);
+        self.overrides = overrides;
+        self
+    }
+
+    /// 指定日時に適用されるモデルの料金（日時が不明なら現在の料金）
+    /// ユーザー定義の料金を優先し、その中では最長一致のパターンを選ぶ
+    pub fn rate(&self, model: &str, at: Option<DateTime<Utc>>) -> Option<CostRate> {
+        let model = model.to_lowercase();
+        let date = at.unwrap_or_else(Utc::now).date_naive();
+        let find = |prices: &[ModelPrice]| {
+            let mut best: Option<(usize, CostRate)> = None;
+            for price in prices.iter().filter(|price| price.in_force(date)) {
+                if let Some(len) = price.match_len(&model)
+                    && best.is_none_or(|(best_len, _)| len > best_len)
+                {
+                    best = Some((len, price.rate));
+                }
+            }
+            best.map(|(_, rate)| rate)
+        };
+        find(&self.overrides).or_else(|| find(&self.prices))
+    }
+
+    /// 料金表の内容から求めた値（料金が変わると変わる）
+    /// 検索インデックスに保存した集計のコストが古くなっていないかの判定に使う
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for prices in [&self.prices, &self.overrides] {
+            prices.len().hash(&mut hasher);
+            for price in prices {
+                price.patterns.hash(&mut hasher);
+                for value in [
+                    price.rate.input_per_million,
+                    price.rate.output_per_million,
+                    price.rate.cache_write_multiplier,
+                    price.rate.cache_read_multiplier,
+                ] {
+                    value.to_bits().hash(&mut hasher);
+                }
+                price.from.hash(&mut hasher);
+                price.until.hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+}
+
+impl TryFrom<PriceEntry> for ModelPrice {
+    type Error = String;
+
+    fn try_from(entry: PriceEntry) -> Result<Self, Self::Error> {
+        if entry.models.is_empty() || entry.models.iter().any(|model| model.is_empty()) {
+            return Err("models must list non-empty model id patterns".to_string());
+        }
+        for (name, value) in [
+            ("input", entry.input),
+            ("output", entry.output),
+            ("cache_write", entry.cache_write),
+            ("cache_read", entry.cache_read),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!("{} must be a non-negative number", name));
+            }
+        }
+        let date = |name: &str, value: Option<String>| {
+            value
+                .map(|value| {
+                    NaiveDate::parse_from_str(&value, "%Y-%m-%d").map_err(|_| {
+                        format!("{}: invalid date '{}' (expected YYYY-MM-DD)", name, value)
+                    })
+                })
+                .transpose()
+        };
+        let from = date("from", entry.from)?;
+        let until = date("until", entry.until)?;
+        if let (Some(from), Some(until)) = (from, until)
+            && from >= until
+        {
+            return Err(format!("from ({}) must be before until ({})", from, until));
+        }
+        Ok(Self {
+            patterns: entry
+                .models
+                .iter()
+                .map(|model| model.to_lowercase())
+                .collect(),
+            rate: CostRate {
+                input_per_million: entry.input,
+                output_per_million: entry.output,
+                cache_write_multiplier: entry.cache_write,
+                cache_read_multiplier: entry.cache_read,
+            },
+            from,
+            until,
+        })
+    }
+}
+
+/// 料金表を設定（起動時に一度だけ、以降の設定は無視）
+pub fn install(table: PricingTable) {
+    let _ = PRICING.set(table);
+}
+
+/// 現在の料金表（未設定なら同梱の料金表）
+pub fn current() -> &'static PricingTable {
+    PRICING.get_or_init(PricingTable::bundled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn rate(input: f64, output: f64) -> Option<CostRate> {
+        Some(CostRate::new(input, output))
+    }
+
+    fn cached(input: f64, output: f64, cache_write: f64, cache_read: f64) -> Option<CostRate> {
+        Some(CostRate {
+            cache_write_multiplier: cache_write,
+            cache_read_multiplier: cache_read,
+            ..CostRate::new(input, output)
+        })
+    }
+
+    #[test]
+    fn test_bundled_pricing_covers_current_models() {
+        let table = PricingTable::bundled();
+        assert_eq!(
+            table.rate("claude-sonnet-4-5-20250929", None),
+            cached(3.0, 15.0, 1.25, 0.1)
+        );
+        assert_eq!(
+            table.rate("claude-haiku-4-5-20251001", None),
+            cached(1.0, 5.0, 1.25, 0.1)
+        );
+        assert_eq!(
+            table.rate("claude-opus-4-5-20251101", None),
+            cached(5.0, 25.0, 1.25, 0.1)
+        );
+        assert_eq!(
+            table.rate("claude-opus-4-1-20250805", None),
+            cached(15.0, 75.0, 1.25, 0.1)
+        );
+        assert_eq!(
+            table.rate("GPT-5-Codex", None),
+            cached(1.25, 10.0, 1.0, 0.1)
+        );
+        assert_eq!(table.rate("unknown-model", None), None);
+    }
+
+    #[test]
+    fn test_pricing_effective_dates() {
+        let table = PricingTable::bundled();
+        let before = Utc.with_ymd_and_hms(2025, 6, 9, 23, 0, 0).unwrap();
+        let after = Utc.with_ymd_and_hms(2025, 6, 10, 0, 0, 0).unwrap();
+        assert_eq!(
+            table.rate("o3-2025-04-16", Some(before)),
+            cached(10.0, 40.0, 1.0, 0.25)
+        );
+        assert_eq!(
+            table.rate("o3-2025-04-16", Some(after)),
+            cached(2.0, 8.0, 1.0, 0.25)
+        );
+        // 最長一致が優先
+        assert_eq!(
+            table.rate("o3-mini", Some(before)),
+            cached(1.1, 4.4, 1.0, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_pricing_overrides() {
+        let user = PricingTable::parse(
+            r#"
+[[price]]
+models = ["sonnet"]
+input = 1.0
+output = 2.0
+until = "2025-01-01"
+
+[[price]]
+models = ["my-local-model"]
+input = 0.0
+output = 0.0
+"#,
+        )
+        .unwrap();
+        let table = PricingTable::bundled().with_overrides(user);
+        let old = Utc.with_ymd_and_hms(2024, 12, 31, 0, 0, 0).unwrap();
+        let new = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(table.rate("claude-sonnet-4", Some(old)), rate(1.0, 2.0));
+        // 期間外は同梱の料金に戻る
+        assert_eq!(
+            table.rate("claude-sonnet-4", Some(new)),
+            cached(3.0, 15.0, 1.25, 0.1)
+        );
+        assert_eq!(table.rate("my-local-model", None), rate(0.0, 0.0));
+
+        let bundled = PricingTable::bundled().fingerprint();
+        assert_eq!(PricingTable::bundled().fingerprint(), bundled);
+        assert_ne!(table.fingerprint(), bundled);
+    }
+
+    #[test]
+    fn test_pricing_parse_errors() {
+        let error = |text: &str| PricingTable::parse(text).unwrap_err();
+        assert!(error("[[price]]\nmodels = []\ninput = 1.0\noutput = 1.0").contains("price[0]"));
+        assert!(
+            error("[[price]]\nmodels = [\"a\"]\ninput = -1.0\noutput = 1.0")
+                .contains("input must be")
+        );
+        assert!(
+            error("[[price]]\nmodels = [\"a\"]\ninput = 1.0\noutput = 1.0\ncache_read = -0.1")
+                .contains("cache_read must be")
+        );
+        assert!(
+            error("[[price]]\nmodels = [\"a\"]\ninput = 1.0\noutput = 1.0\nfrom = \"2025-13-01\"")
+                .contains("invalid date")
+        );
+        assert!(
+            error(
+                "[[price]]\nmodels = [\"a\"]\ninput = 1.0\noutput = 1.0\nfrom = \"2025-02-01\"\nuntil = \"2025-01-01\""
+            )
+            .contains("must be before")
+        );
+        assert!(error("[[price]]\nmodel = \"a\"").contains("unknown field"));
+    }
+}
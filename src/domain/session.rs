@@ -1,8 +1,30 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::billing::{CostSummary, UsageSummary, estimate_cost_usd};
+use super::billing::{CostSummary, UsageSummary, estimate_cost_breakdown};
+use super::diff::FileDiff;
 use super::message::Message;
+use super::tool::{ToolResults, collect_tool_results};
+
+/// セッションの出所
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SessionSource {
+    Claude,
+    Codex,
+}
+
+/// セッションを一意に識別するキー（出所・データディレクトリのラベル・セッション ID）
+/// 同じ ID のセッションでもデータディレクトリが異なれば別のセッションとして扱う
+pub type SessionKey = (SessionSource, Option<String>, String);
+
+impl SessionSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            SessionSource::Claude => "Claude",
+            SessionSource::Codex => "Codex",
+        }
+    }
+}
 
 /// セッションファイルの各行
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -96,6 +118,60 @@ impl SessionEntry {
             .map(|m| m.all_text_content())
             .filter(|s| !s.is_empty())
     }
+
+    /// thinking ブロックのテキストを取得（複数ブロックは空行で結合）
+    pub fn thinking_text(&self) -> Option<String> {
+        let texts = self.message.as_ref()?.thinking_texts();
+        if texts.is_empty() {
+            None
+        } else {
+            Some(texts.join("\n\n"))
+        }
+    }
+
+    /// ファイル変更ツール呼び出し（Edit / MultiEdit / Write）の差分（ツール名付き）
+    pub fn file_diffs(&self) -> Vec<(&str, FileDiff)> {
+        let Some(message) = self.message.as_ref() else {
+            return Vec::new();
+        };
+        message
+            .tool_uses()
+            .into_iter()
+            .filter_map(|(_, name, input)| Some((name, FileDiff::from_tool_use(name, input)?)))
+            .collect()
+    }
+}
+
+/// セッションの集計メタデータ（検索・フィルタ用）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionStats {
+    /// 使用モデル（出現順、重複なし）
+    pub models: Vec<String>,
+    /// 使用ツール（出現順、重複なし）
+    pub tools: Vec<String>,
+    /// Git ブランチ（出現順、重複なし）
+    pub git_branches: Vec<String>,
+    /// Claude Code バージョン（出現順、重複なし）
+    pub versions: Vec<String>,
+    /// メッセージ数
+    pub message_count: usize,
+    /// 合計トークン数
+    pub total_tokens: u64,
+    /// 推定コスト（USD、データなしの場合は None）
+    pub cost_usd: Option<f64>,
+    /// 最初と最後のエントリの間隔（秒）
+    #[serde(default)]
+    pub duration_secs: u64,
+    /// 料金表にないモデル（出現順、重複なし）
+    #[serde(default)]
+    pub unpriced_models: Vec<String>,
+}
+
+/// 重複しない場合のみ追加
+fn push_unique(values: &mut Vec<String>, value: &str) {
+    if !value.is_empty() && !values.iter().any(|v| v == value) {
+        values.push(value.to_string());
+    }
 }
 
 /// セッション全体
@@ -168,75 +244,138 @@ impl Session {
             .count()
     }
 
-    /// セッショントークン使用量の集計
-    pub fn usage_summary(&self) -> UsageSummary {
-        let mut summary = UsageSummary::default();
+    /// tool_use_id をキーにしたツール実行結果
+    pub fn tool_results(&self) -> ToolResults {
+        collect_tool_results(&self.entries)
+    }
 
-        for entry in self.entries.iter().filter(|e| e.is_assistant()) {
-            let Some(message) = entry.message.as_ref() else {
-                summary.has_unknown = true;
+    /// 検索・フィルタ用のメタデータを集計
+    pub fn stats(&self) -> SessionStats {
+        let mut stats = SessionStats {
+            message_count: self.message_count(),
+            duration_secs: match (self.started_at, self.ended_at) {
+                (Some(start), Some(end)) => (end - start).num_seconds().max(0) as u64,
+                _ => 0,
+            },
+            ..Default::default()
+        };
+
+        for entry in &self.entries {
+            if let Some(branch) = entry.git_branch.as_deref() {
+                push_unique(&mut stats.git_branches, branch);
+            }
+            if let Some(version) = entry.version.as_deref() {
+                push_unique(&mut stats.versions, version);
+            }
+            if !entry.is_assistant() {
                 continue;
-            };
-            let Some(usage) = message.usage.as_ref() else {
-                summary.has_unknown = true;
+            }
+            let Some(message) = entry.message.as_ref() else {
                 continue;
             };
-            let has_any = usage.input_tokens.is_some()
-                || usage.output_tokens.is_some()
-                || usage.cache_creation_input_tokens.is_some()
-                || usage.cache_read_input_tokens.is_some();
-            if !has_any {
-                summary.has_unknown = true;
-                continue;
+            if let Some(model) = message.model.as_deref() {
+                push_unique(&mut stats.models, model);
             }
+            for (_, name, _) in message.tool_uses() {
+                push_unique(&mut stats.tools, name);
+            }
+        }
 
-            summary.has_data = true;
-            let input = usage.total_input_tokens();
-            let output = usage.total_output_tokens();
-            summary.input_tokens += input;
-            summary.output_tokens += output;
-            summary.total_tokens += input + output;
+        let usage_summary = self.usage_summary();
+        stats.total_tokens = usage_summary.total_tokens;
+        let cost_summary = self.cost_summary();
+        if cost_summary.has_data {
+            stats.cost_usd = Some(cost_summary.usd);
         }
+        stats.unpriced_models = cost_summary.unpriced_models;
+
+        stats
+    }
 
+    /// セッショントークン使用量の集計
+    pub fn usage_summary(&self) -> UsageSummary {
+        let mut summary = UsageSummary::default();
+        for entry in &self.entries {
+            summary.add_entry(entry);
+        }
         summary
     }
 
     /// セッションコスト（USD）の集計
     pub fn cost_summary(&self) -> CostSummary {
         let mut summary = CostSummary::default();
+        for entry in &self.entries {
+            summary.add_entry(entry);
+        }
+        summary
+    }
+}
 
-        for entry in self.entries.iter().filter(|e| e.is_assistant()) {
-            let Some(message) = entry.message.as_ref() else {
-                summary.has_unknown = true;
-                continue;
-            };
-            let Some(usage) = message.usage.as_ref() else {
-                summary.has_unknown = true;
-                continue;
-            };
-            let has_any = usage.input_tokens.is_some()
-                || usage.output_tokens.is_some()
-                || usage.cache_creation_input_tokens.is_some()
-                || usage.cache_read_input_tokens.is_some();
-            if !has_any {
-                summary.has_unknown = true;
-                continue;
-            }
+impl UsageSummary {
+    /// アシスタントのエントリのトークン使用量を加算（それ以外は無視）
+    pub fn add_entry(&mut self, entry: &SessionEntry) {
+        if !entry.is_assistant() {
+            return;
+        }
+        let Some(usage) = entry.message.as_ref().and_then(|m| m.usage.as_ref()) else {
+            self.has_unknown = true;
+            return;
+        };
+        let has_any = usage.input_tokens.is_some()
+            || usage.output_tokens.is_some()
+            || usage.cache_creation_input_tokens.is_some()
+            || usage.cache_read_input_tokens.is_some();
+        if !has_any {
+            self.has_unknown = true;
+            return;
+        }
 
-            summary.has_data = true;
-            let Some(model) = message.model.as_deref() else {
-                summary.has_unknown = true;
-                continue;
-            };
+        self.has_data = true;
+        let input = usage.total_input_tokens();
+        let output = usage.total_output_tokens();
+        self.input_tokens += input;
+        self.output_tokens += output;
+        self.total_tokens += input + output;
+    }
+}
 
-            if let Some(cost) = estimate_cost_usd(model, usage) {
-                summary.usd += cost;
-            } else {
-                summary.has_unknown = true;
-            }
+impl CostSummary {
+    /// アシスタントのエントリのコストを加算（それ以外は無視）
+    pub fn add_entry(&mut self, entry: &SessionEntry) {
+        if !entry.is_assistant() {
+            return;
+        }
+        let Some(message) = entry.message.as_ref() else {
+            self.has_unknown = true;
+            return;
+        };
+        let Some(usage) = message.usage.as_ref() else {
+            self.has_unknown = true;
+            return;
+        };
+        let has_any = usage.input_tokens.is_some()
+            || usage.output_tokens.is_some()
+            || usage.cache_creation_input_tokens.is_some()
+            || usage.cache_read_input_tokens.is_some();
+        if !has_any {
+            self.has_unknown = true;
+            return;
         }
 
-        summary
+        self.has_data = true;
+        let Some(model) = message.model.as_deref() else {
+            self.has_unknown = true;
+            return;
+        };
+
+        // メッセージ時点の料金で見積もる
+        if let Some(cost) = estimate_cost_breakdown(model, usage, entry.datetime()) {
+            self.usd += cost.total();
+            self.breakdown.add(&cost);
+        } else {
+            self.has_unknown = true;
+            push_unique(&mut self.unpriced_models, model);
+        }
     }
 }
 
@@ -430,4 +569,63 @@ mod tests {
         assert!(session.started_at.is_some());
         assert_eq!(session.message_count(), 2);
     }
+
+    #[test]
+    fn test_session_stats() {
+        use crate::domain::message::{ContentBlock, Message, MessageContent, Usage};
+        use serde_json::json;
+
+        let assistant = |model: &str, tool: &str| SessionEntry {
+            entry_type: Some("assistant".to_string()),
+            git_branch: Some("main".to_string()),
+            version: Some("1.0.0".to_string()),
+            message: Some(Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Blocks(vec![ContentBlock::ToolUse {
+                    id: "tool_1".to_string(),
+                    name: tool.to_string(),
+                    input: json!({}),
+                }]),
+                model: Some(model.to_string()),
+                id: None,
+                stop_reason: None,
+                usage: Some(Usage {
+                    input_tokens: Some(1_000_000),
+                    output_tokens: Some(0),
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
+                }),
+            }),
+            ..Default::default()
+        };
+        let entries = vec![
+            SessionEntry {
+                entry_type: Some("user".to_string()),
+                git_branch: Some("feature".to_string()),
+                timestamp: Some("2025-01-01T10:00:00Z".to_string()),
+                ..Default::default()
+            },
+            assistant("claude-3-5-sonnet-20241022", "Bash"),
+            assistant("claude-3-5-sonnet-20241022", "Read"),
+            SessionEntry {
+                timestamp: Some("2025-01-01T10:25:30Z".to_string()),
+                ..assistant("claude-3-5-sonnet-20241022", "Bash")
+            },
+            assistant("in-house-model", "Bash"),
+        ];
+
+        let stats = Session::from_entries("id".to_string(), "/p".to_string(), entries).stats();
+        assert_eq!(
+            stats.models,
+            vec!["claude-3-5-sonnet-20241022", "in-house-model"]
+        );
+        assert_eq!(stats.tools, vec!["Bash", "Read"]);
+        assert_eq!(stats.git_branches, vec!["feature", "main"]);
+        assert_eq!(stats.versions, vec!["1.0.0"]);
+        assert_eq!(stats.message_count, 5);
+        assert_eq!(stats.total_tokens, 4_000_000);
+        assert_eq!(stats.cost_usd, Some(9.0));
+        assert_eq!(stats.unpriced_models, vec!["in-house-model"]);
+        assert_eq!(stats.duration_secs, 25 * 60 + 30);
+    }
 }
@@ -0,0 +1,148 @@
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag, TagEnd};
+use serde_json::Value;
+
+use super::session::SessionEntry;
+
+/// コピー対象のコード片（フェンス付きコードブロックやツール入力）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeSnippet {
+    /// 表示ラベル（言語名やツール名）
+    pub label: String,
+    /// コード本文
+    pub text: String,
+}
+
+impl CodeSnippet {
+    fn new(label: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            text: text.into(),
+        }
+    }
+}
+
+/// Markdown からフェンス付きコードブロックを抽出
+pub fn extract_fenced_code_blocks(markdown: &str) -> Vec<CodeSnippet> {
+    let mut snippets = Vec::new();
+    let mut current: Option<CodeSnippet> = None;
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+                let lang = info.split_whitespace().next().unwrap_or("code");
+                current = Some(CodeSnippet::new(lang, String::new()));
+            }
+            Event::Text(text) => {
+                if let Some(snippet) = &mut current {
+                    snippet.text.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some(mut snippet) = current.take() {
+                    let trimmed = snippet.text.trim_end_matches('\n').len();
+                    snippet.text.truncate(trimmed);
+                    if !snippet.text.trim().is_empty() {
+                        snippets.push(snippet);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    snippets
+}
+
+/// ツール入力のうちコピー対象になる本文（Bash のコマンド、Write/Edit の内容）
+fn tool_input_snippet(name: &str, input: &Value) -> Option<CodeSnippet> {
+    let field = |key: &str| input.get(key).and_then(Value::as_str);
+    let (label, text) = match name {
+        "Bash" => ("Bash".to_string(), field("command")?),
+        "Write" => (format!("Write {}", field("file_path")?), field("content")?),
+        "Edit" => (
+            format!("Edit {}", field("file_path")?),
+            field("new_string")?,
+        ),
+        _ => return None,
+    };
+    if text.trim().is_empty() {
+        return None;
+    }
+    Some(CodeSnippet::new(label, text))
+}
+
+impl SessionEntry {
+    /// メッセージ内のコード片（本文のコードブロック → ツール入力の順）
+    pub fn code_snippets(&self) -> Vec<CodeSnippet> {
+        let mut snippets = self
+            .display_text()
+            .map(|text| extract_fenced_code_blocks(&text))
+            .unwrap_or_default();
+        if let Some(message) = &self.message {
+            snippets.extend(
+                message
+                    .tool_uses()
+                    .into_iter()
+                    .filter_map(|(_, name, input)| tool_input_snippet(name, input)),
+            );
+        }
+        snippets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::message::{ContentBlock, Message, MessageContent};
+    use serde_json::json;
+
+    #[test]
+    fn test_extract_fenced_code_blocks() {
+        let markdown =
+            "Run:\n\n```bash\ncargo test\n```\n\n    indented\n\n```\nplain\nlines\n```\n";
+        let snippets = extract_fenced_code_blocks(markdown);
+        assert_eq!(
+            snippets,
+            vec![
+                CodeSnippet::new("bash", "cargo test"),
+                CodeSnippet::new("code", "plain\nlines"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entry_code_snippets_include_tool_inputs() {
+        let entry = SessionEntry {
+            entry_type: Some("assistant".to_string()),
+            message: Some(Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Blocks(vec![
+                    ContentBlock::Text {
+                        text: "```rust\nfn main() {}\n```".to_string(),
+                    },
+                    ContentBlock::ToolUse {
+                        id: "toolu_1".to_string(),
+                        name: "Bash".to_string(),
+                        input: json!({"command": "cargo build"}),
+                    },
+                    ContentBlock::ToolUse {
+                        id: "toolu_2".to_string(),
+                        name: "Read".to_string(),
+                        input: json!({"file_path": "/src/main.rs"}),
+                    },
+                ]),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            entry.code_snippets(),
+            vec![
+                CodeSnippet::new("rust", "fn main() {}"),
+                CodeSnippet::new("Bash", "cargo build"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::message::{ContentBlock, MessageContent};
+use super::session::SessionEntry;
+
+/// 入力サマリーの最大文字数
+const SUMMARY_MAX_CHARS: usize = 120;
+
+/// ツール実行結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolResult {
+    /// 結果テキスト
+    pub text: String,
+    /// エラー結果か
+    pub is_error: bool,
+}
+
+/// tool_use_id をキーにしたツール実行結果
+pub type ToolResults = HashMap<String, ToolResult>;
+
+/// エントリ群からツール実行結果を収集
+pub fn collect_tool_results<'a>(
+    entries: impl IntoIterator<Item = &'a SessionEntry>,
+) -> ToolResults {
+    let mut results = ToolResults::new();
+    for entry in entries {
+        let Some(MessageContent::Blocks(blocks)) = entry.message.as_ref().map(|m| &m.content)
+        else {
+            continue;
+        };
+        for block in blocks {
+            if let ContentBlock::ToolResult {
+                tool_use_id,
+                content,
+                is_error,
+            } = block
+            {
+                results.insert(
+                    tool_use_id.clone(),
+                    ToolResult {
+                        text: content.text(),
+                        is_error: *is_error,
+                    },
+                );
+            }
+        }
+    }
+    results
+}
+
+/// ツール入力の要約（Bash はコマンド、Read/Edit はパス、Grep はパターンなど）
+pub fn summarize_tool_input(name: &str, input: &Value) -> Option<String> {
+    let field = |key: &str| input.get(key).and_then(Value::as_str);
+
+    let summary = match name {
+        "Bash" => field("command").map(str::to_string),
+        "Read" | "Edit" | "MultiEdit" | "Write" => field("file_path").map(str::to_string),
+        "NotebookEdit" => field("notebook_path").map(str::to_string),
+        "Grep" => field("pattern").map(|pattern| match field("path") {
+            Some(path) => format!("{pattern}  in {path}"),
+            None => pattern.to_string(),
+        }),
+        "Glob" => field("pattern").map(str::to_string),
+        "WebFetch" => field("url").map(str::to_string),
+        "WebSearch" => field("query").map(str::to_string),
+        "Task" => field("description").map(str::to_string),
+        "TodoWrite" => input
+            .get("todos")
+            .and_then(Value::as_array)
+            .map(|todos| format!("{} todos", todos.len())),
+        _ => None,
+    };
+
+    // 未知のツールは最初の文字列フィールドを使う
+    let summary = summary.or_else(|| {
+        input
+            .as_object()?
+            .values()
+            .find_map(Value::as_str)
+            .map(str::to_string)
+    })?;
+
+    let first_line = summary.lines().next().unwrap_or_default().trim();
+    if first_line.is_empty() {
+        return None;
+    }
+    Some(truncate_chars(
+        first_line,
+        SUMMARY_MAX_CHARS,
+        summary.lines().nth(1).is_some(),
+    ))
+}
+
+/// 文字数で切り詰め（続きがある場合は … を付与）
+fn truncate_chars(text: &str, max_chars: usize, has_more: bool) -> String {
+    let mut truncated: String = text.chars().take(max_chars).collect();
+    if has_more || text.chars().count() > max_chars {
+        truncated.push('…');
+    }
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::message::{Message, ToolResultContent};
+    use serde_json::json;
+
+    #[test]
+    fn test_summarize_tool_input() {
+        assert_eq!(
+            summarize_tool_input("Bash", &json!({"command": "cargo test\ncargo build"})),
+            Some("cargo test…".to_string())
+        );
+        assert_eq!(
+            summarize_tool_input("Read", &json!({"file_path": "/src/main.rs"})),
+            Some("/src/main.rs".to_string())
+        );
+        assert_eq!(
+            summarize_tool_input("Grep", &json!({"pattern": "fn main", "path": "src"})),
+            Some("fn main  in src".to_string())
+        );
+        assert_eq!(
+            summarize_tool_input("mcp__custom", &json!({"limit": 3, "name": "x"})),
+            Some("x".to_string())
+        );
+        assert_eq!(summarize_tool_input("Bash", &json!({})), None);
+    }
+
+    #[test]
+    fn test_collect_tool_results() {
+        let entry = SessionEntry {
+            entry_type: Some("user".to_string()),
+            message: Some(Message {
+                role: "user".to_string(),
+                content: MessageContent::Blocks(vec![
+                    ContentBlock::ToolResult {
+                        tool_use_id: "toolu_1".to_string(),
+                        content: ToolResultContent::Text("ok".to_string()),
+                        is_error: false,
+                    },
+                    ContentBlock::ToolResult {
+                        tool_use_id: "toolu_2".to_string(),
+                        content: ToolResultContent::Blocks(vec![ContentBlock::Text {
+                            text: "not found".to_string(),
+                        }]),
+                        is_error: true,
+                    },
+                ]),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        };
+
+        let results = collect_tool_results([&entry]);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results["toolu_1"].text, "ok");
+        assert!(!results["toolu_1"].is_error);
+        assert_eq!(results["toolu_2"].text, "not found");
+        assert!(results["toolu_2"].is_error);
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, Utc};
+
+use super::billing::{CostSummary, UsageSummary};
+use super::session::Session;
+
+/// 集計値（セッション数・メッセージ数・トークン数・コスト）
+#[derive(Debug, Clone, Default)]
+pub struct UsageTotals {
+    /// セッション数（期間・プロジェクト・モデルに関わったセッション）
+    pub sessions: usize,
+    /// メッセージ数
+    pub messages: usize,
+    /// トークン使用量
+    pub usage: UsageSummary,
+    /// コスト
+    pub cost: CostSummary,
+}
+
+/// 使用量とコストの集計（ダッシュボード用）
+#[derive(Debug, Clone, Default)]
+pub struct UsageReport {
+    /// 全体
+    pub total: UsageTotals,
+    /// 日ごと（ローカル時刻の日付）
+    pub by_day: BTreeMap<NaiveDate, UsageTotals>,
+    /// 週ごと（週の初めの月曜日）
+    pub by_week: BTreeMap<NaiveDate, UsageTotals>,
+    /// プロジェクトごと（プロジェクト名）
+    pub by_project: BTreeMap<String, UsageTotals>,
+    /// モデルごと
+    pub by_model: BTreeMap<String, UsageTotals>,
+    /// 読み込めなかったセッション数
+    pub failed_sessions: usize,
+}
+
+/// 日付を含む週の月曜日
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+impl UsageReport {
+    /// 新規作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// セッションのエントリを日・週・プロジェクト・モデルごとに集計
+    pub fn add_session(&mut self, project: &str, session: &Session) {
+        self.add_session_between(project, session, None, None);
+    }
+
+    /// from〜to（どちらも含む、None は制限なし）のエントリのみを集計
+    /// 期間を指定した場合、日時のないエントリや期間内のエントリがないセッションは含めない
+    pub fn add_session_between(
+        &mut self,
+        project: &str,
+        session: &Session,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) {
+        let clipped = from.is_some() || to.is_some();
+        let in_period =
+            |dt: DateTime<Utc>| from.is_none_or(|from| from <= dt) && to.is_none_or(|to| dt <= to);
+        let mut days = HashSet::new();
+        let mut weeks = HashSet::new();
+        let mut models = HashSet::new();
+        let mut matched = false;
+
+        for entry in &session.entries {
+            if !(entry.is_user() || entry.is_assistant()) {
+                continue;
+            }
+            if clipped && !entry.datetime().is_some_and(in_period) {
+                continue;
+            }
+            matched = true;
+            let mut buckets: Vec<&mut UsageTotals> = vec![
+                &mut self.total,
+                self.by_project.entry(project.to_string()).or_default(),
+            ];
+            if let Some(date) = entry
+                .datetime()
+                .map(|dt| dt.with_timezone(&Local).date_naive())
+            {
+                days.insert(date);
+                weeks.insert(week_start(date));
+                buckets.push(self.by_day.entry(date).or_default());
+                buckets.push(self.by_week.entry(week_start(date)).or_default());
+            }
+            if let Some(model) = entry
+                .message
+                .as_ref()
+                .and_then(|message| message.model.as_deref())
+                .filter(|model| entry.is_assistant() && !model.is_empty())
+            {
+                models.insert(model.to_string());
+                buckets.push(self.by_model.entry(model.to_string()).or_default());
+            }
+            for totals in buckets {
+                totals.messages += 1;
+                totals.usage.add_entry(entry);
+                totals.cost.add_entry(entry);
+            }
+        }
+
+        if clipped && !matched {
+            return;
+        }
+        self.total.sessions += 1;
+        self.by_project
+            .entry(project.to_string())
+            .or_default()
+            .sessions += 1;
+        for date in days {
+            self.by_day.entry(date).or_default().sessions += 1;
+        }
+        for week in weeks {
+            self.by_week.entry(week).or_default().sessions += 1;
+        }
+        for model in models {
+            self.by_model.entry(model).or_default().sessions += 1;
+        }
+    }
+
+    /// 直近 count 日（today を含む、古い順、使用のない日も含む）
+    pub fn recent_days(&self, today: NaiveDate, count: usize) -> Vec<(NaiveDate, UsageTotals)> {
+        recent(&self.by_day, today, count, 1)
+    }
+
+    /// 直近 count 週（today を含む週まで、古い順、使用のない週も含む）
+    pub fn recent_weeks(&self, today: NaiveDate, count: usize) -> Vec<(NaiveDate, UsageTotals)> {
+        recent(&self.by_week, week_start(today), count, 7)
+    }
+
+    /// コストの高い順（同じならトークン数の多い順）のプロジェクト
+    pub fn top_projects(&self, count: usize) -> Vec<(&str, &UsageTotals)> {
+        top(&self.by_project, count)
+    }
+
+    /// コストの高い順（同じならトークン数の多い順）のモデル
+    pub fn top_models(&self, count: usize) -> Vec<(&str, &UsageTotals)> {
+        top(&self.by_model, count)
+    }
+}
+
+/// last から step 日ずつ遡った count 個の区間
+fn recent(
+    buckets: &BTreeMap<NaiveDate, UsageTotals>,
+    last: NaiveDate,
+    count: usize,
+    step: u64,
+) -> Vec<(NaiveDate, UsageTotals)> {
+    let mut result: Vec<_> = (0..count as u64)
+        .filter_map(|i| last.checked_sub_days(Days::new(i * step)))
+        .map(|date| (date, buckets.get(&date).cloned().unwrap_or_default()))
+        .collect();
+    result.reverse();
+    result
+}
+
+/// コスト・トークン数の降順で上位 count 件
+fn top(buckets: &BTreeMap<String, UsageTotals>, count: usize) -> Vec<(&str, &UsageTotals)> {
+    let mut result: Vec<_> = buckets
+        .iter()
+        .map(|(name, totals)| (name.as_str(), totals))
+        .collect();
+    result.sort_by(|(_, a), (_, b)| {
+        b.cost
+            .usd
+            .total_cmp(&a.cost.usd)
+            .then(b.usage.total_tokens.cmp(&a.usage.total_tokens))
+    });
+    result.truncate(count);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::message::{Message, MessageContent, Usage};
+    use crate::domain::session::SessionEntry;
+
+    fn entry(role: &str, timestamp: &str, model: Option<&str>, tokens: u64) -> SessionEntry {
+        SessionEntry {
+            entry_type: Some(role.to_string()),
+            timestamp: Some(timestamp.to_string()),
+            message: Some(Message {
+                role: role.to_string(),
+                content: MessageContent::Text("hi".to_string()),
+                model: model.map(str::to_string),
+                usage: model.map(|_| Usage {
+                    input_tokens: Some(tokens),
+                    output_tokens: Some(tokens),
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
+                }),
+                id: None,
+                stop_reason: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn date(timestamp: &str) -> NaiveDate {
+        chrono::DateTime::parse_from_rfc3339(timestamp)
+            .unwrap()
+            .with_timezone(&Local)
+            .date_naive()
+    }
+
+    #[test]
+    fn test_usage_report_buckets() {
+        let day1 = "2025-06-02T12:00:00Z";
+        let day2 = "2025-06-10T12:00:00Z";
+        let first = Session::from_entries(
+            "s1".to_string(),
+            "/a".to_string(),
+            vec![
+                entry("user", day1, None, 0),
+                entry("assistant", day1, Some("claude-sonnet-4-5"), 1_000_000),
+                entry("assistant", day2, Some("in-house-model"), 10),
+            ],
+        );
+        let second = Session::from_entries(
+            "s2".to_string(),
+            "/b".to_string(),
+            vec![entry("assistant", day2, Some("claude-sonnet-4-5"), 100)],
+        );
+
+        let mut report = UsageReport::new();
+        report.add_session("a", &first);
+        report.add_session("b", &second);
+
+        assert_eq!(report.total.sessions, 2);
+        assert_eq!(report.total.messages, 4);
+        assert_eq!(report.total.usage.total_tokens, 2_000_220);
+        // sonnet-4-5: 入力 $3 + 出力 $15
+        assert!(report.total.cost.usd > 18.0);
+        assert_eq!(report.total.cost.unpriced_models, vec!["in-house-model"]);
+
+        let day = &report.by_day[&date(day1)];
+        assert_eq!((day.sessions, day.messages), (1, 2));
+        assert_eq!(report.by_day[&date(day2)].sessions, 2);
+        assert_eq!(report.by_week.len(), 2);
+        assert_eq!(report.by_project["a"].sessions, 1);
+        assert_eq!(report.by_model["claude-sonnet-4-5"].sessions, 2);
+        assert_eq!(report.by_model["claude-sonnet-4-5"].messages, 2);
+
+        let projects: Vec<_> = report
+            .top_projects(1)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(projects, vec!["a"]);
+        let models: Vec<_> = report
+            .top_models(5)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(models, vec!["claude-sonnet-4-5", "in-house-model"]);
+    }
+
+    #[test]
+    fn test_usage_report_period() {
+        let day1 = "2025-06-02T12:00:00Z";
+        let day2 = "2025-06-10T12:00:00Z";
+        let session = Session::from_entries(
+            "s".to_string(),
+            "/a".to_string(),
+            vec![
+                entry("assistant", day1, Some("gpt-5"), 1),
+                entry("assistant", day2, Some("gpt-5"), 2),
+                entry("assistant", "", Some("gpt-5"), 4),
+            ],
+        );
+        let at = |timestamp: &str| Some(timestamp.parse::<DateTime<Utc>>().unwrap());
+
+        let mut report = UsageReport::new();
+        report.add_session_between("a", &session, at("2025-06-05T00:00:00Z"), None);
+        assert_eq!(report.total.sessions, 1);
+        assert_eq!(report.total.messages, 1);
+        assert_eq!(report.total.usage.total_tokens, 4);
+        assert!(!report.by_day.contains_key(&date(day1)));
+
+        // 期間内のエントリがないセッションは含めない
+        report.add_session_between("b", &session, None, at("2025-06-01T00:00:00Z"));
+        assert_eq!(report.total.sessions, 1);
+        assert!(!report.by_project.contains_key("b"));
+
+        let mut report = UsageReport::new();
+        report.add_session("a", &session);
+        assert_eq!(report.total.messages, 3);
+    }
+
+    #[test]
+    fn test_recent_days_and_weeks() {
+        let session = Session::from_entries(
+            "s".to_string(),
+            "/a".to_string(),
+            vec![entry("assistant", "2025-06-10T12:00:00Z", Some("gpt-5"), 5)],
+        );
+        let mut report = UsageReport::new();
+        report.add_session("a", &session);
+        let today = date("2025-06-10T12:00:00Z");
+
+        let days = report.recent_days(today, 3);
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[2].0, today);
+        assert_eq!(days[2].1.usage.total_tokens, 10);
+        assert_eq!(days[0].1.usage.total_tokens, 0);
+
+        let weeks = report.recent_weeks(today, 2);
+        assert_eq!(weeks[1].0, week_start(today));
+        assert_eq!(weeks[1].1.messages, 1);
+        assert_eq!(weeks[0].0, week_start(today) - Days::new(7));
+        assert_eq!(
+            week_start(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()),
+            NaiveDate::from_ymd_opt(2025, 6, 9).unwrap()
+        );
+    }
+}
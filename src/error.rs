@@ -27,6 +27,12 @@ pub enum KathaError {
 
     #[error("Permission denied: {0}")]
     PermissionDenied(String),
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("Budget exceeded: {0}")]
+    BudgetExceeded(String),
 }
 
 impl KathaError {
@@ -43,6 +49,23 @@ impl KathaError {
                 format!("File already exists: {}", path.display())
             }
             KathaError::PermissionDenied(msg) => format!("Permission denied: {}", msg),
+            KathaError::InvalidArgument(msg) => msg.clone(),
+            KathaError::BudgetExceeded(names) => format!("Budget exceeded: {}", names),
+        }
+    }
+
+    /// CLI の終了コード（sysexits.h 準拠、予算超過は cron 向けに 1）
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            KathaError::BudgetExceeded(_) => 1,
+            KathaError::InvalidArgument(_) => 64,
+            KathaError::JsonError(_) => 65,
+            KathaError::SessionNotFound(_) => 66,
+            KathaError::Terminal(_) => 70,
+            KathaError::ExportError(_) | KathaError::FileExists(_) => 73,
+            KathaError::IoError(_) => 74,
+            KathaError::PermissionDenied(_) => 77,
+            KathaError::ConfigError(_) => 78,
         }
     }
 }
@@ -8,12 +8,17 @@ use super::Exporter;
 pub struct JsonExporter {
     /// 整形出力するか
     pretty: bool,
+    /// thinking ブロックを含めるか
+    include_thinking: bool,
 }
 
 impl JsonExporter {
     /// 新規作成
     pub fn new() -> Self {
-        Self { pretty: true }
+        Self {
+            pretty: true,
+            include_thinking: false,
+        }
     }
 
     /// 整形出力を設定
@@ -21,6 +26,12 @@ impl JsonExporter {
         self.pretty = pretty;
         self
     }
+
+    /// thinking ブロックを含めるかを設定
+    pub fn include_thinking(mut self, include: bool) -> Self {
+        self.include_thinking = include;
+        self
+    }
 }
 
 impl Default for JsonExporter {
@@ -48,20 +59,49 @@ struct ExportMessage<'a> {
     role: &'a str,
     content: Option<String>,
     timestamp: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    diffs: Vec<ExportDiff<'a>>,
+}
+
+/// エクスポート用のファイル変更差分
+#[derive(Serialize)]
+struct ExportDiff<'a> {
+    tool: &'a str,
+    path: String,
+    /// unified diff 形式
+    diff: String,
 }
 
 impl Exporter for JsonExporter {
     fn export(&self, session: &Session) -> String {
-        // 空コンテンツのメッセージを除外
+        // 空コンテンツ（テキスト・ファイル変更・thinking がない）のメッセージを除外
         let messages: Vec<ExportMessage> = session
             .entries
             .iter()
             .filter(|e| e.is_user() || e.is_assistant())
             .filter_map(|entry| {
-                entry.display_text().map(|content| ExportMessage {
+                let content = entry.display_text();
+                let thinking = entry.thinking_text().filter(|_| self.include_thinking);
+                let diffs: Vec<ExportDiff> = entry
+                    .file_diffs()
+                    .into_iter()
+                    .map(|(tool, diff)| ExportDiff {
+                        tool,
+                        diff: diff.to_unified_string(),
+                        path: diff.path,
+                    })
+                    .collect();
+                if content.is_none() && thinking.is_none() && diffs.is_empty() {
+                    return None;
+                }
+                Some(ExportMessage {
                     role: if entry.is_user() { "user" } else { "assistant" },
-                    content: Some(content),
+                    content,
                     timestamp: entry.timestamp.as_deref(),
+                    thinking,
+                    diffs,
                 })
             })
             .collect();
@@ -213,4 +253,89 @@ mod tests {
         // thinking のみのアシスタントメッセージは含まれない
         assert!(!output.contains("\"role\": \"assistant\""));
     }
+
+    #[test]
+    fn test_json_export_file_diffs() {
+        use crate::domain::message::ContentBlock;
+        use serde_json::json;
+
+        let entries = vec![SessionEntry {
+            entry_type: Some("assistant".to_string()),
+            timestamp: Some("2025-01-01T10:01:00Z".to_string()),
+            message: Some(Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Blocks(vec![ContentBlock::ToolUse {
+                    id: "toolu_1".to_string(),
+                    name: "Edit".to_string(),
+                    input: json!({
+                        "file_path": "/test/project/src/lib.rs",
+                        "old_string": "let x = 1;",
+                        "new_string": "let x = 2;",
+                    }),
+                }]),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        }];
+        let session = Session::from_entries(
+            "test-session".to_string(),
+            "/test/project".to_string(),
+            entries,
+        );
+
+        let output = JsonExporter::new().export(&session);
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let message = &value["messages"][0];
+        assert!(message["content"].is_null());
+        assert_eq!(message["diffs"][0]["tool"], "Edit");
+        assert_eq!(message["diffs"][0]["path"], "/test/project/src/lib.rs");
+        assert!(
+            message["diffs"][0]["diff"]
+                .as_str()
+                .unwrap()
+                .contains("-let x = 1;\n+let x = 2;\n")
+        );
+    }
+
+    #[test]
+    fn test_json_export_include_thinking() {
+        use crate::domain::message::ContentBlock;
+
+        let entries = vec![SessionEntry {
+            entry_type: Some("assistant".to_string()),
+            timestamp: Some("2025-01-01T10:01:00Z".to_string()),
+            message: Some(Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Blocks(vec![
+                    ContentBlock::Thinking {
+                        thinking: "Check the config first.".to_string(),
+                    },
+                    ContentBlock::Text {
+                        text: "Done.".to_string(),
+                    },
+                ]),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        }];
+        let session = Session::from_entries(
+            "test-session".to_string(),
+            "/test/project".to_string(),
+            entries,
+        );
+
+        let output = JsonExporter::new().export(&session);
+        assert!(!output.contains("\"thinking\""));
+
+        let output = JsonExporter::new().include_thinking(true).export(&session);
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["messages"][0]["thinking"], "Check the config first.");
+        assert_eq!(value["messages"][0]["content"], "Done.");
+    }
 }
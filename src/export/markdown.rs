@@ -3,12 +3,23 @@ use crate::domain::Session;
 use super::Exporter;
 
 /// Markdown エクスポータ
-pub struct MarkdownExporter;
+pub struct MarkdownExporter {
+    /// thinking ブロックを含めるか
+    include_thinking: bool,
+}
 
 impl MarkdownExporter {
     /// 新規作成
     pub fn new() -> Self {
-        Self
+        Self {
+            include_thinking: false,
+        }
+    }
+
+    /// thinking ブロックを含めるかを設定
+    pub fn include_thinking(mut self, include: bool) -> Self {
+        self.include_thinking = include;
+        self
     }
 }
 
@@ -46,7 +57,7 @@ impl Exporter for MarkdownExporter {
 
         output.push_str("\n---\n\n");
 
-        // メッセージ（テキストがある場合のみ出力）
+        // メッセージ（テキスト・ファイル変更・thinking のいずれかがある場合のみ出力）
         for entry in &session.entries {
             if entry.is_user() {
                 if let Some(text) = entry.display_text() {
@@ -55,11 +66,29 @@ impl Exporter for MarkdownExporter {
                     output.push_str("\n\n---\n\n");
                 }
             } else if entry.is_assistant() {
-                if let Some(text) = entry.display_text() {
-                    output.push_str("## Assistant\n\n");
+                let thinking = entry.thinking_text().filter(|_| self.include_thinking);
+                let text = entry.display_text();
+                let diffs = entry.file_diffs();
+                if thinking.is_none() && text.is_none() && diffs.is_empty() {
+                    continue;
+                }
+                output.push_str("## Assistant\n\n");
+                if let Some(thinking) = thinking {
+                    output.push_str("<details>\n<summary>Thinking</summary>\n\n");
+                    output.push_str(&thinking);
+                    output.push_str("\n\n</details>\n\n");
+                }
+                if let Some(text) = text {
                     output.push_str(&text);
-                    output.push_str("\n\n---\n\n");
+                    output.push_str("\n\n");
+                }
+                for (tool, diff) in diffs {
+                    output.push_str(&format!("**{}** `{}`\n\n", tool, diff.path));
+                    output.push_str("```diff\n");
+                    output.push_str(&diff.to_unified_string());
+                    output.push_str("```\n\n");
                 }
+                output.push_str("---\n\n");
             }
         }
 
@@ -187,4 +216,86 @@ mod tests {
         // "## Assistant" が1回も出現しないことを確認
         assert!(!output.contains("## Assistant"));
     }
+
+    #[test]
+    fn test_markdown_export_file_diffs() {
+        use crate::domain::message::ContentBlock;
+        use serde_json::json;
+
+        // ツール呼び出しのみのアシスタントメッセージ
+        let entries = vec![SessionEntry {
+            entry_type: Some("assistant".to_string()),
+            timestamp: Some("2025-01-01T10:01:00Z".to_string()),
+            message: Some(Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Blocks(vec![ContentBlock::ToolUse {
+                    id: "toolu_1".to_string(),
+                    name: "Edit".to_string(),
+                    input: json!({
+                        "file_path": "/test/project/src/lib.rs",
+                        "old_string": "let x = 1;",
+                        "new_string": "let x = 2;",
+                    }),
+                }]),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        }];
+        let session = Session::from_entries(
+            "test-session".to_string(),
+            "/test/project".to_string(),
+            entries,
+        );
+
+        let output = MarkdownExporter::new().export(&session);
+        assert!(output.contains("## Assistant"));
+        assert!(output.contains("**Edit** `/test/project/src/lib.rs`"));
+        assert!(output.contains(
+            "```diff\n--- /test/project/src/lib.rs\n+++ /test/project/src/lib.rs\n@@ -1 +1 @@\n-let x = 1;\n+let x = 2;\n```"
+        ));
+    }
+
+    #[test]
+    fn test_markdown_export_include_thinking() {
+        use crate::domain::message::ContentBlock;
+
+        let entries = vec![SessionEntry {
+            entry_type: Some("assistant".to_string()),
+            timestamp: Some("2025-01-01T10:01:00Z".to_string()),
+            message: Some(Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Blocks(vec![
+                    ContentBlock::Thinking {
+                        thinking: "Check the config first.".to_string(),
+                    },
+                    ContentBlock::Text {
+                        text: "Done.".to_string(),
+                    },
+                ]),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        }];
+        let session = Session::from_entries(
+            "test-session".to_string(),
+            "/test/project".to_string(),
+            entries,
+        );
+
+        let output = MarkdownExporter::new().export(&session);
+        assert!(!output.contains("Check the config first."));
+
+        let output = MarkdownExporter::new()
+            .include_thinking(true)
+            .export(&session);
+        assert!(output.contains(
+            "<details>\n<summary>Thinking</summary>\n\nCheck the config first.\n\n</details>\n\nDone."
+        ));
+    }
 }
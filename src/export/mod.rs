@@ -2,13 +2,17 @@ pub mod json;
 pub mod markdown;
 pub mod writer;
 
+use serde::Deserialize;
+
 use crate::domain::Session;
 
 /// エクスポート形式
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ExportFormat {
     /// Markdown 形式
     #[default]
+    #[serde(alias = "md")]
     Markdown,
     /// JSON 形式
     Json,
@@ -1,7 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 
-use katha::config::ClaudePaths;
+use katha::cli::{self, Command};
+use katha::config::{Config, DataRoots};
 use katha::data::HistoryReader;
+use katha::domain::pricing;
 use katha::tui::App;
 use tracing::{debug, error, info};
 use tracing_subscriber::EnvFilter;
@@ -16,19 +19,52 @@ fn main() {
         .with_writer(std::io::stderr)
         .init();
 
-    let args: Vec<String> = std::env::args().collect();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let cli = match cli::parse_args(&args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("Error: {}", e.user_message());
+            std::process::exit(e.exit_code());
+        }
+    };
+
+    // 設定ファイルは TUI / サブコマンドの実行時のみ読み込む
+    let load_config = || match Config::load() {
+        Ok(mut config) => {
+            config.data = DataRoots::resolve(&cli.roots, &config.data);
+            pricing::install(config.pricing.clone());
+            config
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e.user_message());
+            std::process::exit(e.exit_code());
+        }
+    };
 
-    if args.len() > 1 && args[1] == "--count-sessions" {
-        count_sessions();
-    } else {
-        run_tui();
+    match cli.command {
+        Command::Tui => run_tui(load_config()),
+        Command::CountSessions => count_sessions(&load_config().data),
+        // パイプ先が閉じていても失敗扱いにしない
+        Command::Help => {
+            let _ = writeln!(std::io::stdout(), "{}", cli::USAGE);
+        }
+        Command::Version => {
+            let _ = writeln!(std::io::stdout(), "katha {}", env!("CARGO_PKG_VERSION"));
+        }
+        command => {
+            if let Err(e) = cli::run(&command, &load_config()) {
+                debug!("Command failed: {:?}", e);
+                eprintln!("Error: {}", e.user_message());
+                std::process::exit(e.exit_code());
+            }
+        }
     }
 }
 
-fn run_tui() {
+fn run_tui(config: Config) {
     info!("Starting TUI...");
 
-    match App::new() {
+    match App::new(config) {
         Ok(mut app) => {
             debug!("App created successfully");
 
@@ -54,45 +90,52 @@ fn run_tui() {
     }
 }
 
-fn count_sessions() {
-    let paths = match ClaudePaths::new() {
-        Ok(p) => p,
+fn count_sessions(roots: &DataRoots) {
+    // すべての Claude ディレクトリを集計（出力形式は 1 ディレクトリの場合と同じ）
+    let paths = match roots.claude_paths() {
+        Ok(paths) => paths,
         Err(e) => {
             eprintln!("Error: {}", e);
             std::process::exit(1);
         }
     };
-
-    if !paths.history_exists() {
+    let paths: Vec<_> = paths
+        .into_iter()
+        .filter(|paths| paths.history_exists())
+        .collect();
+    if paths.is_empty() {
         eprintln!("Error: history.jsonl not found");
         std::process::exit(1);
     }
 
-    match HistoryReader::read_all(&paths.history_file) {
-        Ok(entries) => {
-            let session_count = entries
-                .iter()
-                .map(|e| &e.session_id)
-                .collect::<HashSet<_>>()
-                .len();
-
-            println!("Total entries: {}", entries.len());
-            println!("Unique sessions: {}", session_count);
-
-            let projects = HistoryReader::group_by_project(&paths.history_file).unwrap();
-            println!("\nProjects: {}", projects.len());
-
-            for (project, entries) in projects.iter().take(5) {
-                let name = project.rsplit('/').next().unwrap_or(project);
-                println!("  {} ({} entries)", name, entries.len());
-            }
-            if projects.len() > 5 {
-                println!("  ... and {} more", projects.len() - 5);
+    let mut total_entries = 0;
+    // 同じ ID でもディレクトリが違えば別セッション
+    let mut sessions = HashSet::new();
+    let mut projects: HashMap<String, usize> = HashMap::new();
+    for (root, paths) in paths.iter().enumerate() {
+        let entries = match HistoryReader::read_all(&paths.history_file) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
             }
+        };
+        total_entries += entries.len();
+        for entry in entries {
+            *projects.entry(entry.project().to_string()).or_default() += 1;
+            sessions.insert((root, entry.session_id));
         }
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
-        }
+    }
+
+    println!("Total entries: {}", total_entries);
+    println!("Unique sessions: {}", sessions.len());
+
+    println!("\nProjects: {}", projects.len());
+    for (project, entries) in projects.iter().take(5) {
+        let name = project.rsplit('/').next().unwrap_or(project);
+        println!("  {} ({} entries)", name, entries);
+    }
+    if projects.len() > 5 {
+        println!("  ... and {} more", projects.len() - 5);
     }
 }
@@ -0,0 +1,336 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use serde_json::Value;
+use tracing::warn;
+
+use crate::KathaError;
+use crate::data::{CodexSessionReader, SessionReader};
+use crate::domain::{SessionEntry, SessionKey, SessionSource};
+use crate::search::index::{term_counts, tokenize};
+use crate::search::{ContentTerms, SearchQuery};
+
+/// 本文検索の対象セッション
+#[derive(Debug, Clone)]
+pub struct ContentSearchTarget {
+    /// セッションの出所
+    pub source: SessionSource,
+    /// データディレクトリのラベル（同じ種類のディレクトリが複数ある場合のみ）
+    pub root: Option<String>,
+    /// セッション ID
+    pub session_id: String,
+    /// セッションファイルのパス
+    pub path: PathBuf,
+}
+
+impl ContentSearchTarget {
+    /// セッションのキー
+    pub fn key(&self) -> SessionKey {
+        (self.source, self.root.clone(), self.session_id.clone())
+    }
+
+    /// セッションファイルのエントリを読み込み
+    pub fn read_entries(&self) -> Result<Vec<SessionEntry>, KathaError> {
+        match self.source {
+            SessionSource::Claude => SessionReader::read_entries(&self.path),
+            SessionSource::Codex => CodexSessionReader::read_entries(&self.path),
+        }
+    }
+}
+
+/// 本文検索の結果
+#[derive(Debug, Clone, Default)]
+pub struct ContentSearchResult {
+    /// 検索に使用した語
+    pub terms: ContentTerms,
+    /// セッションごとのマッチ数（すべての語を含み、除外語を含まないセッションのみ）
+    pub hits: HashMap<SessionKey, usize>,
+    /// 除外語を含むセッション
+    pub excluded: HashSet<SessionKey>,
+}
+
+impl ContentSearchResult {
+    /// 検索に使用した語で空の結果を作成
+    pub fn new(terms: ContentTerms) -> Self {
+        Self {
+            terms,
+            ..Default::default()
+        }
+    }
+
+    /// セッションが本文検索の条件を満たすか
+    pub fn matches(&self, key: &SessionKey) -> bool {
+        !self.excluded.contains(key)
+            && (self.terms.include.is_empty() || self.hits.contains_key(key))
+    }
+
+    /// セッションのマッチ数を取得
+    pub fn hit_count(&self, key: &SessionKey) -> usize {
+        self.hits.get(key).copied().unwrap_or(0)
+    }
+
+    /// マッチしたセッション数
+    pub fn session_count(&self) -> usize {
+        self.hits.len()
+    }
+
+    /// 全セッションの合計マッチ数
+    pub fn total_hits(&self) -> usize {
+        self.hits.values().sum()
+    }
+}
+
+/// セッション本文の全文検索
+pub struct ContentSearcher;
+
+impl ContentSearcher {
+    /// 対象セッションファイルを読み込んでクエリのテキスト句を検索（インデックス未構築時用）
+    /// 検索語は本文と同じく語に分割し（`foo.bar` は `foo` と `bar`）、語の一部に含まれる回数を数える
+    /// 全語を含み除外語を含まないセッションのみを対象とし、マッチ数は語ごとのマッチ数の最小値
+    /// 大文字小文字は区別しない（[`SearchIndex::search`](crate::search::SearchIndex::search) と同じ）
+    pub fn search(targets: &[ContentSearchTarget], query: &SearchQuery) -> ContentSearchResult {
+        let mut result = ContentSearchResult::new(query.content_terms());
+        if result.terms.is_empty() {
+            return result;
+        }
+        let include: Vec<String> = result
+            .terms
+            .include
+            .iter()
+            .flat_map(|term| tokenize(term))
+            .collect();
+        let exclude: Vec<Vec<String>> = result
+            .terms
+            .exclude
+            .iter()
+            .map(|term| tokenize(term))
+            .collect();
+
+        for target in targets {
+            let entries = match target.read_entries() {
+                Ok(entries) => entries,
+                Err(e) => {
+                    warn!("Content search skipped {}: {}", target.path.display(), e);
+                    continue;
+                }
+            };
+
+            let terms = term_counts(&entries);
+            if exclude
+                .iter()
+                .any(|tokens| count_tokens(&terms, tokens) > 0)
+            {
+                result.excluded.insert(target.key());
+                continue;
+            }
+            let count = count_tokens(&terms, &include);
+            if count > 0 {
+                result.hits.insert(target.key(), count);
+            }
+        }
+
+        result
+    }
+
+    /// エントリ一覧の検索語のマッチ数を数える
+    pub fn count_entry_matches(entries: &[SessionEntry], text: &str) -> usize {
+        count_tokens(&term_counts(entries), &tokenize(text))
+    }
+}
+
+/// 語ごとの出現回数から検索語のマッチ数を数える
+/// 検索語ごとに語の一部に含まれる回数を合計し、その最小値を返す（検索語がない場合は 0）
+fn count_tokens(terms: &HashMap<String, u32>, tokens: &[String]) -> usize {
+    tokens
+        .iter()
+        .map(|token| {
+            terms
+                .iter()
+                .map(|(term, count)| term.matches(token.as_str()).count() * *count as usize)
+                .sum()
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// エントリの検索対象テキストを取得
+/// user / assistant の表示テキストと、ツール名・ツール入力の文字列値が対象
+pub fn searchable_texts(entry: &SessionEntry) -> Vec<String> {
+    if !entry.is_user() && !entry.is_assistant() {
+        return vec![];
+    }
+
+    let mut texts: Vec<String> = entry.display_text().into_iter().collect();
+    if let Some(message) = &entry.message {
+        for (_, name, input) in message.tool_uses() {
+            texts.push(name.to_string());
+            collect_json_strings(input, &mut texts);
+        }
+    }
+    texts
+}
+
+/// JSON 値に含まれる文字列を再帰的に収集
+fn collect_json_strings(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::String(s) => out.push(s.clone()),
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_json_strings(item, out)),
+        Value::Object(map) => map
+            .values()
+            .for_each(|item| collect_json_strings(item, out)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::message::{ContentBlock, Message, MessageContent};
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn text_entry(entry_type: &str, text: &str) -> SessionEntry {
+        SessionEntry {
+            entry_type: Some(entry_type.to_string()),
+            message: Some(Message {
+                role: entry_type.to_string(),
+                content: MessageContent::Text(text.to_string()),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn key(id: &str) -> SessionKey {
+        (SessionSource::Claude, None, id.to_string())
+    }
+
+    fn query(text: &str) -> SearchQuery {
+        SearchQuery {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_count_entry_matches() {
+        let entries = vec![
+            text_entry("user", "How do I fix the borrow checker error?"),
+            text_entry("assistant", "The Borrow checker complains because..."),
+            text_entry("system", "borrow"),
+        ];
+
+        assert_eq!(ContentSearcher::count_entry_matches(&entries, "borrow"), 2);
+        assert_eq!(
+            ContentSearcher::count_entry_matches(&entries, "lifetime"),
+            0
+        );
+        // 検索語も語に分割して評価
+        assert_eq!(
+            ContentSearcher::count_entry_matches(&entries, "checker.error"),
+            1
+        );
+    }
+
+    #[test]
+    fn test_searchable_texts_include_tool_input() {
+        let entry = SessionEntry {
+            entry_type: Some("assistant".to_string()),
+            message: Some(Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Blocks(vec![
+                    ContentBlock::Text {
+                        text: "Running tests".to_string(),
+                    },
+                    ContentBlock::ToolUse {
+                        id: "tool_1".to_string(),
+                        name: "Bash".to_string(),
+                        input: serde_json::json!({"command": "cargo test", "args": ["--all"]}),
+                    },
+                ]),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        };
+
+        let texts = searchable_texts(&entry);
+        assert!(texts.contains(&"Running tests".to_string()));
+        assert!(texts.contains(&"Bash".to_string()));
+        assert!(texts.contains(&"cargo test".to_string()));
+        assert!(texts.contains(&"--all".to_string()));
+    }
+
+    #[test]
+    fn test_search_reads_session_files() {
+        let dir = tempdir().unwrap();
+        let matching = dir.path().join("a.jsonl");
+        let other = dir.path().join("b.jsonl");
+        fs::write(
+            &matching,
+            concat!(
+                r#"{"type":"user","message":{"role":"user","content":"tokio runtime panic"}}"#,
+                "\n",
+                r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Use a tokio::main runtime"}]}}"#,
+                "\n"
+            ),
+        )
+        .unwrap();
+        fs::write(
+            &other,
+            r#"{"type":"user","message":{"role":"user","content":"unrelated"}}"#,
+        )
+        .unwrap();
+
+        let targets = vec![
+            ContentSearchTarget {
+                source: SessionSource::Claude,
+                root: None,
+                session_id: "a".to_string(),
+                path: matching,
+            },
+            ContentSearchTarget {
+                source: SessionSource::Claude,
+                root: None,
+                session_id: "b".to_string(),
+                path: other,
+            },
+            ContentSearchTarget {
+                source: SessionSource::Claude,
+                root: None,
+                session_id: "missing".to_string(),
+                path: dir.path().join("missing.jsonl"),
+            },
+        ];
+
+        let result = ContentSearcher::search(&targets, &query("tokio"));
+        assert_eq!(result.terms.include, vec!["tokio"]);
+        assert_eq!(result.session_count(), 1);
+        assert_eq!(result.hit_count(&key("a")), 2);
+        assert_eq!(result.hit_count(&key("b")), 0);
+        assert_eq!(result.total_hits(), 2);
+
+        // 語ごとに評価（隣接していなくてよい）し、マッチ数は最小値
+        let result = ContentSearcher::search(&targets, &query("panic tokio"));
+        assert_eq!(result.hit_count(&key("a")), 1);
+        let result = ContentSearcher::search(&targets, &query(r#""panic tokio""#));
+        assert_eq!(result.hit_count(&key("a")), 1);
+        let result = ContentSearcher::search(&targets, &query("tokio lifetime"));
+        assert_eq!(result.session_count(), 0);
+
+        // 否定された語は本文で評価
+        let result = ContentSearcher::search(&targets, &query("tokio -panic"));
+        assert_eq!(result.session_count(), 0);
+        assert!(!result.matches(&key("a")));
+        let result = ContentSearcher::search(&targets, &query("-panic"));
+        assert!(!result.matches(&key("a")));
+        assert!(result.matches(&key("b")));
+    }
+}
@@ -1,6 +1,23 @@
-use crate::search::{FilterCriteria, SearchQuery};
+use std::collections::HashMap;
+
+use crate::domain::{SessionKey, SessionStats};
+use crate::search::{
+    CompiledQuery, ContentSearchResult, FilterCriteria, QueryField, QueryPredicate, SearchQuery,
+    SearchScope, TextMatcher, Threshold,
+};
 use crate::tea::SessionListItem;
 
+/// 構造化クエリの評価に使うセッションデータ
+#[derive(Debug, Clone, Copy)]
+pub struct QueryContext<'a> {
+    /// セッションごとの集計メタデータ（model / tool / branch / cost の評価用）
+    pub stats: &'a HashMap<SessionKey, SessionStats>,
+    /// 検索範囲
+    pub scope: SearchScope,
+    /// 本文検索の結果（本文検索時のテキスト句はこれで評価）
+    pub content: Option<&'a ContentSearchResult>,
+}
+
 /// 検索エンジン
 pub struct SearchEngine;
 
@@ -31,7 +48,7 @@ impl SearchEngine {
         sessions
             .iter()
             .enumerate()
-            .filter(|(_, session)| Self::matches_criteria(session, criteria))
+            .filter(|(_, session)| Self::matches_criteria(session, None, criteria))
             .map(|(i, _)| i)
             .collect()
     }
@@ -53,7 +70,7 @@ impl SearchEngine {
 
                 // フィルタ条件にマッチ
                 let matches_filter =
-                    !criteria.is_set() || Self::matches_criteria(session, criteria);
+                    !criteria.is_set() || Self::matches_criteria(session, None, criteria);
 
                 matches_query && matches_filter
             })
@@ -61,8 +78,135 @@ impl SearchEngine {
             .collect()
     }
 
+    /// 本文検索結果 + フィルタの組み合わせ（AND条件）
+    pub fn search_content_and_filter(
+        sessions: &[SessionListItem],
+        result: &ContentSearchResult,
+        criteria: &FilterCriteria,
+    ) -> Vec<usize> {
+        sessions
+            .iter()
+            .enumerate()
+            .filter(|(_, session)| {
+                let matches_content = result.matches(&session.key());
+                let matches_filter =
+                    !criteria.is_set() || Self::matches_criteria(session, None, criteria);
+
+                matches_content && matches_filter
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// 構造化クエリ + フィルタの組み合わせ（AND条件）
+    /// マッチしたセッションのインデックスとスコアを元の順序で返す
+    pub fn rank_and_filter(
+        sessions: &[SessionListItem],
+        query: &CompiledQuery,
+        context: QueryContext<'_>,
+        criteria: &FilterCriteria,
+    ) -> Vec<(usize, i64)> {
+        sessions
+            .iter()
+            .enumerate()
+            .filter(|(_, session)| {
+                let stats = context.stats.get(&session.key());
+                !criteria.is_set() || Self::matches_criteria(session, stats, criteria)
+            })
+            .filter_map(|(i, session)| Some((i, Self::score_query(session, query, context)?)))
+            .collect()
+    }
+
+    /// セッションの構造化クエリに対するスコア（マッチしない場合は None）
+    /// スコアはテキスト句のマッチャーのスコアと本文検索のマッチ数の合計
+    /// 集計メタデータがないセッションは model / tool / branch / cost の条件にマッチしない
+    pub fn score_query(
+        session: &SessionListItem,
+        query: &CompiledQuery,
+        context: QueryContext<'_>,
+    ) -> Option<i64> {
+        let mut score = 0;
+        let is_content = context.scope == SearchScope::Content;
+        if is_content && let Some(result) = context.content {
+            let key = session.key();
+            if !result.matches(&key) {
+                return None;
+            }
+            score += result.hit_count(&key) as i64;
+        }
+
+        let stats = context.stats.get(&session.key());
+        for (clause, matcher) in query.clauses() {
+            // 本文検索時のテキスト句（否定を含む）は本文検索結果で評価済み
+            let is_text = matches!(clause.predicate, QueryPredicate::Text(_));
+            if is_content && is_text {
+                continue;
+            }
+
+            let clause_score = match matcher {
+                Some(matcher) => Self::score_text(session, matcher),
+                None => Self::evaluate_predicate(session, stats, &clause.predicate)
+                    .filter(|matched| *matched)
+                    .map(|_| 0),
+            };
+            match (clause.negated, clause_score) {
+                (false, Some(clause_score)) => score += clause_score,
+                (false, None) | (true, Some(_)) => return None,
+                (true, None) => {}
+            }
+        }
+
+        Some(score)
+    }
+
+    /// テキスト句のスコア（プロジェクト名と最新メッセージの高い方）
+    fn score_text(session: &SessionListItem, matcher: &TextMatcher) -> Option<i64> {
+        let project_score = matcher.score(&session.project_name);
+        let message_score = matcher.score(&session.latest_user_message);
+        project_score.max(message_score)
+    }
+
+    /// 条件を評価（判定に必要なデータがない場合は None）
+    fn evaluate_predicate(
+        session: &SessionListItem,
+        stats: Option<&SessionStats>,
+        predicate: &QueryPredicate,
+    ) -> Option<bool> {
+        let contains =
+            |target: &str, value: &str| target.to_lowercase().contains(&value.to_lowercase());
+        let any_contains =
+            |targets: &[String], value: &str| targets.iter().any(|target| contains(target, value));
+
+        match predicate {
+            QueryPredicate::Text(text) => Some(
+                contains(&session.project_name, text)
+                    || contains(&session.latest_user_message, text),
+            ),
+            QueryPredicate::Field(field, value) => match field {
+                QueryField::Project => Some(
+                    contains(&session.project_name, value)
+                        || contains(&session.project_path, value),
+                ),
+                QueryField::Source => Some(contains(session.source.label(), value)),
+                QueryField::Model => stats.map(|s| any_contains(&s.models, value)),
+                QueryField::Tool => stats.map(|s| any_contains(&s.tools, value)),
+                QueryField::Branch => stats.map(|s| any_contains(&s.git_branches, value)),
+            },
+            QueryPredicate::After(datetime) => Some(session.datetime >= *datetime),
+            QueryPredicate::Before(datetime) => Some(session.datetime < *datetime),
+            QueryPredicate::Cost(comparison, amount) => stats
+                .and_then(|s| s.cost_usd)
+                .map(|cost| comparison.compare(cost, *amount)),
+        }
+    }
+
     /// セッションがフィルタ条件にマッチするか
-    fn matches_criteria(session: &SessionListItem, criteria: &FilterCriteria) -> bool {
+    /// 集計メタデータがないセッションは model / branch / version / tool / 使用量の条件にマッチしない
+    fn matches_criteria(
+        session: &SessionListItem,
+        stats: Option<&SessionStats>,
+        criteria: &FilterCriteria,
+    ) -> bool {
         // 日付範囲チェック
         if criteria.date_range.is_set() && !criteria.date_range.contains(session.datetime) {
             return false;
@@ -79,6 +223,38 @@ impl SearchEngine {
             return false;
         }
 
+        if criteria
+            .source
+            .is_some_and(|source| source != session.source)
+        {
+            return false;
+        }
+
+        if criteria.has_metadata_filter() {
+            let Some(stats) = stats else {
+                return false;
+            };
+            let any_contains = |targets: &[String], value: &Option<String>| {
+                value.as_ref().is_none_or(|value| {
+                    let value = value.to_lowercase();
+                    targets
+                        .iter()
+                        .any(|target| target.to_lowercase().contains(&value))
+                })
+            };
+            // コストが計算できないセッションはコストの条件にマッチしない
+            let meets = |threshold: Option<Threshold>, value: Option<f64>| {
+                threshold.is_none_or(|threshold| value.is_some_and(|v| threshold.matches(v)))
+            };
+            return any_contains(&stats.models, &criteria.model)
+                && any_contains(&stats.git_branches, &criteria.branch)
+                && any_contains(&stats.versions, &criteria.version)
+                && any_contains(&stats.tools, &criteria.tool)
+                && meets(criteria.cost, stats.cost_usd)
+                && meets(criteria.tokens, Some(stats.total_tokens as f64))
+                && meets(criteria.messages, Some(stats.message_count as f64));
+        }
+
         true
     }
 }
@@ -86,35 +262,42 @@ impl SearchEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::search::DateRange;
+    use crate::domain::SessionSource;
+    use crate::search::{ContentTerms, DateRange, MatchMode};
     use chrono::{TimeZone, Utc};
 
     fn create_test_sessions() -> Vec<SessionListItem> {
         vec![
             SessionListItem {
                 session_id: "1".to_string(),
-                source: crate::tea::SessionSource::Claude,
+                source: crate::domain::SessionSource::Claude,
+                root: None,
                 project_name: "my-project".to_string(),
                 project_path: "/path/to/my-project".to_string(),
                 latest_user_message: "Hello world".to_string(),
+                first_user_message: String::new(),
                 formatted_time: "2025-01-15 10:00".to_string(),
                 datetime: Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap(),
             },
             SessionListItem {
                 session_id: "2".to_string(),
-                source: crate::tea::SessionSource::Claude,
+                source: crate::domain::SessionSource::Claude,
+                root: None,
                 project_name: "another-app".to_string(),
                 project_path: "/path/to/another-app".to_string(),
                 latest_user_message: "Fix bug in login".to_string(),
+                first_user_message: String::new(),
                 formatted_time: "2025-01-10 14:30".to_string(),
                 datetime: Utc.with_ymd_and_hms(2025, 1, 10, 14, 30, 0).unwrap(),
             },
             SessionListItem {
                 session_id: "3".to_string(),
-                source: crate::tea::SessionSource::Claude,
+                source: crate::domain::SessionSource::Claude,
+                root: None,
                 project_name: "my-project".to_string(),
                 project_path: "/path/to/my-project".to_string(),
                 latest_user_message: "Add new feature".to_string(),
+                first_user_message: String::new(),
                 formatted_time: "2025-01-05 09:00".to_string(),
                 datetime: Utc.with_ymd_and_hms(2025, 1, 5, 9, 0, 0).unwrap(),
             },
@@ -136,6 +319,7 @@ mod tests {
         let query = SearchQuery {
             text: "my-project".to_string(),
             case_sensitive: false,
+            ..Default::default()
         };
 
         let result = SearchEngine::search(&sessions, &query);
@@ -148,6 +332,7 @@ mod tests {
         let query = SearchQuery {
             text: "bug".to_string(),
             case_sensitive: false,
+            ..Default::default()
         };
 
         let result = SearchEngine::search(&sessions, &query);
@@ -160,6 +345,7 @@ mod tests {
         let query = SearchQuery {
             text: "HELLO".to_string(),
             case_sensitive: false,
+            ..Default::default()
         };
 
         let result = SearchEngine::search(&sessions, &query);
@@ -172,6 +358,7 @@ mod tests {
         let query = SearchQuery {
             text: "xyz".to_string(),
             case_sensitive: false,
+            ..Default::default()
         };
 
         let result = SearchEngine::search(&sessions, &query);
@@ -196,6 +383,7 @@ mod tests {
                 to: Some(Utc.with_ymd_and_hms(2025, 1, 20, 0, 0, 0).unwrap()),
             },
             project_filter: None,
+            ..Default::default()
         };
 
         let result = SearchEngine::filter(&sessions, &criteria);
@@ -208,6 +396,7 @@ mod tests {
         let criteria = FilterCriteria {
             date_range: DateRange::default(),
             project_filter: Some("another".to_string()),
+            ..Default::default()
         };
 
         let result = SearchEngine::filter(&sessions, &criteria);
@@ -220,6 +409,7 @@ mod tests {
         let query = SearchQuery {
             text: "my-project".to_string(),
             case_sensitive: false,
+            ..Default::default()
         };
         let criteria = FilterCriteria {
             date_range: DateRange {
@@ -227,6 +417,7 @@ mod tests {
                 to: None,
             },
             project_filter: None,
+            ..Default::default()
         };
 
         let result = SearchEngine::search_and_filter(&sessions, &query, &criteria);
@@ -239,6 +430,7 @@ mod tests {
         let criteria = FilterCriteria {
             date_range: DateRange::default(),
             project_filter: Some("ANOTHER".to_string()),
+            ..Default::default()
         };
 
         let result = SearchEngine::filter(&sessions, &criteria);
@@ -251,9 +443,307 @@ mod tests {
         let criteria = FilterCriteria {
             date_range: DateRange::default(),
             project_filter: Some(String::new()), // Empty string should not filter
+            ..Default::default()
         };
 
         let result = SearchEngine::filter(&sessions, &criteria);
         assert_eq!(result, vec![0, 1, 2]);
     }
+
+    fn parsed_search(
+        sessions: &[SessionListItem],
+        text: &str,
+        stats: &HashMap<SessionKey, SessionStats>,
+    ) -> Vec<usize> {
+        let context = QueryContext {
+            stats,
+            scope: SearchScope::Metadata,
+            content: None,
+        };
+        let query = SearchQuery {
+            text: text.to_string(),
+            ..Default::default()
+        };
+        SearchEngine::rank_and_filter(
+            sessions,
+            &query.compile().unwrap(),
+            context,
+            &FilterCriteria::default(),
+        )
+        .into_iter()
+        .map(|(i, _)| i)
+        .collect()
+    }
+
+    #[test]
+    fn test_search_parsed_metadata() {
+        let sessions = create_test_sessions();
+        let stats = HashMap::new();
+
+        assert_eq!(parsed_search(&sessions, "", &stats), vec![0, 1, 2]);
+        assert_eq!(parsed_search(&sessions, "project:my", &stats), vec![0, 2]);
+        assert_eq!(
+            parsed_search(&sessions, "project:my -feature", &stats),
+            vec![0]
+        );
+        assert_eq!(parsed_search(&sessions, "\"fix bug\"", &stats), vec![1]);
+        assert_eq!(parsed_search(&sessions, "fix login", &stats), vec![1]);
+        assert_eq!(
+            parsed_search(&sessions, "source:codex", &stats),
+            Vec::<usize>::new()
+        );
+        assert_eq!(
+            parsed_search(&sessions, "after:2025-01-10 before:2025-01-15", &stats),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_search_parsed_session_stats() {
+        let sessions = create_test_sessions();
+        let mut stats = HashMap::new();
+        stats.insert(
+            (SessionSource::Claude, None, "1".to_string()),
+            SessionStats {
+                models: vec!["claude-opus-4-5".to_string()],
+                tools: vec!["Bash".to_string(), "Edit".to_string()],
+                git_branches: vec!["main".to_string()],
+                cost_usd: Some(1.2),
+                ..Default::default()
+            },
+        );
+        stats.insert(
+            (SessionSource::Claude, None, "2".to_string()),
+            SessionStats {
+                models: vec!["claude-sonnet-4-5".to_string()],
+                tools: vec!["Read".to_string()],
+                git_branches: vec!["fix/login".to_string()],
+                cost_usd: Some(0.1),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(parsed_search(&sessions, "model:opus", &stats), vec![0]);
+        assert_eq!(parsed_search(&sessions, "tool:bash", &stats), vec![0]);
+        assert_eq!(parsed_search(&sessions, "branch:login", &stats), vec![1]);
+        assert_eq!(parsed_search(&sessions, "cost>0.5", &stats), vec![0]);
+        assert_eq!(parsed_search(&sessions, "cost<=0.5", &stats), vec![1]);
+        // 集計メタデータのないセッションは否定条件では除外されない
+        assert_eq!(parsed_search(&sessions, "-model:opus", &stats), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_filter_by_session_metadata() {
+        let mut sessions = create_test_sessions();
+        sessions[2].source = SessionSource::Codex;
+        let mut stats = HashMap::new();
+        stats.insert(
+            (SessionSource::Claude, None, "1".to_string()),
+            SessionStats {
+                models: vec!["claude-opus-4-5".to_string()],
+                tools: vec!["Bash".to_string()],
+                git_branches: vec!["main".to_string()],
+                versions: vec!["2.0.14".to_string()],
+                message_count: 40,
+                total_tokens: 250_000,
+                cost_usd: Some(3.2),
+                ..Default::default()
+            },
+        );
+        stats.insert(
+            (SessionSource::Claude, None, "2".to_string()),
+            SessionStats {
+                models: vec!["claude-sonnet-4-5".to_string()],
+                tools: vec!["Read".to_string()],
+                git_branches: vec!["fix/login".to_string()],
+                versions: vec!["1.0.98".to_string()],
+                message_count: 6,
+                total_tokens: 12_000,
+                ..Default::default()
+            },
+        );
+        let context = QueryContext {
+            stats: &stats,
+            scope: SearchScope::Metadata,
+            content: None,
+        };
+        let query = SearchQuery::default().compile().unwrap();
+        let filter = |criteria: FilterCriteria| -> Vec<usize> {
+            SearchEngine::rank_and_filter(&sessions, &query, context, &criteria)
+                .into_iter()
+                .map(|(i, _)| i)
+                .collect()
+        };
+
+        let source = |source| FilterCriteria {
+            source: Some(source),
+            ..Default::default()
+        };
+        assert_eq!(filter(source(SessionSource::Claude)), vec![0, 1]);
+        assert_eq!(filter(source(SessionSource::Codex)), vec![2]);
+        assert_eq!(
+            filter(FilterCriteria {
+                model: Some("SONNET".to_string()),
+                ..Default::default()
+            }),
+            vec![1]
+        );
+        assert_eq!(
+            filter(FilterCriteria {
+                version: Some("2.0".to_string()),
+                tool: Some("bash".to_string()),
+                ..Default::default()
+            }),
+            vec![0]
+        );
+        // 条件の組み合わせは AND、集計メタデータのないセッションは除外
+        assert_eq!(
+            filter(FilterCriteria {
+                branch: Some("main".to_string()),
+                tool: Some("Read".to_string()),
+                ..Default::default()
+            }),
+            Vec::<usize>::new()
+        );
+
+        // 使用量のしきい値（コスト不明のセッションはコストの条件から除外）
+        let threshold = |s: &str| Some(s.parse().unwrap());
+        assert_eq!(
+            filter(FilterCriteria {
+                tokens: threshold(">=10k"),
+                ..Default::default()
+            }),
+            vec![0, 1]
+        );
+        assert_eq!(
+            filter(FilterCriteria {
+                messages: threshold("<10"),
+                ..Default::default()
+            }),
+            vec![1]
+        );
+        assert_eq!(
+            filter(FilterCriteria {
+                cost: threshold("<5"),
+                ..Default::default()
+            }),
+            vec![0]
+        );
+    }
+
+    #[test]
+    fn test_search_parsed_content_scope() {
+        let sessions = create_test_sessions();
+        let stats = HashMap::new();
+        let mut result = ContentSearchResult::new(ContentTerms {
+            include: vec!["tokio".to_string()],
+            ..Default::default()
+        });
+        result
+            .hits
+            .insert((SessionSource::Claude, None, "1".to_string()), 2);
+        result
+            .hits
+            .insert((SessionSource::Claude, None, "2".to_string()), 1);
+
+        let context = QueryContext {
+            stats: &stats,
+            scope: SearchScope::Content,
+            content: Some(&result),
+        };
+        let rank = |text: &str, context: QueryContext<'_>| {
+            let query = SearchQuery {
+                text: text.to_string(),
+                scope: SearchScope::Content,
+                ..Default::default()
+            };
+            SearchEngine::rank_and_filter(
+                &sessions,
+                &query.compile().unwrap(),
+                context,
+                &FilterCriteria::default(),
+            )
+        };
+        assert_eq!(rank("tokio -project:another", context), vec![(0, 2)]);
+
+        // 否定されたテキスト句は最新メッセージではなく本文検索結果で評価
+        let mut result = result.clone();
+        result.terms.exclude = vec!["hello".to_string()];
+        result
+            .excluded
+            .insert((SessionSource::Claude, None, "2".to_string()));
+        let context = QueryContext {
+            content: Some(&result),
+            ..context
+        };
+        assert_eq!(rank("tokio -hello", context), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn test_rank_fuzzy_and_regex() {
+        let sessions = create_test_sessions();
+        let stats = HashMap::new();
+        let context = QueryContext {
+            stats: &stats,
+            scope: SearchScope::Metadata,
+            content: None,
+        };
+        let rank = |text: &str, match_mode: MatchMode| {
+            let query = SearchQuery {
+                text: text.to_string(),
+                match_mode,
+                ..Default::default()
+            };
+            SearchEngine::rank_and_filter(
+                &sessions,
+                &query.compile().unwrap(),
+                context,
+                &FilterCriteria::default(),
+            )
+        };
+
+        // "ne" は "Add new feature" の単語頭から連続一致するため "another-app" より高スコア
+        let ranked = rank("ne", MatchMode::Fuzzy);
+        let indices: Vec<usize> = ranked.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, vec![1, 2]);
+        assert!(ranked[1].1 > ranked[0].1);
+
+        let ranked = rank(r"^(hello|add)\b", MatchMode::Regex);
+        assert_eq!(ranked, vec![(0, 1), (2, 1)]);
+
+        let ranked = rank("project", MatchMode::Substring);
+        assert_eq!(ranked, vec![(0, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn test_search_content_and_filter() {
+        let sessions = create_test_sessions();
+        let mut result = ContentSearchResult::new(ContentTerms {
+            include: vec!["tokio".to_string()],
+            ..Default::default()
+        });
+        result.hits.insert(
+            (crate::domain::SessionSource::Claude, None, "1".to_string()),
+            4,
+        );
+        result.hits.insert(
+            (crate::domain::SessionSource::Claude, None, "3".to_string()),
+            1,
+        );
+
+        let criteria = FilterCriteria::default();
+        let matched = SearchEngine::search_content_and_filter(&sessions, &result, &criteria);
+        assert_eq!(matched, vec![0, 2]);
+
+        let criteria = FilterCriteria {
+            date_range: DateRange {
+                from: Some(Utc.with_ymd_and_hms(2025, 1, 10, 0, 0, 0).unwrap()),
+                to: None,
+            },
+            project_filter: None,
+            ..Default::default()
+        };
+        let matched = SearchEngine::search_content_and_filter(&sessions, &result, &criteria);
+        assert_eq!(matched, vec![0]);
+    }
 }
@@ -1,4 +1,14 @@
-use chrono::{DateTime, Duration, Utc};
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+use serde::Deserialize;
+
+use crate::domain::SessionSource;
+use crate::search::Comparison;
 
 /// 日付範囲
 #[derive(Debug, Clone, Default)]
@@ -10,15 +20,44 @@ pub struct DateRange {
 }
 
 impl DateRange {
-    /// 今日（UTC ベース）
+    /// 今日（ローカルタイムゾーンの 0 時から現在まで）
     pub fn today() -> Self {
-        let now = Utc::now();
-        let start_of_today = now.date_naive().and_hms_opt(0, 0, 0).map(|dt| dt.and_utc());
+        Self::today_at(Local::now())
+    }
 
+    /// 指定時刻のタイムゾーンでの今日
+    pub fn today_at<Tz: TimeZone>(now: DateTime<Tz>) -> Self {
         Self {
-            from: start_of_today,
-            to: Some(now),
+            from: Some(DateInput::Day(now.date_naive()).start(&now.timezone())),
+            to: Some(now.to_utc()),
+        }
+    }
+
+    /// フィルタパネルの入力から作成（空欄は制限なし）
+    pub fn from_inputs<Tz: TimeZone>(
+        from: &str,
+        to: &str,
+        now: DateTime<Tz>,
+    ) -> Result<Self, String> {
+        let tz = now.timezone();
+        let parse = |label: &str, input: &str| {
+            if input.trim().is_empty() {
+                return Ok(None);
+            }
+            DateInput::parse(input, now.clone())
+                .map(Some)
+                .map_err(|e| format!("{}: {}", label, e))
+        };
+        let range = Self {
+            from: parse("From", from)?.map(|input| input.start(&tz)),
+            to: parse("To", to)?.map(|input| input.end(&tz)),
+        };
+        if let (Some(from), Some(to)) = (range.from, range.to)
+            && from > to
+        {
+            return Err("From is after To".to_string());
         }
+        Ok(range)
     }
 
     /// 過去1週間
@@ -64,6 +103,191 @@ impl DateRange {
     }
 }
 
+/// フィルタパネルに入力された日付
+/// "2025-01-31", "2025-01-31 18:00", "today", "yesterday", "now", "-3d", "-2w", "-6h",
+/// "monday", "last monday" の形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateInput {
+    /// 日単位（開始は 0 時、終了は 23:59:59 まで）
+    Day(NaiveDate),
+    /// 時刻まで指定
+    Instant(DateTime<Utc>),
+}
+
+impl DateInput {
+    /// 現在時刻を基準に解析
+    pub fn parse<Tz: TimeZone>(input: &str, now: DateTime<Tz>) -> Result<Self, String> {
+        let value = input.trim().to_ascii_lowercase();
+        let today = now.date_naive();
+        let invalid = || format!("invalid date '{}'", input.trim());
+
+        match value.as_str() {
+            "today" => return Ok(Self::Day(today)),
+            "yesterday" => return today.pred_opt().map(Self::Day).ok_or_else(invalid),
+            "now" => return Ok(Self::Instant(now.to_utc())),
+            _ => {}
+        }
+
+        // 相対指定（-3d, -2w, -6h）
+        if let Some(relative) = value.strip_prefix('-') {
+            // 末尾の 1 文字を単位とする（マルチバイト文字でも文字境界で分割）
+            let split = relative.char_indices().last().map_or(0, |(i, _)| i);
+            let (number, unit) = relative.split_at(split);
+            let n: i64 = number.parse().map_err(|_| invalid())?;
+            return match unit {
+                // 範囲外の日時は入力途中でもパニックさせずエラーにする
+                "d" => Duration::try_days(n)
+                    .and_then(|d| today.checked_sub_signed(d))
+                    .map(Self::Day),
+                "w" => Duration::try_weeks(n)
+                    .and_then(|d| today.checked_sub_signed(d))
+                    .map(Self::Day),
+                "h" => Duration::try_hours(n)
+                    .and_then(|d| now.to_utc().checked_sub_signed(d))
+                    .map(Self::Instant),
+                _ => None,
+            }
+            .ok_or_else(invalid);
+        }
+
+        // 曜日（"monday" は今日を含む直近、"last monday" は今日より前の直近）
+        let (strictly_before, day_name) = match value.strip_prefix("last ") {
+            Some(rest) => (true, rest.trim()),
+            None => (false, value.as_str()),
+        };
+        if let Ok(weekday) = day_name.parse::<Weekday>() {
+            let mut days_back =
+                (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+            if strictly_before && days_back == 0 {
+                days_back = 7;
+            }
+            return Ok(Self::Day(today - Duration::days(i64::from(days_back))));
+        }
+
+        // 絶対指定
+        let value = input.trim();
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            return Ok(Self::Day(date));
+        }
+        for format in ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"] {
+            if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+                return Ok(Self::Instant(local_datetime(&now.timezone(), datetime)));
+            }
+        }
+        Err(invalid())
+    }
+
+    /// 範囲の開始として使う日時
+    pub fn start<Tz: TimeZone>(&self, tz: &Tz) -> DateTime<Utc> {
+        match self {
+            Self::Day(date) => start_of_day(tz, *date),
+            Self::Instant(instant) => *instant,
+        }
+    }
+
+    /// 範囲の終了として使う日時（日単位ならその日の終わり）
+    pub fn end<Tz: TimeZone>(&self, tz: &Tz) -> DateTime<Utc> {
+        match self {
+            Self::Day(date) => match date.succ_opt() {
+                Some(next) => start_of_day(tz, next) - Duration::nanoseconds(1),
+                None => DateTime::<Utc>::MAX_UTC,
+            },
+            Self::Instant(instant) => *instant,
+        }
+    }
+}
+
+/// ローカル時刻を UTC に変換（夏時間で存在しない時刻は UTC として扱う）
+fn local_datetime<Tz: TimeZone>(tz: &Tz, datetime: NaiveDateTime) -> DateTime<Utc> {
+    tz.from_local_datetime(&datetime)
+        .earliest()
+        .map(|dt| dt.to_utc())
+        .unwrap_or_else(|| datetime.and_utc())
+}
+
+/// 日付の 0 時（指定タイムゾーン）
+pub(crate) fn start_of_day<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> DateTime<Utc> {
+    local_datetime(tz, date.and_time(NaiveTime::MIN))
+}
+
+/// フィルタパネルの日付プリセット（先頭の All は常に表示）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum DatePreset {
+    /// 今日
+    Today,
+    /// 過去 N 日
+    LastDays(u32),
+}
+
+impl DatePreset {
+    /// 既定のプリセット（Today, Last 7 days, Last 30 days）
+    pub fn defaults() -> Vec<Self> {
+        vec![Self::Today, Self::LastDays(7), Self::LastDays(30)]
+    }
+
+    /// 表示名
+    pub fn label(&self) -> String {
+        match self {
+            Self::Today => "Today".to_string(),
+            Self::LastDays(1) => "Last 1 day".to_string(),
+            Self::LastDays(days) => format!("Last {} days", days),
+        }
+    }
+
+    /// 対応する日付範囲
+    pub fn date_range(&self) -> DateRange {
+        match self {
+            Self::Today => DateRange::today(),
+            Self::LastDays(days) => {
+                let now = Utc::now();
+                DateRange {
+                    from: Some(now - Duration::days(i64::from(*days))),
+                    to: Some(now),
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for DatePreset {
+    type Err = String;
+
+    /// "today", "7d", "2w" の形式
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim().to_ascii_lowercase();
+        if value == "today" {
+            return Ok(Self::Today);
+        }
+        let (number, unit_days) = if let Some(number) = value.strip_suffix('d') {
+            (number, 1)
+        } else if let Some(number) = value.strip_suffix('w') {
+            (number, 7)
+        } else {
+            (value.as_str(), 0)
+        };
+        let days = number
+            .parse::<u32>()
+            .ok()
+            .and_then(|n| n.checked_mul(unit_days));
+        match days {
+            Some(days) if days > 0 => Ok(Self::LastDays(days)),
+            _ => Err(format!(
+                "invalid date preset '{}' (expected \"today\", \"<N>d\" or \"<N>w\")",
+                s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for DatePreset {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 /// フィルタ条件
 #[derive(Debug, Clone, Default)]
 pub struct FilterCriteria {
@@ -71,18 +295,134 @@ pub struct FilterCriteria {
     pub date_range: DateRange,
     /// プロジェクトフィルタ（部分一致）
     pub project_filter: Option<String>,
+    /// 履歴の種類（Claude / Codex）
+    pub source: Option<SessionSource>,
+    /// 使用モデル（部分一致）
+    pub model: Option<String>,
+    /// Git ブランチ（部分一致）
+    pub branch: Option<String>,
+    /// Claude Code のバージョン（部分一致）
+    pub version: Option<String>,
+    /// 使用ツール（部分一致）
+    pub tool: Option<String>,
+    /// 推定コスト（USD）
+    pub cost: Option<Threshold>,
+    /// 合計トークン数
+    pub tokens: Option<Threshold>,
+    /// メッセージ数
+    pub messages: Option<Threshold>,
 }
 
 impl FilterCriteria {
     /// フィルタが設定されているか
     pub fn is_set(&self) -> bool {
-        self.date_range.is_set() || self.project_filter.is_some()
+        self.date_range.is_set()
+            || self.project_filter.is_some()
+            || self.source.is_some()
+            || self.has_metadata_filter()
+    }
+
+    /// 集計メタデータ（model / branch / version / tool / 使用量）の条件があるか
+    pub fn has_metadata_filter(&self) -> bool {
+        self.model.is_some()
+            || self.branch.is_some()
+            || self.version.is_some()
+            || self.tool.is_some()
+            || self.cost.is_some()
+            || self.tokens.is_some()
+            || self.messages.is_some()
+    }
+
+    /// 設定されている条件（ステータスバー表示用のラベルと値）
+    pub fn labels(&self) -> Vec<(&'static str, String)> {
+        let mut labels = Vec::new();
+        if let Some(project) = &self.project_filter {
+            labels.push(("Project", project.clone()));
+        }
+        if let Some(source) = self.source {
+            labels.push(("Source", source.label().to_string()));
+        }
+        for (label, value) in [
+            ("Model", &self.model),
+            ("Branch", &self.branch),
+            ("Version", &self.version),
+            ("Tool", &self.tool),
+        ] {
+            if let Some(value) = value {
+                labels.push((label, value.clone()));
+            }
+        }
+        for (label, threshold) in [
+            ("Cost", self.cost),
+            ("Tokens", self.tokens),
+            ("Messages", self.messages),
+        ] {
+            if let Some(threshold) = threshold {
+                labels.push((label, threshold.to_string()));
+            }
+        }
+        labels
     }
 
     /// フィルタをクリア
     pub fn clear(&mut self) {
-        self.date_range = DateRange::default();
-        self.project_filter = None;
+        *self = Self::default();
+    }
+}
+
+/// 数値のしきい値（">0.5"、"<=100k"、演算子なしは以上）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Threshold {
+    /// 比較演算子
+    pub comparison: Comparison,
+    /// しきい値
+    pub value: f64,
+}
+
+impl Threshold {
+    /// 値がしきい値の条件を満たすか
+    pub fn matches(self, value: f64) -> bool {
+        self.comparison.compare(value, self.value)
+    }
+}
+
+impl FromStr for Threshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim();
+        let (comparison, rest) = [
+            (">=", Comparison::Ge),
+            ("<=", Comparison::Le),
+            (">", Comparison::Gt),
+            ("<", Comparison::Lt),
+            ("=", Comparison::Eq),
+        ]
+        .into_iter()
+        .find_map(|(symbol, comparison)| input.strip_prefix(symbol).map(|rest| (comparison, rest)))
+        .unwrap_or((Comparison::Ge, input));
+
+        let number = rest.trim().trim_start_matches('$').to_ascii_lowercase();
+        let (number, scale) = if let Some(number) = number.strip_suffix('k') {
+            (number, 1_000.0)
+        } else if let Some(number) = number.strip_suffix('m') {
+            (number, 1_000_000.0)
+        } else {
+            (number.as_str(), 1.0)
+        };
+        match number.parse::<f64>() {
+            Ok(value) if value.is_finite() && value >= 0.0 => Ok(Self {
+                comparison,
+                value: value * scale,
+            }),
+            _ => Err(format!("invalid threshold '{}'", input)),
+        }
+    }
+}
+
+impl fmt::Display for Threshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.comparison.symbol(), self.value)
     }
 }
 
@@ -92,24 +432,59 @@ pub enum FilterField {
     /// 日付範囲
     #[default]
     DateRange,
+    /// 日付範囲の開始（入力）
+    From,
+    /// 日付範囲の終了（入力）
+    To,
     /// プロジェクト
     Project,
+    /// 履歴の種類（選択）
+    Source,
+    /// 使用モデル
+    Model,
+    /// Git ブランチ
+    Branch,
+    /// Claude Code のバージョン
+    Version,
+    /// 使用ツール
+    Tool,
+    /// 推定コスト（USD）
+    Cost,
+    /// 合計トークン数
+    Tokens,
+    /// メッセージ数
+    Messages,
 }
 
 impl FilterField {
     /// 次のフィールドに移動
     pub fn next(self) -> Self {
         match self {
-            FilterField::DateRange => FilterField::Project,
-            FilterField::Project => FilterField::DateRange,
+            FilterField::DateRange => FilterField::From,
+            FilterField::From => FilterField::To,
+            FilterField::To => FilterField::Project,
+            FilterField::Project => FilterField::Source,
+            FilterField::Source => FilterField::Model,
+            FilterField::Model => FilterField::Branch,
+            FilterField::Branch => FilterField::Version,
+            FilterField::Version => FilterField::Tool,
+            FilterField::Tool => FilterField::Cost,
+            FilterField::Cost => FilterField::Tokens,
+            FilterField::Tokens => FilterField::Messages,
+            FilterField::Messages => FilterField::DateRange,
         }
     }
+
+    /// 文字入力を受け付けるフィールドか
+    pub fn accepts_text(self) -> bool {
+        !matches!(self, FilterField::DateRange | FilterField::Source)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::TimeZone;
+    use chrono::FixedOffset;
 
     #[test]
     fn test_date_range_default() {
@@ -187,6 +562,7 @@ mod tests {
         let criteria = FilterCriteria {
             date_range: DateRange::today(),
             project_filter: None,
+            ..Default::default()
         };
         assert!(criteria.is_set());
     }
@@ -196,6 +572,7 @@ mod tests {
         let criteria = FilterCriteria {
             date_range: DateRange::default(),
             project_filter: Some("my-project".to_string()),
+            ..Default::default()
         };
         assert!(criteria.is_set());
     }
@@ -205,17 +582,173 @@ mod tests {
         let mut criteria = FilterCriteria {
             date_range: DateRange::today(),
             project_filter: Some("test".to_string()),
+            source: Some(SessionSource::Codex),
+            tool: Some("Bash".to_string()),
+            ..Default::default()
         };
+        assert!(criteria.has_metadata_filter());
+        assert_eq!(
+            criteria.labels(),
+            vec![
+                ("Project", "test".to_string()),
+                ("Source", "Codex".to_string()),
+                ("Tool", "Bash".to_string()),
+            ]
+        );
 
         criteria.clear();
         assert!(!criteria.is_set());
         assert!(criteria.date_range.from.is_none());
         assert!(criteria.project_filter.is_none());
+        assert!(criteria.source.is_none());
+        assert!(!criteria.has_metadata_filter());
+    }
+
+    #[test]
+    fn test_parse_threshold() {
+        let parse = |s: &str| s.parse::<Threshold>();
+        assert_eq!(
+            parse(">0.5"),
+            Ok(Threshold {
+                comparison: Comparison::Gt,
+                value: 0.5
+            })
+        );
+        assert_eq!(
+            parse("<= 100k"),
+            Ok(Threshold {
+                comparison: Comparison::Le,
+                value: 100_000.0
+            })
+        );
+        // 演算子なしは以上
+        assert_eq!(
+            parse("$2"),
+            Ok(Threshold {
+                comparison: Comparison::Ge,
+                value: 2.0
+            })
+        );
+        assert_eq!(parse("1.5M").unwrap().value, 1_500_000.0);
+        assert!(parse(">").is_err());
+        assert!(parse("-1").is_err());
+        assert!(parse("abc").is_err());
+
+        let threshold = parse(">=20").unwrap();
+        assert!(threshold.matches(20.0));
+        assert!(!threshold.matches(19.0));
+        assert_eq!(threshold.to_string(), ">=20");
+        assert_eq!(parse(&threshold.to_string()), Ok(threshold));
+    }
+
+    #[test]
+    fn test_parse_date_preset() {
+        assert_eq!("today".parse(), Ok(DatePreset::Today));
+        assert_eq!("14d".parse(), Ok(DatePreset::LastDays(14)));
+        assert_eq!("2w".parse(), Ok(DatePreset::LastDays(14)));
+        assert_eq!(DatePreset::LastDays(14).label(), "Last 14 days");
+        assert!("0d".parse::<DatePreset>().is_err());
+        assert!("month".parse::<DatePreset>().is_err());
     }
 
     #[test]
     fn test_filter_field_next() {
-        assert_eq!(FilterField::DateRange.next(), FilterField::Project);
-        assert_eq!(FilterField::Project.next(), FilterField::DateRange);
+        assert_eq!(FilterField::DateRange.next(), FilterField::From);
+        assert_eq!(FilterField::From.next(), FilterField::To);
+        assert_eq!(FilterField::To.next(), FilterField::Project);
+        assert_eq!(FilterField::Project.next(), FilterField::Source);
+        assert_eq!(FilterField::Tool.next(), FilterField::Cost);
+        assert_eq!(FilterField::Messages.next(), FilterField::DateRange);
+        assert!(!FilterField::Source.accepts_text());
+        assert!(FilterField::Version.accepts_text());
+    }
+
+    #[test]
+    fn test_date_range_today_in_local_timezone() {
+        // JST の朝 8 時なら今日は前日 15:00 UTC から
+        let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+        let now = jst.with_ymd_and_hms(2025, 1, 2, 8, 0, 0).unwrap();
+        let range = DateRange::today_at(now);
+        assert_eq!(
+            range.from,
+            Some(Utc.with_ymd_and_hms(2025, 1, 1, 15, 0, 0).unwrap())
+        );
+        assert!(range.contains(Utc.with_ymd_and_hms(2025, 1, 1, 22, 30, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_date_input() {
+        let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+        // 2025-01-15 は水曜日
+        let now = jst.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap();
+        let day = |y, m, d| Ok(DateInput::Day(NaiveDate::from_ymd_opt(y, m, d).unwrap()));
+        let parse = |s: &str| DateInput::parse(s, now);
+
+        assert_eq!(parse("2025-01-03"), day(2025, 1, 3));
+        assert_eq!(parse(" Today "), day(2025, 1, 15));
+        assert_eq!(parse("yesterday"), day(2025, 1, 14));
+        assert_eq!(parse("-3d"), day(2025, 1, 12));
+        assert_eq!(parse("-2w"), day(2025, 1, 1));
+        assert_eq!(parse("monday"), day(2025, 1, 13));
+        assert_eq!(parse("wednesday"), day(2025, 1, 15));
+        assert_eq!(parse("last wed"), day(2025, 1, 8));
+        assert_eq!(
+            parse("-6h"),
+            Ok(DateInput::Instant(
+                Utc.with_ymd_and_hms(2025, 1, 14, 19, 30, 0).unwrap()
+            ))
+        );
+        assert_eq!(
+            parse("2025-01-10T09:00"),
+            Ok(DateInput::Instant(
+                Utc.with_ymd_and_hms(2025, 1, 10, 0, 0, 0).unwrap()
+            ))
+        );
+        for input in ["", "-3x", "2025-13-01", "next monday", "-d"] {
+            assert!(parse(input).is_err(), "{}", input);
+        }
+        // マルチバイト文字で終わる相対指定
+        for input in ["-日", "-é", "-3日"] {
+            assert!(parse(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_date_range_from_inputs() {
+        let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+        let now = jst.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap();
+
+        let range = DateRange::from_inputs("2025-01-10", "2025-01-10", now).unwrap();
+        assert_eq!(
+            range.from,
+            Some(Utc.with_ymd_and_hms(2025, 1, 9, 15, 0, 0).unwrap())
+        );
+        assert!(range.contains(Utc.with_ymd_and_hms(2025, 1, 10, 14, 59, 59).unwrap()));
+        assert!(!range.contains(Utc.with_ymd_and_hms(2025, 1, 10, 15, 0, 0).unwrap()));
+
+        // 片方だけの指定
+        let range = DateRange::from_inputs("-3d", "", now).unwrap();
+        assert!(range.from.is_some() && range.to.is_none());
+
+        assert!(DateRange::from_inputs("today", "yesterday", now).is_err());
+        assert!(
+            DateRange::from_inputs("soon", "", now)
+                .unwrap_err()
+                .starts_with("From:")
+        );
+    }
+
+    #[test]
+    fn test_date_input_out_of_range() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap();
+        for input in ["-99999999d", "-99999999w", "-9999999999h"] {
+            assert_eq!(
+                DateInput::parse(input, now),
+                Err(format!("invalid date '{}'", input)),
+                "{}",
+                input
+            );
+        }
+        assert!(DateRange::from_inputs("-99999999d", "", now).is_err());
     }
 }
@@ -0,0 +1,669 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::KathaError;
+use crate::domain::{Session, SessionEntry, SessionKey, SessionSource, SessionStats, pricing};
+use crate::search::content::searchable_texts;
+use crate::search::{ContentSearchResult, ContentSearchTarget, SearchQuery};
+
+/// インデックスファイルのフォーマットバージョン
+const INDEX_VERSION: u32 = 7;
+
+/// インデックスに登録する語の最大文字数（これより長い語は無視）
+const MAX_TERM_CHARS: usize = 128;
+
+/// インデックス済みセッションファイル
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedDocument {
+    source: SessionSource,
+    /// データディレクトリのラベル
+    root: Option<String>,
+    session_id: String,
+    path: PathBuf,
+    /// 最終更新時刻（UNIX エポックからのナノ秒）
+    modified: u64,
+    /// ファイルサイズ（バイト）
+    size: u64,
+    /// 集計メタデータ
+    stats: SessionStats,
+}
+
+impl IndexedDocument {
+    fn key(&self) -> SessionKey {
+        (self.source, self.root.clone(), self.session_id.clone())
+    }
+}
+
+/// インデックス更新の結果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexUpdateStats {
+    /// 新規に追加したセッション数
+    pub added: usize,
+    /// 再インデックスしたセッション数
+    pub updated: usize,
+    /// 削除したセッション数
+    pub removed: usize,
+    /// 料金表の変更によりコストを計算し直したセッション数
+    pub repriced: usize,
+    /// 変更のなかったセッション数
+    pub unchanged: usize,
+}
+
+impl IndexUpdateStats {
+    /// インデックスに変更があったか
+    pub fn is_changed(&self) -> bool {
+        self.added > 0 || self.updated > 0 || self.removed > 0 || self.repriced > 0
+    }
+}
+
+/// 語の部分一致検索用の接尾辞配列
+#[derive(Clone, Default)]
+struct TermSuffixes {
+    /// 登録されている語
+    terms: Vec<String>,
+    /// (語の番号, 開始バイト位置) を接尾辞の辞書順に並べたもの
+    suffixes: Vec<(u32, u32)>,
+}
+
+impl TermSuffixes {
+    fn build<'a>(terms: impl Iterator<Item = &'a String>) -> Self {
+        let terms: Vec<String> = terms.cloned().collect();
+        let mut suffixes: Vec<(u32, u32)> = terms
+            .iter()
+            .enumerate()
+            .flat_map(|(id, term)| {
+                term.char_indices()
+                    .map(move |(offset, _)| (id as u32, offset as u32))
+            })
+            .collect();
+        suffixes
+            .sort_unstable_by(|a, b| Self::suffix_of(&terms, *a).cmp(Self::suffix_of(&terms, *b)));
+        Self { terms, suffixes }
+    }
+
+    fn suffix_of(terms: &[String], (id, offset): (u32, u32)) -> &str {
+        &terms[id as usize][offset as usize..]
+    }
+
+    /// query を部分文字列として含む語（接尾辞の前方一致を二分探索）
+    fn containing(&self, query: &str) -> Vec<&str> {
+        let start = self
+            .suffixes
+            .partition_point(|&suffix| Self::suffix_of(&self.terms, suffix) < query);
+        let mut ids: Vec<u32> = self.suffixes[start..]
+            .iter()
+            .take_while(|&&suffix| Self::suffix_of(&self.terms, suffix).starts_with(query))
+            .map(|&(id, _)| id)
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.into_iter()
+            .map(|id| self.terms[id as usize].as_str())
+            .collect()
+    }
+}
+
+/// セッション本文の転置インデックス
+/// 語は小文字化して登録するため、検索は大文字小文字を区別しない
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SearchIndex {
+    version: u32,
+    /// 集計メタデータのコストを計算した料金表（[`PricingTable::fingerprint`](crate::domain::PricingTable::fingerprint)）
+    pricing: u64,
+    /// ドキュメント ID ごとのセッション情報
+    documents: Vec<IndexedDocument>,
+    /// 語 -> (ドキュメント ID, 出現回数)
+    postings: HashMap<String, Vec<(u32, u32)>>,
+    /// 語の接尾辞配列（保存せず、更新後または最初の検索時に作成）
+    #[serde(skip)]
+    suffixes: OnceLock<TermSuffixes>,
+}
+
+impl fmt::Debug for SearchIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchIndex")
+            .field("documents", &self.documents.len())
+            .field("terms", &self.postings.len())
+            .finish()
+    }
+}
+
+impl SearchIndex {
+    /// 空のインデックスを作成
+    pub fn new() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            ..Default::default()
+        }
+    }
+
+    /// インデックスファイルを読み込み（存在しない場合やバージョン違いは空のインデックス）
+    pub fn load(path: &Path) -> Result<Self, KathaError> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let bytes = fs::read(path)?;
+        let index: Self =
+            serde_json::from_slice(&bytes).map_err(|e| KathaError::JsonError(e.to_string()))?;
+        if index.version != INDEX_VERSION {
+            return Ok(Self::new());
+        }
+        Ok(index)
+    }
+
+    /// インデックスファイルを保存（一時ファイルに書いてから置き換える）
+    pub fn save(&self, path: &Path) -> Result<(), KathaError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let bytes = serde_json::to_vec(self).map_err(|e| KathaError::JsonError(e.to_string()))?;
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// インデックス済みセッション数
+    pub fn document_count(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// セッションごとの集計メタデータ
+    pub fn stats_map(&self) -> HashMap<SessionKey, SessionStats> {
+        self.documents
+            .iter()
+            .map(|doc| (doc.key(), doc.stats.clone()))
+            .collect()
+    }
+
+    /// 対象セッションに合わせてインデックスを更新
+    /// 更新時刻とサイズが変わっていないファイルは読み直さない
+    /// 料金表が変わっている場合は、変更のないファイルも読み直してコストを計算し直す
+    pub fn update(&mut self, targets: &[ContentSearchTarget]) -> IndexUpdateStats {
+        let mut stats = IndexUpdateStats::default();
+        let pricing = pricing::current().fingerprint();
+        let repricing = self.pricing != pricing;
+        let existing: HashMap<SessionKey, u32> = self
+            .documents
+            .iter()
+            .enumerate()
+            .map(|(id, doc)| (doc.key(), id as u32))
+            .collect();
+
+        let mut keep: HashSet<u32> = HashSet::new();
+        let mut pending: Vec<(IndexedDocument, HashMap<String, u32>)> = Vec::new();
+
+        for target in targets {
+            let key = target.key();
+            let Some((modified, size)) = file_stamp(&target.path) else {
+                continue;
+            };
+
+            let old_id = existing.get(&key).copied();
+            if let Some(id) = old_id {
+                let doc = &self.documents[id as usize];
+                if doc.path == target.path && doc.modified == modified && doc.size == size {
+                    keep.insert(id);
+                    if repricing {
+                        self.reprice(id, target);
+                        stats.repriced += 1;
+                    } else {
+                        stats.unchanged += 1;
+                    }
+                    continue;
+                }
+            }
+
+            let entries = match target.read_entries() {
+                Ok(entries) => entries,
+                Err(e) => {
+                    warn!("Search index skipped {}: {}", target.path.display(), e);
+                    continue;
+                }
+            };
+
+            let session = Session::from_entries(target.session_id.clone(), String::new(), entries);
+            let terms = term_counts(&session.entries);
+
+            if old_id.is_some() {
+                stats.updated += 1;
+            } else {
+                stats.added += 1;
+            }
+            pending.push((
+                IndexedDocument {
+                    source: target.source,
+                    root: target.root.clone(),
+                    session_id: target.session_id.clone(),
+                    path: target.path.clone(),
+                    modified,
+                    size,
+                    stats: session.stats(),
+                },
+                terms,
+            ));
+        }
+
+        stats.removed = self
+            .documents
+            .len()
+            .saturating_sub(keep.len() + stats.updated);
+        if keep.len() != self.documents.len() {
+            self.retain_documents(&keep);
+        }
+
+        for (doc, terms) in pending {
+            let id = self.documents.len() as u32;
+            self.documents.push(doc);
+            for (term, count) in terms {
+                self.postings.entry(term).or_default().push((id, count));
+            }
+        }
+
+        self.pricing = pricing;
+        if stats.is_changed() {
+            self.suffixes = OnceLock::new();
+        }
+        // 検索時に待たないよう接尾辞配列を作成しておく
+        self.term_suffixes();
+        stats
+    }
+
+    /// ドキュメントの集計メタデータを現在の料金表で計算し直す（読み込めない場合は元のまま）
+    fn reprice(&mut self, id: u32, target: &ContentSearchTarget) {
+        match target.read_entries() {
+            Ok(entries) => {
+                let session =
+                    Session::from_entries(target.session_id.clone(), String::new(), entries);
+                self.documents[id as usize].stats = session.stats();
+            }
+            Err(e) => warn!(
+                "Search index kept stale cost for {}: {}",
+                target.path.display(),
+                e
+            ),
+        }
+    }
+
+    fn term_suffixes(&self) -> &TermSuffixes {
+        self.suffixes
+            .get_or_init(|| TermSuffixes::build(self.postings.keys()))
+    }
+
+    /// 指定 ID のドキュメントのみを残し、ID を詰め直す
+    fn retain_documents(&mut self, keep: &HashSet<u32>) {
+        let mut remap: HashMap<u32, u32> = HashMap::new();
+        let mut documents = Vec::with_capacity(keep.len());
+        for (id, doc) in self.documents.drain(..).enumerate() {
+            if keep.contains(&(id as u32)) {
+                remap.insert(id as u32, documents.len() as u32);
+                documents.push(doc);
+            }
+        }
+        self.documents = documents;
+
+        self.postings.retain(|_, postings| {
+            postings.retain_mut(|(id, _)| match remap.get(id) {
+                Some(new_id) => {
+                    *id = *new_id;
+                    true
+                }
+                None => false,
+            });
+            !postings.is_empty()
+        });
+    }
+
+    /// クエリのテキスト句を検索してセッションごとのマッチ数を返す
+    /// 全語を含み、除外語（`-word`）を含まないセッションのみを対象とする
+    /// 語の区切りや数え方は [`ContentSearcher::search`](crate::search::ContentSearcher::search) と同じ
+    pub fn search(&self, query: &SearchQuery) -> ContentSearchResult {
+        let mut result = ContentSearchResult::new(query.content_terms());
+
+        // 除外語は語ごとに引き直し、いずれかを含むセッションを除外
+        let excluded: HashSet<u32> = result
+            .terms
+            .exclude
+            .iter()
+            .filter_map(|term| self.lookup(&tokenize(term)))
+            .flat_map(HashMap::into_keys)
+            .collect();
+        for id in &excluded {
+            if let Some(doc) = self.documents.get(*id as usize) {
+                result.excluded.insert(doc.key());
+            }
+        }
+
+        let include: Vec<String> = result
+            .terms
+            .include
+            .iter()
+            .flat_map(|term| tokenize(term))
+            .collect();
+        for (id, count) in self.lookup(&include).unwrap_or_default() {
+            if excluded.contains(&id) {
+                continue;
+            }
+            if let Some(doc) = self.documents.get(id as usize) {
+                result.hits.insert(doc.key(), count);
+            }
+        }
+        result
+    }
+
+    /// 各語を部分一致で引き、全語を含むドキュメントのマッチ数を返す（語がない場合は None）
+    /// 複数語の場合のマッチ数は語ごとのマッチ数の最小値
+    fn lookup(&self, query_terms: &[String]) -> Option<HashMap<u32, usize>> {
+        let suffixes = self.term_suffixes();
+        let mut combined: Option<HashMap<u32, usize>> = None;
+        for query_term in query_terms {
+            let mut counts: HashMap<u32, usize> = HashMap::new();
+            for term in suffixes.containing(query_term) {
+                let occurrences = term.matches(query_term.as_str()).count();
+                for (id, count) in &self.postings[term] {
+                    *counts.entry(*id).or_default() += occurrences * *count as usize;
+                }
+            }
+
+            combined = Some(match combined {
+                None => counts,
+                Some(prev) => prev
+                    .into_iter()
+                    .filter_map(|(id, prev_count)| {
+                        counts.get(&id).map(|count| (id, prev_count.min(*count)))
+                    })
+                    .collect(),
+            });
+        }
+        combined
+    }
+}
+
+/// エントリの検索対象テキストを語に分割し、語ごとの出現回数を集計
+pub fn term_counts(entries: &[SessionEntry]) -> HashMap<String, u32> {
+    let mut terms: HashMap<String, u32> = HashMap::new();
+    for text in entries.iter().flat_map(searchable_texts) {
+        for term in tokenize(&text) {
+            *terms.entry(term).or_default() += 1;
+        }
+    }
+    terms
+}
+
+/// テキストを小文字化して語に分割（英数字とアンダースコア以外で区切る）
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|term| !term.is_empty() && term.chars().count() <= MAX_TERM_CHARS)
+        .map(str::to_string)
+        .collect()
+}
+
+/// ファイルの更新時刻とサイズを取得
+fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0);
+    Some((modified, metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn user_line(text: &str) -> String {
+        format!(
+            r#"{{"type":"user","message":{{"role":"user","content":"{}"}}}}"#,
+            text
+        )
+    }
+
+    fn target(id: &str, path: PathBuf) -> ContentSearchTarget {
+        ContentSearchTarget {
+            source: SessionSource::Claude,
+            root: None,
+            session_id: id.to_string(),
+            path,
+        }
+    }
+
+    fn key(id: &str) -> SessionKey {
+        (SessionSource::Claude, None, id.to_string())
+    }
+
+    fn query(text: &str) -> SearchQuery {
+        SearchQuery {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("Fix the borrow_checker, please!"),
+            vec!["fix", "the", "borrow_checker", "please"]
+        );
+        assert_eq!(
+            tokenize("エラーを直す。テスト"),
+            vec!["エラーを直す", "テスト"]
+        );
+        assert!(tokenize("  --  ").is_empty());
+    }
+
+    #[test]
+    fn test_search_index_search() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.jsonl");
+        let b = dir.path().join("b.jsonl");
+        fs::write(
+            &a,
+            format!(
+                "{}\n{}\n",
+                user_line("tokio runtime panic"),
+                user_line("Tokio again")
+            ),
+        )
+        .unwrap();
+        fs::write(&b, user_line("async runtime")).unwrap();
+
+        let mut index = SearchIndex::new();
+        let stats = index.update(&[target("a", a), target("b", b)]);
+        assert_eq!(stats.added, 2);
+        assert_eq!(index.document_count(), 2);
+        assert_eq!(index.stats_map()[&key("a")].message_count, 2);
+
+        let result = index.search(&query("tokio"));
+        assert_eq!(result.hit_count(&key("a")), 2);
+        assert_eq!(result.hit_count(&key("b")), 0);
+
+        // 部分一致
+        let result = index.search(&query("time"));
+        assert_eq!(result.session_count(), 2);
+
+        // 複数語は全語を含むセッションのみ
+        let result = index.search(&query("runtime panic"));
+        assert_eq!(result.session_count(), 1);
+        assert_eq!(result.hit_count(&key("a")), 1);
+
+        assert_eq!(index.search(&query("")).session_count(), 0);
+
+        // 否定された語を含むセッションは除外
+        let result = index.search(&query("runtime -panic"));
+        assert_eq!(result.session_count(), 1);
+        assert_eq!(result.hit_count(&key("b")), 1);
+        assert!(!result.matches(&key("a")));
+        let result = index.search(&query("-tokio"));
+        assert!(!result.matches(&key("a")));
+        assert!(result.matches(&key("b")));
+    }
+
+    #[test]
+    fn test_term_suffixes() {
+        let terms = ["runtime", "time", "tokio", "エラー", "aaaa"].map(str::to_string);
+        let suffixes = TermSuffixes::build(terms.iter());
+        assert_eq!(suffixes.containing("time"), vec!["runtime", "time"]);
+        assert_eq!(suffixes.containing("to"), vec!["tokio"]);
+        assert_eq!(suffixes.containing("ラー"), vec!["エラー"]);
+        assert_eq!(suffixes.containing("aa"), vec!["aaaa"]);
+        assert!(suffixes.containing("timer").is_empty());
+    }
+
+    #[test]
+    fn test_search_index_matches_fallback() {
+        use crate::search::ContentSearcher;
+
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.jsonl");
+        let b = dir.path().join("b.jsonl");
+        fs::write(
+            &a,
+            format!(
+                "{}\n{}\n",
+                user_line("see foo.bar and tokio::main"),
+                user_line("aaaa runtime エラーを直す")
+            ),
+        )
+        .unwrap();
+        fs::write(&b, user_line("foo bar time")).unwrap();
+        let targets = [target("a", a), target("b", b)];
+
+        let mut index = SearchIndex::new();
+        index.update(&targets);
+        for text in [
+            "foo.bar",
+            "\"bar foo\"",
+            "time",
+            "aa",
+            "tokio::main",
+            "エラー",
+            "FOO -tokio",
+            "-runtime",
+            "--",
+        ] {
+            let indexed = index.search(&query(text));
+            let scanned = ContentSearcher::search(&targets, &query(text));
+            assert_eq!(indexed.hits, scanned.hits, "{}", text);
+            assert_eq!(indexed.excluded, scanned.excluded, "{}", text);
+        }
+        assert_eq!(index.search(&query("aa")).hit_count(&key("a")), 2);
+    }
+
+    #[test]
+    fn test_search_index_incremental_update() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.jsonl");
+        let b = dir.path().join("b.jsonl");
+        fs::write(&a, user_line("first")).unwrap();
+        fs::write(&b, user_line("second")).unwrap();
+
+        let mut index = SearchIndex::new();
+        index.update(&[target("a", a.clone()), target("b", b.clone())]);
+
+        // 変更なし
+        let stats = index.update(&[target("a", a.clone()), target("b", b.clone())]);
+        assert_eq!(stats.unchanged, 2);
+        assert!(!stats.is_changed());
+
+        // 料金表が変わるとコストを計算し直す
+        index.pricing = 0;
+        let stats = index.update(&[target("a", a.clone()), target("b", b.clone())]);
+        assert_eq!(stats.repriced, 2);
+        assert!(stats.is_changed());
+        assert!(
+            !index
+                .update(&[target("a", a.clone()), target("b", b.clone())])
+                .is_changed()
+        );
+
+        // a を更新、b を削除
+        fs::write(
+            &a,
+            format!("{}\n{}\n", user_line("first"), user_line("updated")),
+        )
+        .unwrap();
+        let stats = index.update(&[target("a", a.clone())]);
+        assert_eq!(stats.updated, 1);
+        assert_eq!(stats.removed, 1);
+        assert_eq!(index.document_count(), 1);
+
+        assert_eq!(index.search(&query("updated")).hit_count(&key("a")), 1);
+        assert_eq!(index.search(&query("second")).session_count(), 0);
+        assert_eq!(index.search(&query("first")).total_hits(), 1);
+    }
+
+    #[test]
+    fn test_search_index_same_id_in_two_roots() {
+        let dir = tempdir().unwrap();
+        let work = dir.path().join("work.jsonl");
+        let home = dir.path().join("home.jsonl");
+        fs::write(&work, user_line("deploy the service")).unwrap();
+        fs::write(
+            &home,
+            format!("{}\n{}\n", user_line("hello"), user_line("again")),
+        )
+        .unwrap();
+        let targets = |root: &str, path: &PathBuf| ContentSearchTarget {
+            root: Some(root.to_string()),
+            ..target("a", path.clone())
+        };
+        let targets = [targets("work", &work), targets("home", &home)];
+        let work_key = (
+            SessionSource::Claude,
+            Some("work".to_string()),
+            "a".to_string(),
+        );
+        let home_key = (
+            SessionSource::Claude,
+            Some("home".to_string()),
+            "a".to_string(),
+        );
+
+        let mut index = SearchIndex::new();
+        assert_eq!(index.update(&targets).added, 2);
+        // 同じ ID でも読み直さない
+        assert!(!index.update(&targets).is_changed());
+
+        let stats = index.stats_map();
+        assert_eq!(stats[&work_key].message_count, 1);
+        assert_eq!(stats[&home_key].message_count, 2);
+        let result = index.search(&query("deploy"));
+        assert_eq!(result.hit_count(&work_key), 1);
+        assert_eq!(result.hit_count(&home_key), 0);
+    }
+
+    #[test]
+    fn test_search_index_save_and_load() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.jsonl");
+        fs::write(&a, user_line("persisted content")).unwrap();
+
+        let mut index = SearchIndex::new();
+        index.update(&[target("a", a.clone())]);
+
+        let index_path = dir.path().join("cache").join("search-index.json");
+        index.save(&index_path).unwrap();
+
+        let mut loaded = SearchIndex::load(&index_path).unwrap();
+        assert_eq!(loaded.document_count(), 1);
+        assert_eq!(loaded.search(&query("persisted")).session_count(), 1);
+        assert_eq!(loaded.update(&[target("a", a)]).unchanged, 1);
+
+        let missing = SearchIndex::load(&dir.path().join("missing.json")).unwrap();
+        assert_eq!(missing.document_count(), 0);
+    }
+}
@@ -0,0 +1,289 @@
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+
+use crate::search::{MatchMode, ParsedQuery, QueryClause};
+
+/// 一致した文字のスコア
+const SCORE_MATCH: i64 = 16;
+/// ギャップ開始のペナルティ
+const PENALTY_GAP_START: i64 = 3;
+/// ギャップ継続のペナルティ
+const PENALTY_GAP_EXTENSION: i64 = 1;
+/// 単語境界（先頭・区切り文字の直後・camelCase）のボーナス
+const BONUS_BOUNDARY: i64 = 8;
+/// 連続一致のボーナス
+const BONUS_CONSECUTIVE: i64 = PENALTY_GAP_START + PENALTY_GAP_EXTENSION;
+/// パターン先頭文字のボーナス倍率
+const BONUS_FIRST_CHAR_MULTIPLIER: i64 = 2;
+
+/// テキスト句のマッチャーを作成済みの構造化クエリ
+#[derive(Debug, Clone)]
+pub struct CompiledQuery {
+    parsed: ParsedQuery,
+    /// 句ごとのマッチャー（テキスト句以外は None）
+    matchers: Vec<Option<TextMatcher>>,
+}
+
+impl CompiledQuery {
+    /// 構文木と句ごとのマッチャーから作成
+    pub fn new(parsed: ParsedQuery, matchers: Vec<Option<TextMatcher>>) -> Self {
+        Self { parsed, matchers }
+    }
+
+    /// 句とマッチャーの組を列挙
+    pub fn clauses(&self) -> impl Iterator<Item = (&QueryClause, Option<&TextMatcher>)> {
+        self.parsed
+            .clauses
+            .iter()
+            .zip(self.matchers.iter().map(Option::as_ref))
+    }
+}
+
+/// マッチモードに応じたテキストマッチャー
+#[derive(Debug, Clone)]
+pub enum TextMatcher {
+    /// 部分一致（スコアは常に 0）
+    Substring {
+        pattern: String,
+        case_sensitive: bool,
+    },
+    /// 正規表現（スコアはマッチ数）
+    Regex(Regex),
+    /// fzf 風のあいまい一致（サブシーケンス）
+    Fuzzy {
+        pattern: Vec<char>,
+        case_sensitive: bool,
+    },
+}
+
+impl TextMatcher {
+    /// パターンからマッチャーを作成（正規表現の構文エラーは Err）
+    pub fn new(pattern: &str, mode: MatchMode, case_sensitive: bool) -> Result<Self, regex::Error> {
+        Ok(match mode {
+            MatchMode::Substring => TextMatcher::Substring {
+                pattern: fold_case(pattern, case_sensitive),
+                case_sensitive,
+            },
+            MatchMode::Regex => TextMatcher::Regex(
+                RegexBuilder::new(pattern)
+                    .case_insensitive(!case_sensitive)
+                    .build()?,
+            ),
+            MatchMode::Fuzzy => TextMatcher::Fuzzy {
+                pattern: fold_case(pattern, case_sensitive).chars().collect(),
+                case_sensitive,
+            },
+        })
+    }
+
+    /// 対象テキストのスコアを計算（マッチしない場合は None、高いほど良い）
+    pub fn score(&self, target: &str) -> Option<i64> {
+        match self {
+            TextMatcher::Substring {
+                pattern,
+                case_sensitive,
+            } => fold_case(target, *case_sensitive)
+                .contains(pattern.as_str())
+                .then_some(0),
+            TextMatcher::Regex(regex) => {
+                let count = regex.find_iter(target).count();
+                (count > 0).then_some(count as i64)
+            }
+            TextMatcher::Fuzzy {
+                pattern,
+                case_sensitive,
+            } => fuzzy_score(pattern, target, *case_sensitive),
+        }
+    }
+}
+
+fn fold_case(text: &str, case_sensitive: bool) -> String {
+    if case_sensitive {
+        text.to_string()
+    } else {
+        text.to_lowercase()
+    }
+}
+
+/// fzf (v1) 風のあいまい一致スコア
+/// 前方走査で最初の一致を見つけ、後方走査で一致範囲を最短に絞ってから採点する
+pub fn fuzzy_score(pattern: &[char], target: &str, case_sensitive: bool) -> Option<i64> {
+    if pattern.is_empty() {
+        return Some(0);
+    }
+
+    let original: Vec<char> = target.chars().collect();
+    let folded: Vec<char> = if case_sensitive {
+        original.clone()
+    } else {
+        // 1 文字ずつ小文字化して位置を保つ
+        original.iter().map(|c| fold_char(*c)).collect()
+    };
+
+    // 前方走査: パターン全体が一致する最初の終端
+    let mut pattern_index = 0;
+    let mut end = None;
+    for (i, c) in folded.iter().enumerate() {
+        if *c == pattern[pattern_index] {
+            pattern_index += 1;
+            if pattern_index == pattern.len() {
+                end = Some(i);
+                break;
+            }
+        }
+    }
+    let end = end?;
+
+    // 後方走査: 一致範囲の開始位置を最も後ろに寄せる
+    let mut pattern_index = pattern.len();
+    let mut start = end;
+    for i in (0..=end).rev() {
+        if folded[i] == pattern[pattern_index - 1] {
+            pattern_index -= 1;
+            if pattern_index == 0 {
+                start = i;
+                break;
+            }
+        }
+    }
+
+    // 採点
+    let mut score = 0;
+    let mut pattern_index = 0;
+    let mut in_gap = false;
+    let mut consecutive = false;
+    // 連続一致の塊は先頭文字のボーナスを引き継ぐ
+    let mut chunk_bonus = 0;
+    for (i, c) in folded.iter().enumerate().take(end + 1).skip(start) {
+        if pattern_index < pattern.len() && *c == pattern[pattern_index] {
+            let mut bonus = boundary_bonus(&original, i);
+            if consecutive {
+                bonus = bonus.max(BONUS_CONSECUTIVE).max(chunk_bonus);
+            } else {
+                chunk_bonus = bonus;
+            }
+            if pattern_index == 0 {
+                bonus *= BONUS_FIRST_CHAR_MULTIPLIER;
+            }
+            score += SCORE_MATCH + bonus;
+            pattern_index += 1;
+            in_gap = false;
+            consecutive = true;
+        } else {
+            score -= if in_gap {
+                PENALTY_GAP_EXTENSION
+            } else {
+                PENALTY_GAP_START
+            };
+            in_gap = true;
+            consecutive = false;
+        }
+    }
+
+    Some(score)
+}
+
+/// 大文字小文字を区別せずにパターンの出現位置（バイト範囲）を列挙（重なりなし）
+pub fn find_ranges(text: &str, pattern: &str) -> Vec<Range<usize>> {
+    let needle: Vec<char> = pattern.chars().map(fold_char).collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let chars: Vec<(usize, char)> = text
+        .char_indices()
+        .map(|(i, c)| (i, fold_char(c)))
+        .collect();
+
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= chars.len() {
+        let matched = chars[i..i + needle.len()]
+            .iter()
+            .zip(&needle)
+            .all(|((_, c), n)| c == n);
+        if matched {
+            let end = chars
+                .get(i + needle.len())
+                .map(|(byte, _)| *byte)
+                .unwrap_or(text.len());
+            ranges.push(chars[i].0..end);
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    ranges
+}
+
+fn fold_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// 単語境界のボーナス
+fn boundary_bonus(chars: &[char], index: usize) -> i64 {
+    let Some(prev) = index.checked_sub(1).map(|i| chars[i]) else {
+        return BONUS_BOUNDARY;
+    };
+    let current = chars[index];
+    if !prev.is_alphanumeric() && current.is_alphanumeric() {
+        BONUS_BOUNDARY
+    } else if prev.is_lowercase() && current.is_uppercase() {
+        BONUS_BOUNDARY / 2
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fuzzy(pattern: &str, target: &str) -> Option<i64> {
+        TextMatcher::new(pattern, MatchMode::Fuzzy, false)
+            .unwrap()
+            .score(target)
+    }
+
+    #[test]
+    fn test_substring_matcher() {
+        let matcher = TextMatcher::new("Bug", MatchMode::Substring, false).unwrap();
+        assert_eq!(matcher.score("fix BUG in login"), Some(0));
+        assert_eq!(matcher.score("feature"), None);
+    }
+
+    #[test]
+    fn test_regex_matcher() {
+        let matcher = TextMatcher::new(r"fix(es)?\s+\w+", MatchMode::Regex, false).unwrap();
+        assert_eq!(matcher.score("Fix login, fixes auth"), Some(2));
+        assert_eq!(matcher.score("no match"), None);
+
+        assert!(TextMatcher::new("(unclosed", MatchMode::Regex, false).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_subsequence() {
+        assert!(fuzzy("fbl", "fix bug in login").is_some());
+        assert!(fuzzy("FBL", "fix bug in login").is_some());
+        assert!(fuzzy("lbf", "fix bug in login").is_none());
+        assert_eq!(fuzzy("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_find_ranges() {
+        assert_eq!(find_ranges("Fix bug, fix BUG", "bug"), vec![4..7, 13..16]);
+        assert_eq!(find_ranges("ああバグあ", "バグ"), vec![6..12]);
+        assert_eq!(find_ranges("aaaa", "aa"), vec![0..2, 2..4]);
+        assert!(find_ranges("text", "").is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_ranking() {
+        // 連続一致 > 分散した一致
+        assert!(fuzzy("login", "login page") > fuzzy("login", "l-o-g-i-n"));
+        // 単語境界の一致 > 単語途中の一致
+        assert!(fuzzy("st", "search term") > fuzzy("st", "fastest"));
+        // 一致範囲が短いほど良い
+        assert!(fuzzy("ab", "ab") > fuzzy("ab", "a___b"));
+    }
+}
@@ -1,7 +1,19 @@
+pub mod content;
 pub mod engine;
 pub mod filter;
+pub mod index;
+pub mod matcher;
+pub mod parser;
 pub mod query;
+pub mod sort;
 
-pub use engine::SearchEngine;
-pub use filter::{DateRange, FilterCriteria, FilterField};
-pub use query::SearchQuery;
+pub use content::{ContentSearchResult, ContentSearchTarget, ContentSearcher};
+pub use engine::{QueryContext, SearchEngine};
+pub use filter::{DateInput, DatePreset, DateRange, FilterCriteria, FilterField, Threshold};
+pub use index::{IndexUpdateStats, SearchIndex};
+pub use matcher::{CompiledQuery, TextMatcher, find_ranges};
+pub use parser::{
+    Comparison, ParsedQuery, QueryClause, QueryField, QueryParseError, QueryPredicate, parse_query,
+};
+pub use query::{ContentTerms, MatchMode, SearchQuery, SearchScope};
+pub use sort::{SortMode, sort_project_groups, sort_sessions};
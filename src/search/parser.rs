@@ -0,0 +1,482 @@
+use std::fmt;
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+use crate::search::filter::start_of_day;
+
+/// 検索クエリの構文木（各句の AND）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedQuery {
+    /// 句の一覧
+    pub clauses: Vec<QueryClause>,
+}
+
+/// 検索クエリの句
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryClause {
+    /// `-` で否定されているか
+    pub negated: bool,
+    /// 条件
+    pub predicate: QueryPredicate,
+}
+
+/// 句の条件
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryPredicate {
+    /// 単語または "フレーズ"（部分一致）
+    Text(String),
+    /// `field:value` 形式の条件（部分一致）
+    Field(QueryField, String),
+    /// `after:YYYY-MM-DD`（その日を含む）
+    After(DateTime<Utc>),
+    /// `before:YYYY-MM-DD`（その日を含まない）
+    Before(DateTime<Utc>),
+    /// `cost>0.5` 形式のコスト比較（USD）
+    Cost(Comparison, f64),
+}
+
+/// `field:value` で指定できるフィールド
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryField {
+    Project,
+    Model,
+    Tool,
+    Branch,
+    Source,
+}
+
+impl QueryField {
+    /// フィールド名から取得
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "project" => Some(QueryField::Project),
+            "model" => Some(QueryField::Model),
+            "tool" => Some(QueryField::Tool),
+            "branch" => Some(QueryField::Branch),
+            "source" => Some(QueryField::Source),
+            _ => None,
+        }
+    }
+}
+
+/// `name:` で条件として扱うフィールド名か
+fn is_field_name(name: &str) -> bool {
+    matches!(name, "after" | "before" | "cost") || QueryField::from_name(name).is_some()
+}
+
+/// 数値比較演算子
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+}
+
+impl Comparison {
+    /// 比較を評価
+    pub fn compare(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Comparison::Gt => lhs > rhs,
+            Comparison::Ge => lhs >= rhs,
+            Comparison::Lt => lhs < rhs,
+            Comparison::Le => lhs <= rhs,
+            Comparison::Eq => (lhs - rhs).abs() < f64::EPSILON,
+        }
+    }
+
+    /// 演算子の表記
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Eq => "=",
+        }
+    }
+}
+
+/// クエリの構文エラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryParseError {
+    /// エラー内容
+    pub message: String,
+    /// エラー位置（文字単位）
+    pub position: usize,
+}
+
+impl fmt::Display for QueryParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at {})", self.message, self.position + 1)
+    }
+}
+
+impl ParsedQuery {
+    /// 句がないか
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// テキスト句の語（negated が true なら否定された語、本文検索用）
+    pub fn text_terms(&self, negated: bool) -> Vec<String> {
+        self.clauses
+            .iter()
+            .filter(|clause| clause.negated == negated)
+            .filter_map(|clause| match &clause.predicate {
+                QueryPredicate::Text(text) => Some(text.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// クエリ文字列を構文解析
+pub fn parse_query(input: &str) -> Result<ParsedQuery, QueryParseError> {
+    Parser {
+        chars: input.chars().collect(),
+        pos: 0,
+    }
+    .parse()
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn parse(mut self) -> Result<ParsedQuery, QueryParseError> {
+        let mut query = ParsedQuery::default();
+
+        loop {
+            while self.peek().is_some_and(char::is_whitespace) {
+                self.pos += 1;
+            }
+            if self.peek().is_none() {
+                break;
+            }
+            query.clauses.push(self.parse_clause()?);
+        }
+
+        Ok(query)
+    }
+
+    fn parse_clause(&mut self) -> Result<QueryClause, QueryParseError> {
+        let negated = self.peek() == Some('-')
+            && self
+                .chars
+                .get(self.pos + 1)
+                .is_some_and(|c| !c.is_whitespace());
+        if negated {
+            self.pos += 1;
+        }
+
+        if self.peek() == Some('"') {
+            let start = self.pos;
+            let phrase = self.read_quoted()?;
+            if phrase.is_empty() {
+                return Err(self.error("Empty phrase", start));
+            }
+            return Ok(QueryClause {
+                negated,
+                predicate: QueryPredicate::Text(phrase),
+            });
+        }
+
+        let start = self.pos;
+        let key: String = self.read_while(|c| c.is_alphanumeric() || c == '_');
+        let key_lower = key.to_lowercase();
+
+        match self.peek() {
+            // 既知のフィールド以外（`TODO:`、`http://…`、`std::mem` など）はテキストとして扱う
+            Some(':') if is_field_name(&key_lower) => {
+                self.pos += 1;
+                let value_start = self.pos;
+                let value = self.read_value()?;
+                if value.is_empty() {
+                    return Err(self.error(format!("Missing value for '{}:'", key), value_start));
+                }
+                let predicate = self.field_predicate(&key_lower, value, start, value_start)?;
+                Ok(QueryClause { negated, predicate })
+            }
+            Some('>' | '<' | '=') if key_lower == "cost" => {
+                let comparison = self.read_comparison();
+                let value_start = self.pos;
+                let value = self.read_value()?;
+                let amount = value
+                    .parse::<f64>()
+                    .map_err(|_| self.error(format!("Invalid number '{}'", value), value_start))?;
+                Ok(QueryClause {
+                    negated,
+                    predicate: QueryPredicate::Cost(comparison, amount),
+                })
+            }
+            _ => {
+                let rest = self.read_while(|c| !c.is_whitespace());
+                Ok(QueryClause {
+                    negated,
+                    predicate: QueryPredicate::Text(format!("{}{}", key, rest)),
+                })
+            }
+        }
+    }
+
+    fn field_predicate(
+        &self,
+        key: &str,
+        value: String,
+        key_start: usize,
+        value_start: usize,
+    ) -> Result<QueryPredicate, QueryParseError> {
+        match key {
+            "after" | "before" => {
+                let date = NaiveDate::parse_from_str(&value, "%Y-%m-%d").map_err(|_| {
+                    self.error(
+                        format!("Invalid date '{}' (expected YYYY-MM-DD)", value),
+                        value_start,
+                    )
+                })?;
+                // フィルタパネルと同じくローカルタイムゾーンの 0 時を境界にする
+                let datetime = start_of_day(&Local, date);
+                Ok(if key == "after" {
+                    QueryPredicate::After(datetime)
+                } else {
+                    QueryPredicate::Before(datetime)
+                })
+            }
+            "cost" => {
+                let amount = value
+                    .parse::<f64>()
+                    .map_err(|_| self.error(format!("Invalid number '{}'", value), value_start))?;
+                Ok(QueryPredicate::Cost(Comparison::Eq, amount))
+            }
+            _ => QueryField::from_name(key)
+                .map(|field| QueryPredicate::Field(field, value))
+                .ok_or_else(|| self.error(format!("Unknown field '{}'", key), key_start)),
+        }
+    }
+
+    fn read_comparison(&mut self) -> Comparison {
+        let first = self.chars[self.pos];
+        self.pos += 1;
+        let has_eq = self.peek() == Some('=');
+        if has_eq && first != '=' {
+            self.pos += 1;
+        }
+        match (first, has_eq) {
+            ('>', true) => Comparison::Ge,
+            ('>', false) => Comparison::Gt,
+            ('<', true) => Comparison::Le,
+            ('<', false) => Comparison::Lt,
+            _ => Comparison::Eq,
+        }
+    }
+
+    /// 値を読み込み（"..." で空白を含められる）
+    fn read_value(&mut self) -> Result<String, QueryParseError> {
+        if self.peek() == Some('"') {
+            self.read_quoted()
+        } else {
+            Ok(self.read_while(|c| !c.is_whitespace()))
+        }
+    }
+
+    fn read_quoted(&mut self) -> Result<String, QueryParseError> {
+        let start = self.pos;
+        self.pos += 1; // 開き引用符
+        let text = self.read_while(|c| c != '"');
+        if self.peek() != Some('"') {
+            return Err(self.error("Unterminated quote", start));
+        }
+        self.pos += 1; // 閉じ引用符
+        Ok(text)
+    }
+
+    fn read_while(&mut self, pred: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(&pred) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn error(&self, message: impl Into<String>, position: usize) -> QueryParseError {
+        QueryParseError {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::DateRange;
+    use chrono::TimeZone;
+
+    fn clause(negated: bool, predicate: QueryPredicate) -> QueryClause {
+        QueryClause { negated, predicate }
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert!(parse_query("").unwrap().is_empty());
+        assert!(parse_query("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_text_and_phrase() {
+        let query = parse_query(r#"fix "borrow checker" -wip"#).unwrap();
+        assert_eq!(
+            query.clauses,
+            vec![
+                clause(false, QueryPredicate::Text("fix".to_string())),
+                clause(false, QueryPredicate::Text("borrow checker".to_string())),
+                clause(true, QueryPredicate::Text("wip".to_string())),
+            ]
+        );
+        assert_eq!(query.text_terms(false), vec!["fix", "borrow checker"]);
+        assert_eq!(query.text_terms(true), vec!["wip"]);
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let query =
+            parse_query(r#"project:katha model:opus tool:Bash branch:"feat x" -source:codex"#)
+                .unwrap();
+        assert_eq!(
+            query.clauses,
+            vec![
+                clause(
+                    false,
+                    QueryPredicate::Field(QueryField::Project, "katha".into())
+                ),
+                clause(
+                    false,
+                    QueryPredicate::Field(QueryField::Model, "opus".into())
+                ),
+                clause(
+                    false,
+                    QueryPredicate::Field(QueryField::Tool, "Bash".into())
+                ),
+                clause(
+                    false,
+                    QueryPredicate::Field(QueryField::Branch, "feat x".into())
+                ),
+                clause(
+                    true,
+                    QueryPredicate::Field(QueryField::Source, "codex".into())
+                ),
+            ]
+        );
+        assert!(query.text_terms(false).is_empty());
+    }
+
+    #[test]
+    fn test_parse_date_and_cost() {
+        let query = parse_query("after:2025-01-01 before:2025-02-01 cost>0.5 cost<=2").unwrap();
+        assert_eq!(
+            query.clauses,
+            vec![
+                clause(
+                    false,
+                    QueryPredicate::After(
+                        Local
+                            .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                            .unwrap()
+                            .to_utc()
+                    )
+                ),
+                clause(
+                    false,
+                    QueryPredicate::Before(
+                        Local
+                            .with_ymd_and_hms(2025, 2, 1, 0, 0, 0)
+                            .unwrap()
+                            .to_utc()
+                    )
+                ),
+                clause(false, QueryPredicate::Cost(Comparison::Gt, 0.5)),
+                clause(false, QueryPredicate::Cost(Comparison::Le, 2.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_date_matches_filter_panel() {
+        // 検索バーの after: とフィルタパネルの From は同じ境界になる
+        let query = parse_query("after:2025-01-01").unwrap();
+        let range = DateRange::from_inputs("2025-01-01", "", Local::now()).unwrap();
+        assert_eq!(
+            query.clauses,
+            vec![clause(false, QueryPredicate::After(range.from.unwrap()))]
+        );
+    }
+
+    #[test]
+    fn test_parse_dash_and_symbols_are_text() {
+        let query = parse_query("- a<b foo-bar").unwrap();
+        assert_eq!(
+            query.clauses,
+            vec![
+                clause(false, QueryPredicate::Text("-".to_string())),
+                clause(false, QueryPredicate::Text("a<b".to_string())),
+                clause(false, QueryPredicate::Text("foo-bar".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_field_is_text() {
+        let query = parse_query("TODO: http://example.com std::mem -error:E0502 owner:me").unwrap();
+        assert_eq!(
+            query.clauses,
+            vec![
+                clause(false, QueryPredicate::Text("TODO:".to_string())),
+                clause(
+                    false,
+                    QueryPredicate::Text("http://example.com".to_string())
+                ),
+                clause(false, QueryPredicate::Text("std::mem".to_string())),
+                clause(true, QueryPredicate::Text("error:E0502".to_string())),
+                clause(false, QueryPredicate::Text("owner:me".to_string())),
+            ]
+        );
+        assert_eq!(
+            query.text_terms(false),
+            vec!["TODO:", "http://example.com", "std::mem", "owner:me"]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_query(r#"fix "unterminated"#).unwrap_err();
+        assert_eq!(err.position, 4);
+        assert!(err.message.contains("Unterminated"));
+
+        let err = parse_query("after:yesterday").unwrap_err();
+        assert_eq!(err.position, 6);
+
+        let err = parse_query("cost>abc").unwrap_err();
+        assert_eq!(err.position, 5);
+
+        assert!(parse_query("project:").is_err());
+        assert!(parse_query(r#""""#).is_err());
+    }
+
+    #[test]
+    fn test_comparison() {
+        assert!(Comparison::Gt.compare(1.0, 0.5));
+        assert!(!Comparison::Gt.compare(0.5, 0.5));
+        assert!(Comparison::Ge.compare(0.5, 0.5));
+        assert!(Comparison::Lt.compare(0.1, 0.5));
+        assert!(Comparison::Le.compare(0.5, 0.5));
+        assert!(Comparison::Eq.compare(0.5, 0.5));
+    }
+}
@@ -1,3 +1,82 @@
+use crate::search::matcher::{CompiledQuery, TextMatcher};
+use crate::search::parser::{ParsedQuery, QueryParseError, QueryPredicate, parse_query};
+
+/// 検索対象の範囲
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchScope {
+    /// プロジェクト名・履歴の表示テキスト
+    #[default]
+    Metadata,
+    /// セッションファイルの会話本文
+    Content,
+}
+
+impl SearchScope {
+    /// 範囲を切り替え
+    pub fn toggle(self) -> Self {
+        match self {
+            SearchScope::Metadata => SearchScope::Content,
+            SearchScope::Content => SearchScope::Metadata,
+        }
+    }
+
+    /// 表示名を取得
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchScope::Metadata => "title",
+            SearchScope::Content => "content",
+        }
+    }
+}
+
+/// テキスト句のマッチ方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchMode {
+    /// 部分一致（時系列順のまま）
+    #[default]
+    Substring,
+    /// 正規表現（マッチ数順）
+    Regex,
+    /// fzf 風のあいまい一致（スコア順）
+    Fuzzy,
+}
+
+impl MatchMode {
+    /// 次のモードに切り替え
+    pub fn next(self) -> Self {
+        match self {
+            MatchMode::Substring => MatchMode::Regex,
+            MatchMode::Regex => MatchMode::Fuzzy,
+            MatchMode::Fuzzy => MatchMode::Substring,
+        }
+    }
+
+    /// 表示名を取得
+    pub fn label(self) -> &'static str {
+        match self {
+            MatchMode::Substring => "substring",
+            MatchMode::Regex => "regex",
+            MatchMode::Fuzzy => "fuzzy",
+        }
+    }
+}
+
+/// 本文検索の語（クエリのテキスト句）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentTerms {
+    /// 本文にすべて含まれる必要がある語
+    pub include: Vec<String>,
+    /// 本文に含まれてはならない語（`-word`）
+    pub exclude: Vec<String>,
+}
+
+impl ContentTerms {
+    /// 語がないか
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+}
+
 /// 検索クエリ
 #[derive(Debug, Clone, Default)]
 pub struct SearchQuery {
@@ -5,6 +84,10 @@ pub struct SearchQuery {
     pub text: String,
     /// 大文字小文字を区別するか
     pub case_sensitive: bool,
+    /// 検索範囲
+    pub scope: SearchScope,
+    /// テキスト句のマッチ方式
+    pub match_mode: MatchMode,
 }
 
 impl SearchQuery {
@@ -13,6 +96,61 @@ impl SearchQuery {
         self.text.is_empty()
     }
 
+    /// クエリ文字列を構文解析
+    pub fn parse(&self) -> Result<ParsedQuery, QueryParseError> {
+        parse_query(&self.text)
+    }
+
+    /// 実際に使うマッチ方式（本文検索は部分一致のみ）
+    pub fn effective_match_mode(&self) -> MatchMode {
+        match self.scope {
+            SearchScope::Metadata => self.match_mode,
+            SearchScope::Content => MatchMode::Substring,
+        }
+    }
+
+    /// 構文解析してテキスト句のマッチャーを作成（正規表現の誤りも構文エラーとして返す）
+    pub fn compile(&self) -> Result<CompiledQuery, QueryParseError> {
+        let parsed = self.parse()?;
+        let mut matchers = Vec::with_capacity(parsed.clauses.len());
+        for clause in &parsed.clauses {
+            let QueryPredicate::Text(text) = &clause.predicate else {
+                matchers.push(None);
+                continue;
+            };
+            let matcher = TextMatcher::new(text, self.effective_match_mode(), self.case_sensitive)
+                .map_err(|e| self.regex_error(text, &e))?;
+            matchers.push(Some(matcher));
+        }
+        Ok(CompiledQuery::new(parsed, matchers))
+    }
+
+    /// 正規表現のエラーを構文エラーに変換
+    fn regex_error(&self, pattern: &str, error: &regex::Error) -> QueryParseError {
+        // regex のエラーは複数行のため最終行（原因）のみ使う
+        let detail = error.to_string();
+        let reason = detail.lines().last().unwrap_or_default();
+        let position = self
+            .text
+            .find(pattern)
+            .map(|byte| self.text[..byte].chars().count())
+            .unwrap_or(0);
+        QueryParseError {
+            message: format!("Invalid regex: {}", reason.trim_start_matches("error: ")),
+            position,
+        }
+    }
+
+    /// 本文検索に使う語（フィールド指定を除いたテキスト句、構文エラーの場合は空）
+    pub fn content_terms(&self) -> ContentTerms {
+        self.parse()
+            .map(|query| ContentTerms {
+                include: query.text_terms(false),
+                exclude: query.text_terms(true),
+            })
+            .unwrap_or_default()
+    }
+
     /// 対象テキストがクエリにマッチするか
     /// case_sensitive が false の場合は大文字小文字を無視
     pub fn matches(&self, target: &str) -> bool {
@@ -26,6 +164,22 @@ impl SearchQuery {
             target.to_lowercase().contains(&self.text.to_lowercase())
         }
     }
+
+    /// 対象テキスト中のマッチ数を数える（重複なし）
+    pub fn count_matches(&self, target: &str) -> usize {
+        if self.text.is_empty() {
+            return 0;
+        }
+
+        if self.case_sensitive {
+            target.matches(self.text.as_str()).count()
+        } else {
+            target
+                .to_lowercase()
+                .matches(self.text.to_lowercase().as_str())
+                .count()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -40,6 +194,7 @@ mod tests {
         let query = SearchQuery {
             text: "test".to_string(),
             case_sensitive: false,
+            ..Default::default()
         };
         assert!(!query.is_empty());
     }
@@ -56,6 +211,7 @@ mod tests {
         let query = SearchQuery {
             text: "test".to_string(),
             case_sensitive: false,
+            ..Default::default()
         };
 
         assert!(query.matches("test"));
@@ -71,6 +227,7 @@ mod tests {
         let query = SearchQuery {
             text: "Test".to_string(),
             case_sensitive: true,
+            ..Default::default()
         };
 
         assert!(query.matches("Test"));
@@ -84,10 +241,83 @@ mod tests {
         let query = SearchQuery {
             text: "hello".to_string(),
             case_sensitive: false,
+            ..Default::default()
         };
 
         assert!(query.matches("hello world"));
         assert!(query.matches("say hello"));
         assert!(query.matches("helloooo"));
     }
+
+    #[test]
+    fn test_count_matches() {
+        let query = SearchQuery {
+            text: "foo".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(query.count_matches("foo bar FOO baz foo"), 3);
+        assert_eq!(query.count_matches("nothing here"), 0);
+        assert_eq!(SearchQuery::default().count_matches("foo"), 0);
+    }
+
+    #[test]
+    fn test_content_terms() {
+        let query = SearchQuery {
+            text: r#"project:katha "async fn" -wip tokio"#.to_string(),
+            scope: SearchScope::Content,
+            ..Default::default()
+        };
+
+        let terms = query.content_terms();
+        assert_eq!(terms.include, vec!["async fn", "tokio"]);
+        assert_eq!(terms.exclude, vec!["wip"]);
+
+        let invalid = SearchQuery {
+            text: "after:yesterday fix".to_string(),
+            ..Default::default()
+        };
+        assert!(invalid.parse().is_err());
+        assert!(invalid.content_terms().is_empty());
+    }
+
+    #[test]
+    fn test_compile_regex_error() {
+        let query = SearchQuery {
+            text: "project:a (unclosed".to_string(),
+            match_mode: MatchMode::Regex,
+            ..Default::default()
+        };
+        let err = query.compile().unwrap_err();
+        assert_eq!(err.position, 10);
+        assert!(err.message.starts_with("Invalid regex"));
+
+        let query = SearchQuery {
+            match_mode: MatchMode::Substring,
+            ..query
+        };
+        assert!(query.compile().is_ok());
+
+        // 本文検索は部分一致のみ
+        let query = SearchQuery {
+            match_mode: MatchMode::Regex,
+            scope: SearchScope::Content,
+            ..query
+        };
+        assert_eq!(query.effective_match_mode(), MatchMode::Substring);
+        assert!(query.compile().is_ok());
+    }
+
+    #[test]
+    fn test_match_mode_next() {
+        assert_eq!(MatchMode::Substring.next(), MatchMode::Regex);
+        assert_eq!(MatchMode::Regex.next(), MatchMode::Fuzzy);
+        assert_eq!(MatchMode::Fuzzy.next(), MatchMode::Substring);
+    }
+
+    #[test]
+    fn test_search_scope_toggle() {
+        assert_eq!(SearchScope::Metadata.toggle(), SearchScope::Content);
+        assert_eq!(SearchScope::Content.toggle(), SearchScope::Metadata);
+    }
 }
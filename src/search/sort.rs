@@ -0,0 +1,259 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{SessionKey, SessionStats};
+use crate::tea::{ProjectGroup, SessionListItem};
+
+/// セッション一覧の並び順
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortMode {
+    /// 新しい順
+    #[default]
+    Newest,
+    /// 古い順
+    Oldest,
+    /// メッセージ数の多い順
+    Messages,
+    /// コストの高い順
+    Cost,
+    /// トークン数の多い順
+    Tokens,
+    /// 所要時間の長い順
+    Duration,
+    /// プロジェクト名順（プロジェクト内は新しい順）
+    Project,
+}
+
+impl SortMode {
+    /// 切り替え順
+    const ALL: [SortMode; 7] = [
+        SortMode::Newest,
+        SortMode::Oldest,
+        SortMode::Messages,
+        SortMode::Cost,
+        SortMode::Tokens,
+        SortMode::Duration,
+        SortMode::Project,
+    ];
+
+    /// 次の並び順
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|mode| *mode == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// 表示名
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::Newest => "Newest",
+            SortMode::Oldest => "Oldest",
+            SortMode::Messages => "Most messages",
+            SortMode::Cost => "Highest cost",
+            SortMode::Tokens => "Most tokens",
+            SortMode::Duration => "Longest duration",
+            SortMode::Project => "Project name",
+        }
+    }
+
+    /// 集計メタデータを使う並び順か
+    pub fn uses_stats(self) -> bool {
+        matches!(
+            self,
+            SortMode::Messages | SortMode::Cost | SortMode::Tokens | SortMode::Duration
+        )
+    }
+
+    /// 集計メタデータから並べ替えのキーを取得（大きいほど先頭）
+    fn stat_key(self, stats: &SessionStats) -> Option<f64> {
+        match self {
+            SortMode::Messages => Some(stats.message_count as f64),
+            SortMode::Cost => stats.cost_usd,
+            SortMode::Tokens => Some(stats.total_tokens as f64),
+            SortMode::Duration => Some(stats.duration_secs as f64),
+            _ => None,
+        }
+    }
+
+    /// セッションの比較（集計メタデータのないセッションは後ろ、同順位は新しい順）
+    fn compare(
+        self,
+        a: &SessionListItem,
+        b: &SessionListItem,
+        stats: &HashMap<SessionKey, SessionStats>,
+    ) -> Ordering {
+        let newest = b.datetime.cmp(&a.datetime);
+        if self == SortMode::Oldest {
+            return newest.reverse();
+        }
+        if !self.uses_stats() {
+            return newest;
+        }
+        let key = |session: &SessionListItem| {
+            stats
+                .get(&session.key())
+                .and_then(|stats| self.stat_key(stats))
+        };
+        key(b)
+            .partial_cmp(&key(a))
+            .unwrap_or(Ordering::Equal)
+            .then(newest)
+    }
+}
+
+/// プロジェクト内のセッションとプロジェクトを並べ替え
+/// プロジェクトは並べ替え後の先頭セッションで並ぶ（Project はプロジェクト名順）
+pub fn sort_project_groups(
+    groups: &mut [ProjectGroup],
+    mode: SortMode,
+    stats: &HashMap<SessionKey, SessionStats>,
+) {
+    for group in groups.iter_mut() {
+        group.sessions.sort_by(|a, b| mode.compare(a, b, stats));
+    }
+    if mode == SortMode::Project {
+        groups.sort_by_key(|group| {
+            (
+                group.project_name.to_lowercase(),
+                group.project_path.clone(),
+            )
+        });
+        return;
+    }
+    // セッションのないプロジェクトは末尾
+    groups.sort_by(|a, b| match (a.sessions.first(), b.sessions.first()) {
+        (Some(a), Some(b)) => mode.compare(a, b, stats),
+        (a, b) => Reverse(a.is_some()).cmp(&Reverse(b.is_some())),
+    });
+}
+
+/// プロジェクトをまたいでセッションを並べ替え（フラット表示用）
+/// Project はプロジェクト名順、同じプロジェクト内は新しい順
+pub fn sort_sessions(
+    sessions: &mut [SessionListItem],
+    mode: SortMode,
+    stats: &HashMap<SessionKey, SessionStats>,
+) {
+    if mode == SortMode::Project {
+        sessions.sort_by(|a, b| {
+            (a.project_name.to_lowercase(), &a.project_path)
+                .cmp(&(b.project_name.to_lowercase(), &b.project_path))
+                .then(b.datetime.cmp(&a.datetime))
+        });
+        return;
+    }
+    sessions.sort_by(|a, b| mode.compare(a, b, stats));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SessionSource;
+    use crate::tea::SessionListItem;
+    use chrono::{TimeZone, Utc};
+
+    fn group(name: &str, days: &[u32]) -> ProjectGroup {
+        ProjectGroup {
+            project_path: format!("/{}", name),
+            project_name: name.to_string(),
+            sessions: days
+                .iter()
+                .map(|day| SessionListItem {
+                    session_id: format!("{}-{}", name, day),
+                    source: SessionSource::Claude,
+                    root: None,
+                    project_name: name.to_string(),
+                    project_path: format!("/{}", name),
+                    latest_user_message: String::new(),
+                    first_user_message: String::new(),
+                    formatted_time: String::new(),
+                    datetime: Utc.with_ymd_and_hms(2025, 1, *day, 0, 0, 0).unwrap(),
+                })
+                .collect(),
+        }
+    }
+
+    fn order(groups: &[ProjectGroup]) -> Vec<&str> {
+        groups
+            .iter()
+            .flat_map(|group| group.sessions.iter().map(|s| s.session_id.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_project_groups() {
+        let mut groups = vec![group("a", &[3, 1]), group("b", &[2, 5])];
+        let stats = HashMap::new();
+
+        sort_project_groups(&mut groups, SortMode::Newest, &stats);
+        assert_eq!(order(&groups), vec!["b-5", "b-2", "a-3", "a-1"]);
+
+        sort_project_groups(&mut groups, SortMode::Oldest, &stats);
+        assert_eq!(order(&groups), vec!["a-1", "a-3", "b-2", "b-5"]);
+    }
+
+    #[test]
+    fn test_sort_project_groups_by_stats() {
+        let mut groups = vec![group("b", &[1, 2, 3]), group("a", &[4])];
+        let mut stats = HashMap::new();
+        let mut insert = |id: &str, cost: Option<f64>, messages: usize| {
+            stats.insert(
+                (SessionSource::Claude, None, id.to_string()),
+                SessionStats {
+                    cost_usd: cost,
+                    message_count: messages,
+                    ..Default::default()
+                },
+            );
+        };
+        insert("b-1", Some(2.0), 10);
+        insert("b-2", Some(0.5), 10);
+        insert("b-3", None, 3);
+        insert("a-4", Some(1.0), 5);
+
+        // コスト不明・集計メタデータなしは後ろ
+        sort_project_groups(&mut groups, SortMode::Cost, &stats);
+        assert_eq!(order(&groups), vec!["b-1", "b-2", "b-3", "a-4"]);
+
+        // 同数は新しい順
+        sort_project_groups(&mut groups, SortMode::Messages, &stats);
+        assert_eq!(order(&groups), vec!["b-2", "b-1", "b-3", "a-4"]);
+
+        sort_project_groups(&mut groups, SortMode::Project, &stats);
+        assert_eq!(order(&groups), vec!["a-4", "b-3", "b-2", "b-1"]);
+    }
+
+    #[test]
+    fn test_sort_sessions_across_projects() {
+        let mut sessions: Vec<SessionListItem> = [group("b", &[1, 4]), group("a", &[2, 3])]
+            .into_iter()
+            .flat_map(|group| group.sessions)
+            .collect();
+        let ids = |sessions: &[SessionListItem]| -> Vec<String> {
+            sessions.iter().map(|s| s.session_id.clone()).collect()
+        };
+        let stats = HashMap::new();
+
+        sort_sessions(&mut sessions, SortMode::Newest, &stats);
+        assert_eq!(ids(&sessions), vec!["b-4", "a-3", "a-2", "b-1"]);
+
+        sort_sessions(&mut sessions, SortMode::Project, &stats);
+        assert_eq!(ids(&sessions), vec!["a-3", "a-2", "b-4", "b-1"]);
+    }
+
+    #[test]
+    fn test_sort_mode_cycle() {
+        let mut mode = SortMode::default();
+        let mut labels = Vec::new();
+        for _ in 0..SortMode::ALL.len() {
+            labels.push(mode.label());
+            mode = mode.next();
+        }
+        assert_eq!(mode, SortMode::Newest);
+        assert_eq!(labels.len(), 7);
+        assert!(SortMode::Duration.uses_stats());
+        assert!(!SortMode::Project.uses_stats());
+    }
+}
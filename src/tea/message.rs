@@ -1,7 +1,9 @@
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use crate::domain::Session;
+use crate::domain::{BudgetReport, Session, UsageReport};
 use crate::export::ExportFormat;
+use crate::search::{ContentSearchResult, SearchIndex};
 
 /// TEA アーキテクチャのメッセージ型
 /// UI イベントを表現する
@@ -23,14 +25,32 @@ pub enum Message {
     ScrollUp(usize),
     /// 下にスクロール
     ScrollDown(usize),
+    /// 選択中メッセージのツール結果を展開/折りたたみ
+    ToggleToolOutput,
+    /// thinking ブロックの展開/折りたたみ
+    ToggleThinking,
     /// 選択中メッセージをコピー
     CopySelectedMessage,
     /// 選択中メッセージをメタ情報付きでコピー
     CopySelectedMessageWithMeta,
+    /// 選択中メッセージのコード片選択を開く
+    OpenCodePicker,
+    /// 次のコード片を選択
+    CodePickerNext,
+    /// 前のコード片を選択
+    CodePickerPrev,
+    /// 選択中のコード片をコピー
+    CopyCodeSnippet,
+    /// コード片選択を閉じる
+    CloseCodePicker,
     /// コスト表示通貨を切り替え
     ToggleCurrency,
     /// テーマを切り替え
     ToggleTheme,
+    /// セッション一覧の並び順を切り替え
+    CycleSort,
+    /// ツリー表示とフラット表示を切り替え
+    ToggleListMode,
     /// セッション読み込み完了
     SessionLoaded(Session),
     /// セッション読み込みエラー
@@ -51,6 +71,32 @@ pub enum Message {
     SearchBackspace,
     /// 検索確定
     ConfirmSearch,
+    /// 検索範囲（タイトル / 本文）を切り替え
+    ToggleSearchScope,
+    /// マッチ方式（部分一致 / 正規表現 / あいまい）を切り替え
+    CycleMatchMode,
+    /// 本文検索完了
+    ContentSearchCompleted(ContentSearchResult),
+    /// 本文検索用インデックスの構築完了
+    SearchIndexReady(Arc<SearchIndex>),
+    /// 今月の予算の集計完了
+    BudgetReady(Arc<BudgetReport>),
+
+    // === 詳細画面内検索関連 ===
+    /// 詳細画面内検索開始
+    StartDetailFind,
+    /// 詳細画面内検索入力
+    DetailFindInput(char),
+    /// 詳細画面内検索バックスペース
+    DetailFindBackspace,
+    /// 詳細画面内検索確定
+    ConfirmDetailFind,
+    /// 詳細画面内検索キャンセル
+    CancelDetailFind,
+    /// 次の一致箇所へ移動
+    NextDetailMatch,
+    /// 前の一致箇所へ移動
+    PrevDetailMatch,
 
     // === フィルタ関連 ===
     /// フィルタモード開始
@@ -67,16 +113,33 @@ pub enum Message {
     FilterDatePresetNext,
     /// 日付プリセット前へ
     FilterDatePresetPrev,
-    /// プロジェクト入力
-    FilterProjectInput(char),
-    /// プロジェクトバックスペース
-    FilterProjectBackspace,
+    /// 選択中の入力欄（開始日・終了日・プロジェクト）に文字入力
+    FilterInput(char),
+    /// 選択中の入力欄のバックスペース
+    FilterBackspace,
 
     // === ヘルプ関連 ===
     /// ヘルプ表示
     ShowHelp,
     /// ヘルプを閉じる
     CloseHelp,
+    /// ヘルプを上にスクロール
+    HelpScrollUp,
+    /// ヘルプを下にスクロール
+    HelpScrollDown,
+
+    // === ダッシュボード関連 ===
+    /// ダッシュボード表示（フィルタ後のセッションを集計）
+    ShowDashboard,
+    /// ダッシュボードを閉じる
+    CloseDashboard,
+    /// グラフの表示値（コスト / トークン数）を切り替え
+    ToggleDashboardMetric,
+    /// ダッシュボードの集計完了（generation は集計開始時の世代）
+    DashboardReady {
+        generation: u64,
+        report: Arc<UsageReport>,
+    },
 
     // === エクスポート関連 ===
     /// エクスポートダイアログ表示
@@ -85,6 +148,8 @@ pub enum Message {
     SelectExportFormat(ExportFormat),
     /// エクスポート形式切り替え
     ToggleExportFormat,
+    /// エクスポートに thinking ブロックを含めるか切り替え
+    ToggleExportThinking,
     /// エクスポート実行
     ConfirmExport,
     /// エクスポートキャンセル
@@ -2,9 +2,11 @@ pub mod message;
 pub mod model;
 pub mod update;
 
+pub use crate::domain::{SessionKey, SessionSource};
 pub use message::Message;
 pub use model::{
-    ExportStatus, Model, ProjectGroup, SessionListItem, SessionPreview, SessionSource, TreeItem,
-    TreeNodeKind, ViewMode,
+    CodePicker, DEFAULT_LIST_WIDTH_PERCENT, DashboardMetric, DetailFind, DetailMatch, ExportStatus,
+    ListMode, Model, ProjectGroup, SessionListItem, SessionPreview, TreeItem, TreeNodeKind,
+    ViewMode,
 };
 pub use update::update;
@@ -1,13 +1,28 @@
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, Utc};
+use ratatui::text::{Line, Span};
 
-use crate::domain::{Currency, Session, SessionEntry};
+use crate::domain::{
+    BudgetReport, CodeSnippet, Currency, ExchangeRates, Session, SessionEntry, SessionKey,
+    SessionSource, SessionStats, ToolResults, UsageReport,
+};
 use crate::export::ExportFormat;
-use crate::search::{FilterCriteria, FilterField, SearchQuery};
-use crate::theme::Theme;
+use crate::search::{
+    ContentSearchResult, DatePreset, DateRange, FilterCriteria, FilterField, QueryContext,
+    QueryParseError, SearchIndex, SearchQuery, SearchScope, SortMode, Threshold, find_ranges,
+    sort_project_groups, sort_sessions,
+};
+use crate::theme::{Theme, ThemeCycle};
 use crate::widgets::MessageBlock;
+use unicode_width::UnicodeWidthStr;
+
+/// 2 ペイン表示での一覧の幅（%）の既定値
+pub const DEFAULT_LIST_WIDTH_PERCENT: u16 = 55;
 
 /// ツリーノードの種類
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,21 +33,6 @@ pub enum TreeNodeKind {
     Session,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum SessionSource {
-    Claude,
-    Codex,
-}
-
-impl SessionSource {
-    pub fn label(self) -> &'static str {
-        match self {
-            SessionSource::Claude => "Claude",
-            SessionSource::Codex => "Codex",
-        }
-    }
-}
-
 /// ツリー表示用のアイテム
 #[derive(Debug, Clone)]
 pub struct TreeItem {
@@ -55,9 +55,11 @@ pub struct TreeItem {
 impl TreeItem {
     /// プロジェクトノードを作成
     pub fn project(group: &ProjectGroup) -> Self {
+        // 並び順によらず最新のセッション
         let latest = group
             .sessions
-            .first()
+            .iter()
+            .max_by_key(|s| s.datetime)
             .map(|s| (s.datetime, s.formatted_time.clone()))
             .unwrap_or_else(|| (Utc::now(), String::new()));
 
@@ -120,12 +122,54 @@ pub enum ViewMode {
     SessionDetail,
     /// 検索モード
     Search,
+    /// 詳細画面内の検索入力
+    DetailSearch,
     /// フィルタモード
     Filter,
     /// ヘルプ表示
     Help,
     /// エクスポートダイアログ
     Export,
+    /// コード片の選択ポップアップ
+    CodePicker,
+    /// 使用量・コストのダッシュボード
+    Dashboard,
+}
+
+/// セッション一覧の表示形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListMode {
+    /// プロジェクトごとのツリー表示
+    #[default]
+    Tree,
+    /// プロジェクトをまたいだフラットなテーブル表示
+    Flat,
+}
+
+/// ダッシュボードのグラフに表示する値
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DashboardMetric {
+    /// コスト
+    #[default]
+    Cost,
+    /// トークン数
+    Tokens,
+}
+
+impl DashboardMetric {
+    pub fn toggle(self) -> Self {
+        match self {
+            DashboardMetric::Cost => DashboardMetric::Tokens,
+            DashboardMetric::Tokens => DashboardMetric::Cost,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DashboardMetric::Cost => "Cost",
+            DashboardMetric::Tokens => "Tokens",
+        }
+    }
 }
 
 /// セッションプレビュー（軽量なプレビュー情報）
@@ -160,18 +204,29 @@ pub struct SessionListItem {
     pub session_id: String,
     /// セッションの出所
     pub source: SessionSource,
+    /// データディレクトリのラベル（同じ種類のディレクトリが複数ある場合のみ）
+    pub root: Option<String>,
     /// プロジェクト名
     pub project_name: String,
     /// プロジェクトパス
     pub project_path: String,
     /// 表示テキスト（最新のユーザーメッセージ）
     pub latest_user_message: String,
+    /// セッション最初のユーザーメッセージ
+    pub first_user_message: String,
     /// フォーマット済み日時
     pub formatted_time: String,
     /// 日時（フィルタリング用）
     pub datetime: DateTime<Utc>,
 }
 
+impl SessionListItem {
+    /// セッションのキー
+    pub fn key(&self) -> SessionKey {
+        (self.source, self.root.clone(), self.session_id.clone())
+    }
+}
+
 /// TEA アーキテクチャの Model
 /// アプリケーション全体の状態を保持する
 #[derive(Debug, Clone)]
@@ -188,6 +243,8 @@ pub struct Model {
     pub previous_view_mode: ViewMode,
     /// 現在表示中のセッション
     pub current_session: Option<Session>,
+    /// 詳細表示のレンダリング結果（カーソル移動のたびに描き直さないため）
+    detail_cache: RefCell<DetailRenderCache>,
     /// 詳細表示のスクロールオフセット
     pub detail_scroll_offset: usize,
     /// 詳細表示のカーソル行（ビューポート内）
@@ -196,10 +253,36 @@ pub struct Model {
     pub detail_viewport_width: usize,
     /// 詳細表示のビューポート高さ（ボーダー除外）
     pub detail_viewport_height: usize,
+    /// 詳細画面内検索
+    pub detail_find: DetailFind,
+    /// 詳細表示中セッションのツール実行結果（tool_use_id で対応付け）
+    pub detail_tool_results: ToolResults,
+    /// ツール結果を展開中のメッセージ（詳細表示のメッセージ番号）
+    pub expanded_tool_output: HashSet<usize>,
+    /// ツール結果を展開するキー（折りたたみ時の案内用、未割り当てなら None）
+    pub tool_output_key: Option<String>,
+    /// 詳細表示で thinking ブロックを展開するか
+    pub show_thinking: bool,
+    /// thinking ブロックを展開するキー（折りたたみ時の案内用、未割り当てなら None）
+    pub thinking_key: Option<String>,
+    /// コード片の選択状態（ポップアップ表示中のみ Some）
+    pub code_picker: Option<CodePicker>,
     /// プレビュー用のセッション情報
     pub preview_session: Option<SessionPreview>,
     /// 検索クエリ
     pub search_query: SearchQuery,
+    /// 検索クエリの構文エラー
+    pub search_error: Option<QueryParseError>,
+    /// 本文検索の結果
+    pub content_search: Option<ContentSearchResult>,
+    /// 本文検索を実行中か
+    pub content_search_pending: bool,
+    /// 本文検索用インデックス（構築完了まで None）
+    pub search_index: Option<Arc<SearchIndex>>,
+    /// 検索スコア（sessions のインデックス -> スコア、高いほど上位）
+    pub search_scores: HashMap<usize, i64>,
+    /// セッションごとの集計メタデータ（インデックスと読み込んだセッションから取得）
+    pub session_stats: HashMap<SessionKey, SessionStats>,
     /// フィルタ条件
     pub filter_criteria: FilterCriteria,
     /// フィルタ適用後のインデックス一覧
@@ -210,14 +293,58 @@ pub struct Model {
     pub filter_field: FilterField,
     /// フィルタパネルのプロジェクト名入力
     pub filter_project_input: String,
-    /// 日付プリセット選択インデックス (0: All, 1: Today, 2: Last 7 days, 3: Last 30 days)
+    /// フィルタパネルの開始日入力
+    pub filter_from_input: String,
+    /// フィルタパネルの終了日入力
+    pub filter_to_input: String,
+    /// フィルタパネルで選択中の履歴の種類（None は両方）
+    pub filter_source: Option<SessionSource>,
+    /// フィルタパネルのモデル名入力
+    pub filter_model_input: String,
+    /// フィルタパネルの Git ブランチ入力
+    pub filter_branch_input: String,
+    /// フィルタパネルのバージョン入力
+    pub filter_version_input: String,
+    /// フィルタパネルのツール名入力
+    pub filter_tool_input: String,
+    /// フィルタパネルのコスト入力（USD のしきい値）
+    pub filter_cost_input: String,
+    /// フィルタパネルのトークン数入力（しきい値）
+    pub filter_tokens_input: String,
+    /// フィルタパネルのメッセージ数入力（しきい値）
+    pub filter_messages_input: String,
+    /// フィルタパネルの入力エラー
+    pub filter_error: Option<String>,
+    /// 日付プリセット選択インデックス (0: All, 1 以降: date_presets)
     pub date_preset_index: usize,
+    /// フィルタパネルの日付プリセット
+    pub date_presets: Vec<DatePreset>,
+    /// セッション一覧の並び順
+    pub sort_mode: SortMode,
+    /// セッション一覧の表示形式
+    pub list_mode: ListMode,
+    /// ダッシュボードの集計結果（集計中は None）
+    pub dashboard_report: Option<Arc<UsageReport>>,
+    /// ダッシュボードの集計を実行中か
+    pub dashboard_pending: bool,
+    /// ダッシュボードの集計の世代（開き直す前の古い結果を破棄する）
+    pub dashboard_generation: u64,
+    /// ダッシュボードのグラフに表示する値
+    pub dashboard_metric: DashboardMetric,
+    /// 今月の予算の消化状況（予算未設定・集計中は None）
+    pub budget_report: Option<Arc<BudgetReport>>,
     /// エクスポート形式
     pub export_format: ExportFormat,
     /// エクスポートステータス
     pub export_status: Option<ExportStatus>,
+    /// エクスポートに thinking ブロックを含めるか
+    pub export_include_thinking: bool,
     /// エラーメッセージ（セッション一覧画面で表示）
     pub error_message: Option<String>,
+    /// ヘルプのスクロール位置
+    pub help_scroll: usize,
+    /// ヘルプの最大スクロール位置（描画前に設定）
+    pub help_max_scroll: usize,
     /// プロジェクトグループ一覧
     pub project_groups: Vec<ProjectGroup>,
     /// フィルタ適用後のプロジェクトグループ一覧
@@ -230,15 +357,135 @@ pub struct Model {
     pub tree_items: Vec<TreeItem>,
     /// コスト表示通貨
     pub currency: Currency,
+    /// `u` で切り替える通貨
+    pub currencies: Vec<Currency>,
+    /// 為替レート表
+    pub exchange_rates: ExchangeRates,
+    /// 2 ペイン表示での一覧の幅（%）
+    pub list_width_percent: u16,
     /// テーマ
     pub theme: Theme,
+    /// 切り替え可能なテーマ
+    pub themes: ThemeCycle,
+}
+
+/// 詳細画面内検索の一致箇所
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetailMatch {
+    /// 一致した行（詳細画面の論理行）
+    pub line: usize,
+    /// 行内のバイト範囲
+    pub range: Range<usize>,
+    /// 折り返し後の表示行
+    pub row: usize,
+}
+
+/// 詳細画面内検索の状態
+#[derive(Debug, Clone, Default)]
+pub struct DetailFind {
+    /// 検索語
+    pub query: String,
+    /// 一致箇所（出現順）
+    pub matches: Vec<DetailMatch>,
+    /// 選択中の一致箇所
+    pub current: Option<usize>,
+    /// 検索開始時の位置（スクロールオフセット, カーソル行）
+    origin: Option<(usize, usize)>,
 }
 
+impl DetailFind {
+    /// 検索語が入力されているか
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    /// 「3/17」形式の位置表示
+    pub fn position_label(&self) -> Option<String> {
+        if !self.is_active() {
+            return None;
+        }
+        let current = self.current.map(|index| index + 1).unwrap_or(0);
+        Some(format!("{}/{}", current, self.matches.len()))
+    }
+}
+
+/// コード片の選択状態
+#[derive(Debug, Clone, Default)]
+pub struct CodePicker {
+    /// 選択中メッセージのコード片
+    pub snippets: Vec<CodeSnippet>,
+    /// 選択中のコード片
+    pub selected: usize,
+}
+
+impl CodePicker {
+    /// 選択中のコード片
+    pub fn selected_snippet(&self) -> Option<&CodeSnippet> {
+        self.snippets.get(self.selected)
+    }
+
+    /// 次のコード片を選択（末尾で先頭に戻る）
+    pub fn select_next(&mut self) {
+        if !self.snippets.is_empty() {
+            self.selected = (self.selected + 1) % self.snippets.len();
+        }
+    }
+
+    /// 前のコード片を選択（先頭で末尾に戻る）
+    pub fn select_prev(&mut self) {
+        if !self.snippets.is_empty() {
+            self.selected = (self.selected + self.snippets.len() - 1) % self.snippets.len();
+        }
+    }
+}
+
+/// 詳細表示の全メッセージの行と表示行の対応
+#[derive(Debug)]
 struct DetailLayout {
+    /// 全メッセージの論理行
+    lines: Vec<Line<'static>>,
     message_ranges: Vec<(usize, usize)>,
+    /// 論理行ごとの開始表示行
+    line_rows: Vec<usize>,
     total_lines: usize,
 }
 
+/// 全メッセージの描画に影響する表示設定
+#[derive(Debug, Clone, PartialEq)]
+struct DetailRenderKey {
+    width: u16,
+    theme: Theme,
+    currency: Currency,
+    show_thinking: bool,
+    tool_output_key: Option<String>,
+    thinking_key: Option<String>,
+}
+
+/// 詳細表示のレンダリング結果のキャッシュ
+/// 表示設定が変わると破棄し、ツール結果の展開は該当メッセージだけ描き直す
+#[derive(Debug, Clone, Default)]
+struct DetailRenderCache {
+    key: Option<DetailRenderKey>,
+    /// メッセージごとの (ツール結果を展開しているか, 行)
+    messages: Vec<Option<(bool, Vec<Line<'static>>)>>,
+    /// layout を作成したときの展開中のメッセージ
+    expanded: HashSet<usize>,
+    layout: Option<Arc<DetailLayout>>,
+}
+
+/// 借用しているテキストを所有する行に変換
+fn owned_line(line: Line<'_>) -> Line<'static> {
+    Line {
+        style: line.style,
+        alignment: line.alignment,
+        spans: line
+            .spans
+            .into_iter()
+            .map(|span| Span::styled(span.content.into_owned(), span.style))
+            .collect(),
+    }
+}
+
 impl Default for Model {
     fn default() -> Self {
         Self::new()
@@ -255,28 +502,68 @@ impl Model {
             view_mode: ViewMode::default(),
             previous_view_mode: ViewMode::default(),
             current_session: None,
+            detail_cache: RefCell::default(),
             detail_scroll_offset: 0,
             detail_cursor_row: 0,
             detail_viewport_width: 0,
             detail_viewport_height: 0,
+            detail_find: DetailFind::default(),
+            detail_tool_results: ToolResults::new(),
+            expanded_tool_output: HashSet::new(),
+            tool_output_key: None,
+            show_thinking: false,
+            thinking_key: None,
+            code_picker: None,
             preview_session: None,
             search_query: SearchQuery::default(),
+            search_error: None,
+            content_search: None,
+            content_search_pending: false,
+            search_index: None,
+            search_scores: HashMap::new(),
+            session_stats: HashMap::new(),
             filter_criteria: FilterCriteria::default(),
             filtered_indices: Vec::new(),
             is_filtered: false,
             filter_field: FilterField::default(),
             filter_project_input: String::new(),
+            filter_from_input: String::new(),
+            filter_to_input: String::new(),
+            filter_source: None,
+            filter_model_input: String::new(),
+            filter_branch_input: String::new(),
+            filter_version_input: String::new(),
+            filter_tool_input: String::new(),
+            filter_cost_input: String::new(),
+            filter_tokens_input: String::new(),
+            filter_messages_input: String::new(),
+            filter_error: None,
             date_preset_index: 0,
+            date_presets: DatePreset::defaults(),
+            sort_mode: SortMode::default(),
+            list_mode: ListMode::default(),
+            dashboard_report: None,
+            dashboard_pending: false,
+            dashboard_generation: 0,
+            dashboard_metric: DashboardMetric::default(),
+            budget_report: None,
             export_format: ExportFormat::default(),
             export_status: None,
+            export_include_thinking: false,
             error_message: None,
+            help_scroll: 0,
+            help_max_scroll: 0,
             project_groups: Vec::new(),
             filtered_project_groups: Vec::new(),
             expanded_projects: HashSet::new(),
             expanded_projects_before_filter: None,
             tree_items: Vec::new(),
-            currency: Currency::Usd,
+            currency: Currency::USD,
+            currencies: vec![Currency::USD, Currency::JPY],
+            exchange_rates: ExchangeRates::default(),
+            list_width_percent: DEFAULT_LIST_WIDTH_PERCENT,
             theme: Theme::default(),
+            themes: ThemeCycle::default(),
         }
     }
 
@@ -299,19 +586,102 @@ impl Model {
         self
     }
 
+    /// 並び順を適用（選択中のツリーアイテムは維持）
+    pub fn apply_sort(&mut self) {
+        // with_sessions で作ったモデルはグループがないので並べ替えない
+        if self.project_groups.is_empty() {
+            return;
+        }
+        let key = |item: &TreeItem| {
+            let session = item.session.as_ref();
+            (item.project_path.clone(), session.map(SessionListItem::key))
+        };
+        let selected = self.selected_tree_item().map(key);
+
+        sort_project_groups(
+            &mut self.project_groups,
+            self.sort_mode,
+            &self.session_stats,
+        );
+        self.sessions = self
+            .project_groups
+            .iter()
+            .flat_map(|group| group.sessions.iter().cloned())
+            .collect();
+        if self.is_filtered {
+            // セッションの位置が変わるので検索・フィルタを適用し直す
+            self.apply_search();
+        } else {
+            self.rebuild_tree_items();
+        }
+
+        if let Some(index) = selected.and_then(|selected| {
+            self.tree_items
+                .iter()
+                .position(|item| key(item) == selected)
+        }) {
+            self.selected_index = index;
+        }
+        self.update_preview();
+    }
+
+    /// ツリー表示とフラット表示を切り替え（選択中のセッションは維持）
+    pub fn toggle_list_mode(&mut self) {
+        let selected = self.selected_tree_item().map(|item| {
+            let session = item.session.as_ref();
+            (item.project_path.clone(), session.map(SessionListItem::key))
+        });
+
+        self.list_mode = match self.list_mode {
+            ListMode::Tree => ListMode::Flat,
+            ListMode::Flat => ListMode::Tree,
+        };
+        // ツリーに戻るときは選択中のセッションが見えるようにプロジェクトを展開
+        if self.list_mode == ListMode::Tree
+            && let Some((project_path, Some(_))) = &selected
+        {
+            self.expanded_projects.insert(project_path.clone());
+        }
+        self.rebuild_tree_items();
+
+        // プロジェクトを選択していた場合はそのプロジェクトの先頭セッションを選ぶ
+        let index = selected.and_then(|(project_path, session)| {
+            self.tree_items.iter().position(|item| {
+                item.project_path == project_path
+                    && match &session {
+                        Some(session) => item.session.as_ref().is_some_and(|s| s.key() == *session),
+                        None => true,
+                    }
+            })
+        });
+        self.selected_index = index.unwrap_or(0);
+        self.update_preview();
+    }
+
     /// 展開状態に基づいてツリーアイテムを再構築
     pub fn rebuild_tree_items(&mut self) {
         self.tree_items.clear();
 
-        let groups = self.active_project_groups().clone();
-        for group in &groups {
-            // プロジェクトノードを追加
-            self.tree_items.push(TreeItem::project(group));
-
-            // 展開されている場合はセッションノードを追加
-            if self.expanded_projects.contains(&group.project_path) {
-                for session in &group.sessions {
-                    self.tree_items.push(TreeItem::session(session));
+        if self.list_mode == ListMode::Flat {
+            // フラット表示はセッションのみを並び順に従って並べる
+            let mut sessions: Vec<SessionListItem> = self
+                .active_project_groups()
+                .iter()
+                .flat_map(|group| group.sessions.iter().cloned())
+                .collect();
+            sort_sessions(&mut sessions, self.sort_mode, &self.session_stats);
+            self.tree_items = sessions.iter().map(TreeItem::session).collect();
+        } else {
+            let groups = self.active_project_groups().clone();
+            for group in &groups {
+                // プロジェクトノードを追加
+                self.tree_items.push(TreeItem::project(group));
+
+                // 展開されている場合はセッションノードを追加
+                if self.expanded_projects.contains(&group.project_path) {
+                    for session in &group.sessions {
+                        self.tree_items.push(TreeItem::session(session));
+                    }
                 }
             }
         }
@@ -352,19 +722,21 @@ impl Model {
 
     /// 選択中のプロジェクトを展開
     pub fn expand_current_project(&mut self) {
-        if let Some(item) = self.selected_tree_item() {
-            if item.kind == TreeNodeKind::Project
-                && !self.expanded_projects.contains(&item.project_path)
-            {
-                let path = item.project_path.clone();
-                self.expanded_projects.insert(path);
-                self.rebuild_tree_items();
-            }
+        if let Some(item) = self.selected_tree_item()
+            && item.kind == TreeNodeKind::Project
+            && !self.expanded_projects.contains(&item.project_path)
+        {
+            let path = item.project_path.clone();
+            self.expanded_projects.insert(path);
+            self.rebuild_tree_items();
         }
     }
 
     /// 選択中のプロジェクトを折りたたみ
     pub fn collapse_current_project(&mut self) {
+        if self.list_mode == ListMode::Flat {
+            return;
+        }
         if let Some(item) = self.selected_tree_item() {
             let project_path = item.project_path.clone();
             if self.expanded_projects.contains(&project_path) {
@@ -393,6 +765,9 @@ impl Model {
 
     /// すべてのプロジェクトを折りたたみ
     pub fn collapse_all(&mut self) {
+        if self.list_mode == ListMode::Flat {
+            return;
+        }
         self.expanded_projects.clear();
         // 選択位置を調整（プロジェクトのみになるので）
         if let Some(item) = self.selected_tree_item() {
@@ -439,10 +814,10 @@ impl Model {
 
     /// 選択中のセッションを取得
     pub fn selected_session(&self) -> Option<&SessionListItem> {
-        if let Some(item) = self.selected_tree_item() {
-            if item.kind == TreeNodeKind::Session {
-                return item.session.as_ref();
-            }
+        if let Some(item) = self.selected_tree_item()
+            && item.kind == TreeNodeKind::Session
+        {
+            return item.session.as_ref();
         }
 
         if self.is_filtered {
@@ -477,11 +852,26 @@ impl Model {
     /// 検索/フィルタをクリア
     pub fn clear_search_filter(&mut self) {
         self.search_query = SearchQuery::default();
+        self.search_error = None;
+        self.content_search = None;
+        self.content_search_pending = false;
         self.filter_criteria.clear();
         self.filtered_indices.clear();
+        self.search_scores.clear();
         self.is_filtered = false;
         self.selected_index = 0;
         self.filter_project_input.clear();
+        self.filter_from_input.clear();
+        self.filter_to_input.clear();
+        self.filter_source = None;
+        self.filter_model_input.clear();
+        self.filter_branch_input.clear();
+        self.filter_version_input.clear();
+        self.filter_tool_input.clear();
+        self.filter_cost_input.clear();
+        self.filter_tokens_input.clear();
+        self.filter_messages_input.clear();
+        self.filter_error = None;
         self.date_preset_index = 0;
         self.filtered_project_groups.clear();
         self.restore_expanded_projects_after_filter();
@@ -493,11 +883,25 @@ impl Model {
     pub fn apply_search(&mut self) {
         use crate::search::SearchEngine;
 
-        self.filtered_indices = SearchEngine::search_and_filter(
-            &self.sessions,
-            &self.search_query,
-            &self.filter_criteria,
-        );
+        // 構文エラーの場合は直前の検索結果を維持
+        let query = match self.search_query.compile() {
+            Ok(query) => query,
+            Err(error) => {
+                self.search_error = Some(error);
+                return;
+            }
+        };
+        self.search_error = None;
+
+        let context = QueryContext {
+            stats: &self.session_stats,
+            scope: self.search_query.scope,
+            content: self.active_content_search(),
+        };
+        let ranked =
+            SearchEngine::rank_and_filter(&self.sessions, &query, context, &self.filter_criteria);
+        self.filtered_indices = ranked.iter().map(|(index, _)| *index).collect();
+        self.search_scores = ranked.into_iter().collect();
         let was_filtered = self.is_filtered;
         self.is_filtered = !self.search_query.is_empty() || self.filter_criteria.is_set();
         self.rebuild_filtered_project_groups();
@@ -507,13 +911,114 @@ impl Model {
         self.update_preview();
     }
 
+    /// 現在のクエリに対応する本文検索結果を取得
+    pub fn active_content_search(&self) -> Option<&ContentSearchResult> {
+        if self.search_query.scope != SearchScope::Content {
+            return None;
+        }
+        let terms = self.search_query.content_terms();
+        if terms.is_empty() {
+            return None;
+        }
+        self.content_search
+            .as_ref()
+            .filter(|result| result.terms == terms)
+    }
+
+    /// 検索インデックスで本文検索を実行して適用
+    /// 本文検索モードでない場合やインデックス未構築の場合は何もせず false を返す
+    pub fn search_content_with_index(&mut self) -> bool {
+        if self.search_query.scope != SearchScope::Content {
+            return false;
+        }
+        let Some(index) = &self.search_index else {
+            return false;
+        };
+
+        self.content_search = if self.search_query.content_terms().is_empty() {
+            None
+        } else {
+            Some(index.search(&self.search_query))
+        };
+        self.content_search_pending = false;
+        self.apply_search();
+        true
+    }
+
+    /// フィルタパネルで選択中の入力欄
+    pub fn focused_filter_input(&mut self) -> Option<&mut String> {
+        match self.filter_field {
+            FilterField::DateRange | FilterField::Source => None,
+            FilterField::From => Some(&mut self.filter_from_input),
+            FilterField::To => Some(&mut self.filter_to_input),
+            FilterField::Project => Some(&mut self.filter_project_input),
+            FilterField::Model => Some(&mut self.filter_model_input),
+            FilterField::Branch => Some(&mut self.filter_branch_input),
+            FilterField::Version => Some(&mut self.filter_version_input),
+            FilterField::Tool => Some(&mut self.filter_tool_input),
+            FilterField::Cost => Some(&mut self.filter_cost_input),
+            FilterField::Tokens => Some(&mut self.filter_tokens_input),
+            FilterField::Messages => Some(&mut self.filter_messages_input),
+        }
+    }
+
+    /// フィルタパネルの使用量入力（コスト・トークン数・メッセージ数）のしきい値
+    pub fn usage_thresholds(&self) -> Result<[Option<Threshold>; 3], String> {
+        let parse = |label: &str, input: &str| {
+            if input.trim().is_empty() {
+                return Ok(None);
+            }
+            input
+                .parse()
+                .map(Some)
+                .map_err(|e| format!("{}: {}", label, e))
+        };
+        Ok([
+            parse("Cost", &self.filter_cost_input)?,
+            parse("Tokens", &self.filter_tokens_input)?,
+            parse("Messages", &self.filter_messages_input)?,
+        ])
+    }
+
+    /// フィルタパネルの入力エラー（日付、しきい値の順に確認）
+    pub fn filter_input_error(&self) -> Option<String> {
+        self.custom_date_range()
+            .err()
+            .or_else(|| self.usage_thresholds().err())
+    }
+
+    /// フィルタパネルの日付入力から範囲を作成（未入力なら None）
+    pub fn custom_date_range(&self) -> Result<Option<DateRange>, String> {
+        if self.filter_from_input.trim().is_empty() && self.filter_to_input.trim().is_empty() {
+            return Ok(None);
+        }
+        DateRange::from_inputs(&self.filter_from_input, &self.filter_to_input, Local::now())
+            .map(Some)
+    }
+
+    /// 文字キーをそのまま入力する状態か
+    pub fn is_typing(&self) -> bool {
+        match self.view_mode {
+            ViewMode::Search | ViewMode::DetailSearch => true,
+            ViewMode::Filter => self.filter_field.accepts_text(),
+            _ => false,
+        }
+    }
+
     /// フィルタを適用
     pub fn apply_filter(&mut self) {
-        // プロジェクト入力をフィルタ条件に反映
-        if self.filter_project_input.is_empty() {
-            self.filter_criteria.project_filter = None;
-        } else {
-            self.filter_criteria.project_filter = Some(self.filter_project_input.clone());
+        // 入力欄をフィルタ条件に反映（空欄は条件なし）
+        let non_empty = |input: &str| (!input.is_empty()).then(|| input.to_string());
+        self.filter_criteria.project_filter = non_empty(&self.filter_project_input);
+        self.filter_criteria.source = self.filter_source;
+        self.filter_criteria.model = non_empty(&self.filter_model_input);
+        self.filter_criteria.branch = non_empty(&self.filter_branch_input);
+        self.filter_criteria.version = non_empty(&self.filter_version_input);
+        self.filter_criteria.tool = non_empty(&self.filter_tool_input);
+        if let Ok([cost, tokens, messages]) = self.usage_thresholds() {
+            self.filter_criteria.cost = cost;
+            self.filter_criteria.tokens = tokens;
+            self.filter_criteria.messages = messages;
         }
 
         self.apply_search();
@@ -525,19 +1030,27 @@ impl Model {
             return;
         }
 
-        let mut grouped: HashMap<String, Vec<SessionListItem>> = HashMap::new();
+        let mut grouped: HashMap<String, Vec<usize>> = HashMap::new();
         for index in &self.filtered_indices {
             if let Some(session) = self.sessions.get(*index) {
                 grouped
                     .entry(session.project_path.clone())
                     .or_default()
-                    .push(session.clone());
+                    .push(*index);
             }
         }
 
         let mut filtered_groups = Vec::new();
         for group in &self.project_groups {
-            if let Some(sessions) = grouped.remove(&group.project_path) {
+            if let Some(mut indices) = grouped.remove(&group.project_path) {
+                // プロジェクト内はスコアの高い順（同点は元の順序）
+                indices.sort_by_key(|index| {
+                    std::cmp::Reverse(self.search_scores.get(index).copied().unwrap_or(0))
+                });
+                let sessions = indices
+                    .iter()
+                    .filter_map(|index| self.sessions.get(*index).cloned())
+                    .collect();
                 filtered_groups.push(ProjectGroup {
                     project_path: group.project_path.clone(),
                     project_name: group.project_name.clone(),
@@ -577,6 +1090,7 @@ impl Model {
 
     /// 詳細表示のビューポートを更新
     pub fn set_detail_viewport(&mut self, width: usize, height: usize) {
+        let width_changed = self.detail_viewport_width != width;
         self.detail_viewport_width = width;
         self.detail_viewport_height = height;
         if height > 0 {
@@ -586,6 +1100,10 @@ impl Model {
         }
         let max_scroll = self.detail_max_scroll();
         self.detail_scroll_offset = self.detail_scroll_offset.min(max_scroll);
+        // 折り返し位置が変わるため一致箇所の表示行を再計算
+        if width_changed {
+            self.refresh_detail_find();
+        }
     }
 
     /// 上にスクロール
@@ -607,39 +1125,112 @@ impl Model {
             .entries
             .iter()
             .filter(|entry| {
-                (entry.is_user() || entry.is_assistant()) && entry.display_text().is_some()
+                let has_blocks = entry.message.as_ref().is_some_and(|message| {
+                    !message.tool_uses().is_empty() || !message.thinking_texts().is_empty()
+                });
+                (entry.is_user() || entry.is_assistant())
+                    && (entry.display_text().is_some() || has_blocks)
             })
             .collect()
     }
 
-    fn detail_layout(&self) -> Option<DetailLayout> {
-        let width = self.detail_viewport_width.max(1);
+    /// 詳細表示のメッセージブロックを作成
+    fn detail_message_block<'a>(
+        &'a self,
+        index: usize,
+        entry: &'a SessionEntry,
+        width: u16,
+    ) -> MessageBlock<'a> {
+        MessageBlock::new(entry, width, self.currency, self.theme)
+            .exchange_rates(&self.exchange_rates)
+            .tool_results(&self.detail_tool_results)
+            .tool_output_expanded(self.expanded_tool_output.contains(&index))
+            .tool_output_key(self.tool_output_key.as_deref())
+            .thinking_expanded(self.show_thinking)
+            .thinking_key(self.thinking_key.as_deref())
+    }
+
+    /// 詳細表示の全メッセージをレンダリング用の行に変換
+    pub fn detail_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.detail_render(width)
+            .map(|layout| layout.lines.clone())
+            .unwrap_or_default()
+    }
+
+    /// 表示中のセッションが変わったときにレンダリング結果を破棄
+    pub fn clear_detail_cache(&mut self) {
+        *self.detail_cache.get_mut() = DetailRenderCache::default();
+    }
+
+    /// 詳細表示のレンダリング結果（表示設定と展開状態が同じならキャッシュを返す）
+    fn detail_render(&self, width: u16) -> Option<Arc<DetailLayout>> {
         let entries = self.detail_message_entries();
         if entries.is_empty() {
             return None;
         }
+        let key = DetailRenderKey {
+            width,
+            theme: self.theme,
+            currency: self.currency,
+            show_thinking: self.show_thinking,
+            tool_output_key: self.tool_output_key.clone(),
+            thinking_key: self.thinking_key.clone(),
+        };
+        let mut cache = self.detail_cache.borrow_mut();
+        if cache.key.as_ref() != Some(&key) {
+            *cache = DetailRenderCache {
+                key: Some(key),
+                ..Default::default()
+            };
+        } else if cache.expanded == self.expanded_tool_output
+            && let Some(layout) = &cache.layout
+        {
+            return Some(Arc::clone(layout));
+        }
+
+        let wrap_width = usize::from(width.max(1));
+        cache.messages.resize(entries.len(), None);
+        let mut lines = Vec::new();
         let mut message_ranges = Vec::with_capacity(entries.len());
+        let mut line_rows = Vec::new();
         let mut total_lines = 0usize;
 
-        for entry in entries {
-            let block = MessageBlock::new(entry, width as u16, self.currency, self.theme);
-            let lines = block.to_lines();
-            let mut line_count = 0usize;
-            for line in lines {
+        for (index, entry) in entries.into_iter().enumerate() {
+            let expanded = self.expanded_tool_output.contains(&index);
+            let message = match cache.messages[index].take() {
+                Some((cached_expanded, message)) if cached_expanded == expanded => message,
+                _ => self
+                    .detail_message_block(index, entry, width)
+                    .to_lines()
+                    .into_iter()
+                    .map(owned_line)
+                    .collect(),
+            };
+            let start = total_lines;
+            for line in &message {
+                line_rows.push(total_lines);
                 let line_width = line.width().max(1);
-                let wrapped = (line_width + width - 1) / width;
-                line_count += wrapped.max(1);
+                let wrapped = line_width.div_ceil(wrap_width);
+                total_lines += wrapped.max(1);
             }
-            let start = total_lines;
-            let end = total_lines + line_count;
-            message_ranges.push((start, end));
-            total_lines = end;
+            message_ranges.push((start, total_lines));
+            lines.extend(message.iter().cloned());
+            cache.messages[index] = Some((expanded, message));
         }
 
-        Some(DetailLayout {
+        let layout = Arc::new(DetailLayout {
+            lines,
             message_ranges,
+            line_rows,
             total_lines,
-        })
+        });
+        cache.expanded = self.expanded_tool_output.clone();
+        cache.layout = Some(Arc::clone(&layout));
+        Some(layout)
+    }
+
+    fn detail_layout(&self) -> Option<Arc<DetailLayout>> {
+        self.detail_render(self.detail_viewport_width.max(1) as u16)
     }
 
     pub fn detail_max_scroll(&self) -> usize {
@@ -660,14 +1251,73 @@ impl Model {
     }
 
     pub fn detail_entry_for_cursor(&self) -> Option<&SessionEntry> {
+        let index = self.detail_message_index_for_cursor()?;
+        let entries = self.detail_message_entries();
+        entries.get(index).copied()
+    }
+
+    /// カーソル行のメッセージ番号
+    fn detail_message_index_for_cursor(&self) -> Option<usize> {
         let line = self.detail_cursor_line()?;
         let layout = self.detail_layout()?;
-        let index = layout
+        layout
             .message_ranges
             .iter()
-            .position(|(start, end)| line >= *start && line < *end)?;
-        let entries = self.detail_message_entries();
-        entries.get(index).copied()
+            .position(|(start, end)| line >= *start && line < *end)
+    }
+
+    /// カーソル行のメッセージのツール結果を展開/折りたたみ
+    pub fn toggle_tool_output(&mut self) {
+        let Some(index) = self.detail_message_index_for_cursor() else {
+            return;
+        };
+        self.relayout_detail(|model| {
+            if !model.expanded_tool_output.remove(&index) {
+                model.expanded_tool_output.insert(index);
+            }
+        });
+    }
+
+    /// thinking ブロックの展開/折りたたみ（全メッセージ共通）
+    pub fn toggle_thinking(&mut self) {
+        self.relayout_detail(|model| model.show_thinking = !model.show_thinking);
+    }
+
+    /// 表示行数が変わる変更を適用し、カーソルを同じメッセージ内に保つ
+    fn relayout_detail(&mut self, change: impl FnOnce(&mut Self)) {
+        let anchor = self.detail_message_index_for_cursor().and_then(|index| {
+            let layout = self.detail_layout()?;
+            let (start, _) = *layout.message_ranges.get(index)?;
+            let line = self.detail_scroll_offset + self.detail_cursor_row;
+            Some((index, line - start))
+        });
+        change(self);
+
+        // 行数が減ってはみ出す場合はメッセージ先頭にカーソルを戻す
+        if let Some((index, offset)) = anchor
+            && let Some(layout) = self.detail_layout()
+            && let Some(&(start, end)) = layout.message_ranges.get(index)
+        {
+            let line = if start + offset < end {
+                start + offset
+            } else {
+                start
+            };
+            self.detail_scroll_offset = self.detail_scroll_offset.min(self.detail_max_scroll());
+            self.place_detail_cursor(line);
+        }
+        self.refresh_detail_find();
+    }
+
+    /// 指定行にカーソルを移動（画面外ならビューポート中央付近へスクロール）
+    fn place_detail_cursor(&mut self, line: usize) {
+        let viewport = self.detail_viewport_height.max(1);
+        let visible = self.detail_scroll_offset..self.detail_scroll_offset + viewport;
+        if !visible.contains(&line) {
+            let max_scroll = self.detail_max_scroll();
+            self.detail_scroll_offset = line.saturating_sub(viewport / 2).min(max_scroll);
+        }
+        self.detail_cursor_row = line - self.detail_scroll_offset;
     }
 
     pub fn detail_total_lines(&self) -> usize {
@@ -727,6 +1377,119 @@ impl Model {
         self.detail_cursor_row = 0;
     }
 
+    /// 詳細画面内検索を開始（現在位置を起点として記録）
+    pub fn start_detail_find(&mut self) {
+        self.detail_find = DetailFind {
+            origin: Some((self.detail_scroll_offset, self.detail_cursor_row)),
+            ..DetailFind::default()
+        };
+    }
+
+    /// 検索語の変更を反映し、起点以降の最初の一致箇所へ移動
+    pub fn update_detail_find(&mut self) {
+        let (scroll, cursor) = self
+            .detail_find
+            .origin
+            .unwrap_or((self.detail_scroll_offset, self.detail_cursor_row));
+        self.detail_find.matches = self.collect_detail_matches();
+
+        if self.detail_find.matches.is_empty() {
+            self.detail_find.current = None;
+            self.detail_scroll_offset = scroll;
+            self.detail_cursor_row = cursor;
+            return;
+        }
+        let origin_row = scroll + cursor;
+        let index = self
+            .detail_find
+            .matches
+            .iter()
+            .position(|m| m.row >= origin_row)
+            .unwrap_or(0);
+        self.jump_to_detail_match(index);
+    }
+
+    /// 検索入力を確定
+    pub fn confirm_detail_find(&mut self) {
+        self.detail_find.origin = None;
+        if !self.detail_find.is_active() {
+            self.detail_find = DetailFind::default();
+        }
+    }
+
+    /// 検索を取り消して起点に戻る
+    pub fn cancel_detail_find(&mut self) {
+        if let Some((scroll, cursor)) = self.detail_find.origin {
+            self.detail_scroll_offset = scroll;
+            self.detail_cursor_row = cursor;
+        }
+        self.detail_find = DetailFind::default();
+    }
+
+    /// 次（または前）の一致箇所へ移動（端で折り返す）
+    pub fn move_detail_match(&mut self, forward: bool) {
+        let count = self.detail_find.matches.len();
+        if count == 0 {
+            return;
+        }
+        let index = match self.detail_find.current {
+            Some(current) if forward => (current + 1) % count,
+            Some(current) => (current + count - 1) % count,
+            None if forward => 0,
+            None => count - 1,
+        };
+        self.jump_to_detail_match(index);
+    }
+
+    /// 表示内容の変化に合わせて一致箇所を再計算（位置は移動しない）
+    pub fn refresh_detail_find(&mut self) {
+        if !self.detail_find.is_active() {
+            return;
+        }
+        self.detail_find.matches = self.collect_detail_matches();
+        let count = self.detail_find.matches.len();
+        self.detail_find.current = self
+            .detail_find
+            .current
+            .filter(|_| count > 0)
+            .map(|index| index.min(count - 1));
+    }
+
+    fn collect_detail_matches(&self) -> Vec<DetailMatch> {
+        let query = &self.detail_find.query;
+        let Some(layout) = self.detail_layout().filter(|_| !query.is_empty()) else {
+            return Vec::new();
+        };
+        let width = self.detail_viewport_width.max(1);
+        let mut matches = Vec::new();
+        for (line_index, line) in layout.lines.iter().enumerate() {
+            let text: String = line
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect();
+            for range in find_ranges(&text, query) {
+                // 一致開始位置までの表示幅から折り返し後の行を求める
+                let offset = UnicodeWidthStr::width(&text[..range.start]) / width;
+                matches.push(DetailMatch {
+                    line: line_index,
+                    range,
+                    row: layout.line_rows[line_index] + offset,
+                });
+            }
+        }
+        matches
+    }
+
+    /// 一致箇所へカーソルを移動
+    fn jump_to_detail_match(&mut self, index: usize) {
+        let Some(row) = self.detail_find.matches.get(index).map(|m| m.row) else {
+            return;
+        };
+        self.detail_find.current = Some(index);
+        self.place_detail_cursor(row);
+    }
+
     /// 選択中のセッションからプレビューを更新
     pub fn update_preview(&mut self) {
         // ツリーアイテムから選択中のセッションを取得
@@ -767,9 +1530,11 @@ mod tests {
             .map(|i| SessionListItem {
                 session_id: format!("session-{}", i),
                 source: SessionSource::Claude,
+                root: None,
                 project_name: format!("project-{}", i),
                 project_path: format!("/path/to/project-{}", i),
                 latest_user_message: format!("Message {}", i),
+                first_user_message: String::new(),
                 formatted_time: "2025-01-01 00:00".to_string(),
                 datetime: Utc::now(),
             })
@@ -896,9 +1661,11 @@ mod tests {
             .map(|i| SessionListItem {
                 session_id: format!("{}-session-{}", name, i),
                 source: SessionSource::Claude,
+                root: None,
                 project_name: name.to_string(),
                 project_path: format!("/path/to/{}", name),
                 latest_user_message: format!("Message {} for {}", i, name),
+                first_user_message: String::new(),
                 formatted_time: format!("2025-01-0{} 00:00", i + 1),
                 datetime: Utc::now(),
             })
@@ -936,9 +1703,11 @@ mod tests {
         let session = SessionListItem {
             session_id: "test-session-id".to_string(),
             source: SessionSource::Claude,
+            root: None,
             project_name: "test-project".to_string(),
             project_path: "/path/to/test-project".to_string(),
             latest_user_message: "Hello, world!".to_string(),
+            first_user_message: String::new(),
             formatted_time: "2025-01-15 10:30".to_string(),
             datetime: Utc::now(),
         };
@@ -1108,4 +1877,38 @@ mod tests {
         assert_eq!(model.tree_items.len(), 1);
         assert_eq!(model.selected_index, 0);
     }
+
+    #[test]
+    fn test_detail_render_cache() {
+        use crate::domain::message::{Message as ChatMessage, MessageContent};
+
+        let entry = SessionEntry {
+            entry_type: Some("user".to_string()),
+            message: Some(ChatMessage {
+                role: "user".to_string(),
+                content: MessageContent::Text("hello\nworld".to_string()),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        };
+        let mut model = Model::new();
+        model.current_session = Some(Session::from_entries(
+            "id".to_string(),
+            "/p".to_string(),
+            vec![entry],
+        ));
+        model.set_detail_viewport(40, 2);
+        let first = model.detail_layout().unwrap();
+
+        // カーソル移動では描き直さない
+        model.move_detail_cursor_down(1);
+        assert!(Arc::ptr_eq(&first, &model.detail_layout().unwrap()));
+
+        // 表示設定が変わると描き直す
+        model.show_thinking = true;
+        assert!(!Arc::ptr_eq(&first, &model.detail_layout().unwrap()));
+    }
 }
@@ -1,6 +1,9 @@
 use super::message::Message;
-use super::model::{ExportStatus, Model, TreeNodeKind, ViewMode};
-use crate::search::DateRange;
+use super::model::{
+    CodePicker, DetailFind, ExportStatus, Model, SessionListItem, TreeNodeKind, ViewMode,
+};
+use crate::domain::SessionSource;
+use crate::search::{FilterField, SearchScope, Threshold};
 
 /// TEA の update 関数
 /// Message を受け取り Model を更新する純粋関数
@@ -49,7 +52,11 @@ pub fn update(model: &mut Model, msg: Message) {
             // 一覧画面に戻る
             model.view_mode = ViewMode::SessionList;
             model.current_session = None;
+            model.clear_detail_cache();
             model.reset_detail_cursor();
+            model.detail_find = DetailFind::default();
+            model.detail_tool_results.clear();
+            model.expanded_tool_output.clear();
         }
         Message::ScrollUp(amount) => {
             model.move_detail_cursor_up(amount);
@@ -57,22 +64,89 @@ pub fn update(model: &mut Model, msg: Message) {
         Message::ScrollDown(amount) => {
             model.move_detail_cursor_down(amount);
         }
+        Message::ToggleToolOutput => {
+            model.toggle_tool_output();
+        }
+        Message::ToggleThinking => {
+            model.toggle_thinking();
+        }
         Message::CopySelectedMessage => {}
         Message::CopySelectedMessageWithMeta => {}
+        Message::OpenCodePicker => {
+            let snippets = model
+                .detail_entry_for_cursor()
+                .map(|entry| entry.code_snippets())
+                .unwrap_or_default();
+            if snippets.is_empty() {
+                model.error_message = Some("Selected message has no code blocks".to_string());
+            } else {
+                model.code_picker = Some(CodePicker {
+                    snippets,
+                    selected: 0,
+                });
+                model.previous_view_mode = model.view_mode;
+                model.view_mode = ViewMode::CodePicker;
+            }
+        }
+        Message::CodePickerNext => {
+            if let Some(picker) = &mut model.code_picker {
+                picker.select_next();
+            }
+        }
+        Message::CodePickerPrev => {
+            if let Some(picker) = &mut model.code_picker {
+                picker.select_prev();
+            }
+        }
+        // コピー自体は App 側で実行し、完了後に閉じる
+        Message::CopyCodeSnippet => {}
+        Message::CloseCodePicker => {
+            model.code_picker = None;
+            model.view_mode = model.previous_view_mode;
+        }
         Message::ToggleCurrency => {
-            model.currency = model.currency.toggle();
+            model.currency = model
+                .currencies
+                .iter()
+                .position(|&currency| currency == model.currency)
+                .and_then(|index| model.currencies.get(index + 1))
+                .or(model.currencies.first())
+                .copied()
+                .unwrap_or_default();
+            model.refresh_detail_find();
         }
         Message::ToggleTheme => {
-            model.theme = model.theme.toggle();
+            model.theme = model.themes.advance().theme;
+        }
+        Message::CycleSort => {
+            model.sort_mode = model.sort_mode.next();
+            model.apply_sort();
+        }
+        Message::ToggleListMode => {
+            model.toggle_list_mode();
         }
         Message::SessionLoaded(session) => {
+            // 読み込んだセッションの集計メタデータを検索用に反映
+            let selected_key = model
+                .selected_tree_item()
+                .and_then(|item| item.session.as_ref())
+                .filter(|item| item.session_id == session.id)
+                .map(SessionListItem::key);
+            if let Some(key) = selected_key {
+                model.session_stats.insert(key, session.stats());
+            }
+            model.detail_tool_results = session.tool_results();
             model.current_session = Some(session);
+            model.clear_detail_cache();
             model.reset_detail_cursor();
+            model.detail_find = DetailFind::default();
+            model.expanded_tool_output.clear();
         }
         Message::SessionLoadFailed(_error) => {
             // エラー時は一覧に戻る
             model.view_mode = ViewMode::SessionList;
             model.current_session = None;
+            model.clear_detail_cache();
         }
         Message::Quit => {
             model.should_quit = true;
@@ -88,38 +162,145 @@ pub fn update(model: &mut Model, msg: Message) {
         Message::CancelSearch => {
             model.view_mode = ViewMode::SessionList;
             model.search_query.text.clear();
+            model.content_search = None;
+            model.content_search_pending = false;
             model.apply_search(); // 検索をクリアして再適用
         }
         Message::SearchInput(c) => {
             model.search_query.text.push(c);
-            model.apply_search(); // インクリメンタル検索
+            // 本文検索はインデックス構築済みの場合のみインクリメンタル（未構築なら確定時に実行）
+            if !model.search_content_with_index()
+                && model.search_query.scope == SearchScope::Metadata
+            {
+                model.apply_search(); // インクリメンタル検索
+            }
         }
         Message::SearchBackspace => {
             model.search_query.text.pop();
-            model.apply_search(); // インクリメンタル検索
+            if !model.search_content_with_index()
+                && model.search_query.scope == SearchScope::Metadata
+            {
+                model.apply_search(); // インクリメンタル検索
+            }
         }
         Message::ConfirmSearch => {
             model.view_mode = ViewMode::SessionList;
-            model.apply_search();
+            if model.search_query.scope == SearchScope::Content
+                && !model.search_query.content_terms().is_empty()
+                && model.active_content_search().is_none()
+            {
+                if !model.search_content_with_index() {
+                    // App 側で本文検索を開始し、完了時に ContentSearchCompleted を受け取る
+                    model.content_search_pending = true;
+                }
+            } else {
+                model.apply_search();
+            }
+        }
+        Message::ToggleSearchScope => {
+            model.search_query.scope = model.search_query.scope.toggle();
+            model.content_search = None;
+            model.content_search_pending = false;
+            if !model.search_content_with_index() {
+                model.apply_search();
+            }
+        }
+        Message::CycleMatchMode => {
+            // 本文検索は部分一致のみのため切り替えない
+            if model.search_query.scope == SearchScope::Metadata {
+                model.search_query.match_mode = model.search_query.match_mode.next();
+                model.apply_search();
+            }
+        }
+        Message::ContentSearchCompleted(result) => {
+            // 検索中にクエリが変わった場合は古い結果を破棄
+            if result.terms == model.search_query.content_terms()
+                && model.search_query.scope == SearchScope::Content
+            {
+                model.content_search = Some(result);
+                model.content_search_pending = false;
+                model.apply_search();
+            }
+        }
+        Message::SearchIndexReady(index) => {
+            model.session_stats = index.stats_map();
+            model.search_index = Some(index);
+            // 集計メタデータで並べ替えている場合は並べ直す
+            if model.sort_mode.uses_stats() {
+                model.apply_sort();
+            }
+            // 検索中または集計メタデータで絞り込み中であれば検索し直す
+            let needs_refresh =
+                !model.search_query.is_empty() || model.filter_criteria.has_metadata_filter();
+            if needs_refresh && !model.search_content_with_index() {
+                model.apply_search();
+            }
+        }
+        Message::BudgetReady(report) => {
+            model.budget_report = Some(report);
+        }
+
+        // === 詳細画面内検索関連 ===
+        Message::StartDetailFind => {
+            model.view_mode = ViewMode::DetailSearch;
+            model.start_detail_find();
+        }
+        Message::DetailFindInput(c) => {
+            model.detail_find.query.push(c);
+            model.update_detail_find();
+        }
+        Message::DetailFindBackspace => {
+            model.detail_find.query.pop();
+            model.update_detail_find();
+        }
+        Message::ConfirmDetailFind => {
+            model.view_mode = ViewMode::SessionDetail;
+            model.confirm_detail_find();
+        }
+        Message::CancelDetailFind => {
+            model.view_mode = ViewMode::SessionDetail;
+            model.cancel_detail_find();
+        }
+        Message::NextDetailMatch => {
+            model.move_detail_match(true);
+        }
+        Message::PrevDetailMatch => {
+            model.move_detail_match(false);
         }
 
         // === フィルタ関連 ===
         Message::StartFilter => {
             model.view_mode = ViewMode::Filter;
             // フィルタパネル表示時に現在の状態を入力フィールドに反映
-            model.filter_project_input = model
-                .filter_criteria
-                .project_filter
-                .clone()
-                .unwrap_or_default();
+            let criteria = &model.filter_criteria;
+            model.filter_project_input = criteria.project_filter.clone().unwrap_or_default();
+            model.filter_source = criteria.source;
+            model.filter_model_input = criteria.model.clone().unwrap_or_default();
+            model.filter_branch_input = criteria.branch.clone().unwrap_or_default();
+            model.filter_version_input = criteria.version.clone().unwrap_or_default();
+            model.filter_tool_input = criteria.tool.clone().unwrap_or_default();
+            let threshold_input =
+                |threshold: Option<Threshold>| threshold.map(|t| t.to_string()).unwrap_or_default();
+            model.filter_cost_input = threshold_input(criteria.cost);
+            model.filter_tokens_input = threshold_input(criteria.tokens);
+            model.filter_messages_input = threshold_input(criteria.messages);
         }
         Message::CancelFilter => {
             model.view_mode = ViewMode::SessionList;
         }
-        Message::ApplyFilter => {
-            model.view_mode = ViewMode::SessionList;
-            model.apply_filter();
-        }
+        Message::ApplyFilter => match model.filter_input_error() {
+            // 入力が不正なら閉じずにエラーを表示
+            Some(e) => model.filter_error = Some(e),
+            None => {
+                match model.custom_date_range() {
+                    Ok(Some(range)) => model.filter_criteria.date_range = range,
+                    _ => update_date_range_from_preset(model),
+                }
+                model.filter_error = None;
+                model.view_mode = ViewMode::SessionList;
+                model.apply_filter();
+            }
+        },
         Message::ClearFilter => {
             if model.is_filtered || !model.search_query.is_empty() {
                 model.clear_search_filter();
@@ -132,38 +313,55 @@ pub fn update(model: &mut Model, msg: Message) {
             model.filter_field = model.filter_field.next();
         }
         Message::FilterDatePresetNext => {
-            if model.date_preset_index < 3 {
-                model.date_preset_index += 1;
-                update_date_range_from_preset(model);
+            if model.filter_field == FilterField::Source {
+                model.filter_source = cycle_source(model.filter_source, true);
+            } else {
+                select_date_preset(model, model.date_preset_index + 1);
             }
         }
         Message::FilterDatePresetPrev => {
-            if model.date_preset_index > 0 {
-                model.date_preset_index -= 1;
-                update_date_range_from_preset(model);
+            if model.filter_field == FilterField::Source {
+                model.filter_source = cycle_source(model.filter_source, false);
+            } else if let Some(index) = model.date_preset_index.checked_sub(1) {
+                select_date_preset(model, index);
             }
         }
-        Message::FilterProjectInput(c) => {
-            model.filter_project_input.push(c);
+        Message::FilterInput(c) => {
+            if let Some(input) = model.focused_filter_input() {
+                input.push(c);
+                model.filter_error = model.filter_input_error();
+            }
         }
-        Message::FilterProjectBackspace => {
-            model.filter_project_input.pop();
+        Message::FilterBackspace => {
+            if let Some(input) = model.focused_filter_input() {
+                input.pop();
+                model.filter_error = model.filter_input_error();
+            }
         }
 
         // === ヘルプ関連 ===
         Message::ShowHelp => {
             model.previous_view_mode = model.view_mode;
             model.view_mode = ViewMode::Help;
+            model.help_scroll = 0;
         }
         Message::CloseHelp => {
             model.view_mode = model.previous_view_mode;
         }
+        Message::HelpScrollUp => {
+            model.help_scroll = model.help_scroll.saturating_sub(1);
+        }
+        Message::HelpScrollDown => {
+            model.help_scroll = (model.help_scroll + 1).min(model.help_max_scroll);
+        }
 
         // === エクスポート関連 ===
         Message::StartExport => {
             model.previous_view_mode = model.view_mode;
             model.view_mode = ViewMode::Export;
             model.export_status = Some(ExportStatus::Selecting);
+            // 詳細表示の thinking 表示状態を初期値にする
+            model.export_include_thinking = model.show_thinking;
         }
         Message::SelectExportFormat(format) => {
             model.export_format = format;
@@ -171,6 +369,9 @@ pub fn update(model: &mut Model, msg: Message) {
         Message::ToggleExportFormat => {
             model.export_format = model.export_format.next();
         }
+        Message::ToggleExportThinking => {
+            model.export_include_thinking = !model.export_include_thinking;
+        }
         Message::ConfirmExport => {
             model.export_status = Some(ExportStatus::Exporting);
         }
@@ -178,6 +379,29 @@ pub fn update(model: &mut Model, msg: Message) {
             model.view_mode = model.previous_view_mode;
             model.export_status = None;
         }
+        // === ダッシュボード関連 ===
+        Message::ShowDashboard => {
+            model.view_mode = ViewMode::Dashboard;
+            // App 側で集計を開始し、完了時に DashboardReady を受け取る
+            model.dashboard_generation += 1;
+            model.dashboard_report = None;
+            model.dashboard_pending = true;
+        }
+        Message::CloseDashboard => {
+            model.view_mode = ViewMode::SessionList;
+            model.dashboard_pending = false;
+        }
+        Message::ToggleDashboardMetric => {
+            model.dashboard_metric = model.dashboard_metric.toggle();
+        }
+        Message::DashboardReady { generation, report } => {
+            // 集計中に開き直した場合は古い結果を破棄
+            if generation == model.dashboard_generation {
+                model.dashboard_report = Some(report);
+                model.dashboard_pending = false;
+            }
+        }
+
         Message::ExportCompleted(path) => {
             model.export_status = Some(ExportStatus::Success(path));
         }
@@ -217,15 +441,47 @@ pub fn update(model: &mut Model, msg: Message) {
     }
 }
 
+/// 日付プリセットを選択（日付範囲のフィールドを選択中のみ）
+fn select_date_preset(model: &mut Model, index: usize) {
+    if model.filter_field != FilterField::DateRange || index > model.date_presets.len() {
+        return;
+    }
+    model.date_preset_index = index;
+    // プリセットを選んだら日付の入力は破棄
+    model.filter_from_input.clear();
+    model.filter_to_input.clear();
+    model.filter_error = None;
+    update_date_range_from_preset(model);
+}
+
+/// 履歴の種類の選択を切り替え（All → Claude → Codex）
+fn cycle_source(source: Option<SessionSource>, forward: bool) -> Option<SessionSource> {
+    let options = [
+        None,
+        Some(SessionSource::Claude),
+        Some(SessionSource::Codex),
+    ];
+    let index = options
+        .iter()
+        .position(|option| *option == source)
+        .unwrap_or(0);
+    let next = if forward {
+        (index + 1) % options.len()
+    } else {
+        (index + options.len() - 1) % options.len()
+    };
+    options[next]
+}
+
 /// 日付プリセットインデックスから DateRange を更新
 fn update_date_range_from_preset(model: &mut Model) {
-    model.filter_criteria.date_range = match model.date_preset_index {
-        0 => DateRange::default(), // All
-        1 => DateRange::today(),
-        2 => DateRange::last_week(),
-        3 => DateRange::last_month(),
-        _ => DateRange::default(),
-    };
+    // 0 は All（範囲指定なし）
+    model.filter_criteria.date_range = model
+        .date_preset_index
+        .checked_sub(1)
+        .and_then(|index| model.date_presets.get(index))
+        .map(|preset| preset.date_range())
+        .unwrap_or_default();
 }
 
 #[cfg(test)]
@@ -240,9 +496,11 @@ mod tests {
             .map(|i| SessionListItem {
                 session_id: format!("session-{}", i),
                 source: crate::tea::SessionSource::Claude,
+                root: None,
                 project_name: format!("project-{}", i),
                 project_path: format!("/path/to/project-{}", i),
                 latest_user_message: format!("Message {}", i),
+                first_user_message: String::new(),
                 formatted_time: "2025-01-01 00:00".to_string(),
                 datetime: Utc::now(),
             })
@@ -308,4 +566,677 @@ mod tests {
         assert_eq!(model.selected_index, 2);
         assert!(!model.should_quit);
     }
+
+    #[test]
+    fn test_update_content_search_flow() {
+        use crate::search::{ContentSearchResult, ContentTerms, MatchMode};
+        use crate::tea::SessionSource;
+
+        let mut model = create_test_model();
+        update(&mut model, Message::StartSearch);
+        update(&mut model, Message::ToggleSearchScope);
+        assert_eq!(model.search_query.scope, SearchScope::Content);
+        // 本文検索ではマッチ方式を切り替えない
+        update(&mut model, Message::CycleMatchMode);
+        assert_eq!(model.search_query.match_mode, MatchMode::Substring);
+
+        update(&mut model, Message::SearchInput('x'));
+        // 本文検索は入力中には適用されない
+        assert!(!model.content_search_pending);
+
+        update(&mut model, Message::ConfirmSearch);
+        assert!(model.content_search_pending);
+        assert_eq!(model.view_mode, ViewMode::SessionList);
+
+        let mut result = ContentSearchResult::new(ContentTerms {
+            include: vec!["x".to_string()],
+            ..Default::default()
+        });
+        result
+            .hits
+            .insert((SessionSource::Claude, None, "session-3".to_string()), 2);
+        update(&mut model, Message::ContentSearchCompleted(result));
+
+        assert!(!model.content_search_pending);
+        assert!(model.is_filtered);
+        assert_eq!(model.filtered_indices, vec![3]);
+    }
+
+    #[test]
+    fn test_update_content_search_with_index() {
+        use crate::search::{ContentSearchTarget, SearchIndex};
+        use crate::tea::SessionSource;
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session-1.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"type":"user","message":{"role":"user","content":"indexed keyword"}}"#,
+        )
+        .unwrap();
+        let mut index = SearchIndex::new();
+        index.update(&[ContentSearchTarget {
+            source: SessionSource::Claude,
+            root: None,
+            session_id: "session-1".to_string(),
+            path,
+        }]);
+
+        let mut model = create_test_model();
+        update(&mut model, Message::StartSearch);
+        update(&mut model, Message::ToggleSearchScope);
+        update(&mut model, Message::SearchInput('k'));
+        // インデックス構築前は確定まで適用されない
+        assert!(model.active_content_search().is_none());
+
+        update(&mut model, Message::SearchIndexReady(Arc::new(index)));
+        assert_eq!(model.filtered_indices, vec![1]);
+
+        // 構築後は入力ごとに適用される
+        update(&mut model, Message::SearchInput('z'));
+        assert!(model.filtered_indices.is_empty());
+        update(&mut model, Message::SearchBackspace);
+        assert_eq!(model.filtered_indices, vec![1]);
+
+        update(&mut model, Message::ConfirmSearch);
+        assert!(!model.content_search_pending);
+        assert_eq!(model.filtered_indices, vec![1]);
+    }
+
+    #[test]
+    fn test_update_search_parse_error_keeps_results() {
+        let mut model = create_test_model();
+        update(&mut model, Message::StartSearch);
+        for c in "project:project-2 \"".chars() {
+            update(&mut model, Message::SearchInput(c));
+        }
+
+        // 構文エラー中は直前の結果を維持
+        assert!(model.search_error.is_some());
+        assert_eq!(model.filtered_indices, vec![2]);
+
+        update(&mut model, Message::SearchBackspace);
+        assert!(model.search_error.is_none());
+        assert_eq!(model.filtered_indices, vec![2]);
+    }
+
+    #[test]
+    fn test_update_fuzzy_search_ranks_within_project() {
+        use crate::search::MatchMode;
+        use crate::tea::ProjectGroup;
+
+        let sessions: Vec<SessionListItem> = ["l-o-g-i-n", "unrelated", "login page"]
+            .iter()
+            .enumerate()
+            .map(|(i, message)| SessionListItem {
+                session_id: format!("session-{}", i),
+                source: crate::tea::SessionSource::Claude,
+                root: None,
+                project_name: "app".to_string(),
+                project_path: "/path/to/app".to_string(),
+                latest_user_message: message.to_string(),
+                first_user_message: String::new(),
+                formatted_time: "2025-01-01 00:00".to_string(),
+                datetime: Utc::now(),
+            })
+            .collect();
+        let mut model = Model::new().with_project_groups(vec![ProjectGroup {
+            project_path: "/path/to/app".to_string(),
+            project_name: "app".to_string(),
+            sessions,
+        }]);
+
+        update(&mut model, Message::StartSearch);
+        update(&mut model, Message::CycleMatchMode);
+        update(&mut model, Message::CycleMatchMode);
+        assert_eq!(model.search_query.match_mode, MatchMode::Fuzzy);
+        for c in "login".chars() {
+            update(&mut model, Message::SearchInput(c));
+        }
+
+        let ids: Vec<&str> = model.filtered_project_groups[0]
+            .sessions
+            .iter()
+            .map(|s| s.session_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["session-2", "session-0"]);
+    }
+
+    #[test]
+    fn test_update_content_search_discards_stale_result() {
+        use crate::search::{ContentSearchResult, ContentTerms};
+
+        let mut model = create_test_model();
+        update(&mut model, Message::ToggleSearchScope);
+        update(&mut model, Message::SearchInput('a'));
+        update(&mut model, Message::ConfirmSearch);
+
+        let result = ContentSearchResult::new(ContentTerms {
+            include: vec!["old".to_string()],
+            ..Default::default()
+        });
+        update(&mut model, Message::ContentSearchCompleted(result));
+
+        assert!(model.content_search.is_none());
+        assert!(model.content_search_pending);
+    }
+
+    fn create_detail_model(texts: &[&str]) -> Model {
+        use crate::domain::message::{Message as ChatMessage, MessageContent};
+        use crate::domain::{Session, SessionEntry};
+
+        let entries = texts
+            .iter()
+            .map(|text| SessionEntry {
+                entry_type: Some("user".to_string()),
+                message: Some(ChatMessage {
+                    role: "user".to_string(),
+                    content: MessageContent::Text(text.to_string()),
+                    model: None,
+                    id: None,
+                    stop_reason: None,
+                    usage: None,
+                }),
+                ..Default::default()
+            })
+            .collect();
+
+        let mut model = Model::new();
+        model.view_mode = ViewMode::SessionDetail;
+        model.current_session = Some(Session::from_entries(
+            "id".to_string(),
+            "/p".to_string(),
+            entries,
+        ));
+        // 各メッセージは ヘッダー・本文・空行 の 3 行
+        model.set_detail_viewport(40, 3);
+        model
+    }
+
+    #[test]
+    fn test_update_detail_find_navigation() {
+        let mut model = create_detail_model(&["alpha bug", "nothing", "bug and BUG"]);
+
+        update(&mut model, Message::StartDetailFind);
+        assert_eq!(model.view_mode, ViewMode::DetailSearch);
+        for c in "bug".chars() {
+            update(&mut model, Message::DetailFindInput(c));
+        }
+        assert_eq!(model.detail_find.matches.len(), 3);
+        assert_eq!(model.detail_find.position_label().as_deref(), Some("1/3"));
+        assert_eq!(
+            (model.detail_scroll_offset, model.detail_cursor_row),
+            (0, 1)
+        );
+
+        update(&mut model, Message::ConfirmDetailFind);
+        assert_eq!(model.view_mode, ViewMode::SessionDetail);
+
+        // 画面外の一致箇所へスクロール
+        update(&mut model, Message::NextDetailMatch);
+        assert_eq!(
+            (model.detail_scroll_offset, model.detail_cursor_row),
+            (6, 1)
+        );
+        update(&mut model, Message::NextDetailMatch);
+        assert_eq!(model.detail_find.position_label().as_deref(), Some("3/3"));
+        assert_eq!(model.detail_find.matches[2].range, 8..11);
+
+        // 末尾から先頭へ折り返す
+        update(&mut model, Message::NextDetailMatch);
+        assert_eq!(model.detail_find.current, Some(0));
+        assert_eq!(
+            (model.detail_scroll_offset, model.detail_cursor_row),
+            (0, 1)
+        );
+        update(&mut model, Message::PrevDetailMatch);
+        assert_eq!(model.detail_find.current, Some(2));
+
+        update(&mut model, Message::BackToList);
+        assert!(!model.detail_find.is_active());
+    }
+
+    #[test]
+    fn test_update_detail_find_cancel_restores_position() {
+        let mut model = create_detail_model(&["alpha", "beta", "gamma"]);
+
+        update(&mut model, Message::StartDetailFind);
+        for c in "gam".chars() {
+            update(&mut model, Message::DetailFindInput(c));
+        }
+        assert_eq!(model.detail_scroll_offset, 6);
+
+        update(&mut model, Message::DetailFindInput('x'));
+        assert_eq!(model.detail_find.position_label().as_deref(), Some("0/0"));
+
+        update(&mut model, Message::CancelDetailFind);
+        assert_eq!(model.view_mode, ViewMode::SessionDetail);
+        assert_eq!(
+            (model.detail_scroll_offset, model.detail_cursor_row),
+            (0, 0)
+        );
+        assert!(model.detail_find.position_label().is_none());
+    }
+
+    #[test]
+    fn test_update_toggle_tool_output() {
+        use crate::domain::{ContentBlock, MessageContent, ToolResult};
+
+        let mut model = create_detail_model(&["run tests"]);
+        let session = model.current_session.as_mut().unwrap();
+        let message = session.entries[0].message.as_mut().unwrap();
+        message.content = MessageContent::Blocks(vec![ContentBlock::ToolUse {
+            id: "toolu_1".to_string(),
+            name: "Bash".to_string(),
+            input: serde_json::json!({"command": "cargo test"}),
+        }]);
+        model.detail_tool_results.insert(
+            "toolu_1".to_string(),
+            ToolResult {
+                text: "line\n".repeat(10),
+                is_error: false,
+            },
+        );
+        // セッションを直接書き換えたのでレンダリング結果を破棄
+        model.clear_detail_cache();
+        // ヘッダー, ツール行, 結果 3 行, 残り行数, 空行
+        assert_eq!(model.detail_total_lines(), 7);
+
+        update(&mut model, Message::ToggleToolOutput);
+        assert_eq!(model.detail_total_lines(), 13);
+
+        // 末尾付近から折りたたむとメッセージ先頭に戻る
+        model.move_detail_cursor_down(12);
+        update(&mut model, Message::ToggleToolOutput);
+        assert_eq!(model.detail_total_lines(), 7);
+        assert_eq!(model.detail_cursor_line(), Some(0));
+    }
+
+    #[test]
+    fn test_update_code_picker() {
+        let mut model = create_detail_model(&["plain", "```sh\nls\n```\n```py\nprint()\n```"]);
+
+        // コード片のないメッセージではエラー表示のみ
+        update(&mut model, Message::OpenCodePicker);
+        assert_eq!(model.view_mode, ViewMode::SessionDetail);
+        assert!(model.code_picker.is_none());
+        assert!(model.error_message.is_some());
+
+        model.move_detail_cursor_down(3);
+        update(&mut model, Message::OpenCodePicker);
+        assert_eq!(model.view_mode, ViewMode::CodePicker);
+        let labels: Vec<&str> = model
+            .code_picker
+            .as_ref()
+            .unwrap()
+            .snippets
+            .iter()
+            .map(|snippet| snippet.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["sh", "py"]);
+
+        update(&mut model, Message::CodePickerNext);
+        update(&mut model, Message::CodePickerNext);
+        let picker = model.code_picker.as_ref().unwrap();
+        assert_eq!(picker.selected_snippet().unwrap().text, "ls");
+        update(&mut model, Message::CodePickerPrev);
+        let picker = model.code_picker.as_ref().unwrap();
+        assert_eq!(picker.selected_snippet().unwrap().text, "print()");
+
+        update(&mut model, Message::CloseCodePicker);
+        assert_eq!(model.view_mode, ViewMode::SessionDetail);
+        assert!(model.code_picker.is_none());
+    }
+
+    #[test]
+    fn test_update_toggle_thinking() {
+        use crate::domain::{ContentBlock, MessageContent};
+
+        let mut model = create_detail_model(&["first", "second"]);
+        let session = model.current_session.as_mut().unwrap();
+        session.entries[1].message.as_mut().unwrap().content = MessageContent::Blocks(vec![
+            ContentBlock::Thinking {
+                thinking: "a\nb\nc".to_string(),
+            },
+            ContentBlock::Text {
+                text: "second".to_string(),
+            },
+        ]);
+        model.clear_detail_cache();
+        // 1 件目 3 行 + 2 件目（ヘッダー, thinking 要約, 空行, 本文, 空行）
+        assert_eq!(model.detail_total_lines(), 8);
+
+        // 2 件目の本文行にカーソルを置いて展開してもカーソルは同じメッセージ内に留まる
+        model.move_detail_cursor_down(6);
+        update(&mut model, Message::ToggleThinking);
+        assert!(model.show_thinking);
+        assert_eq!(model.detail_total_lines(), 11);
+        assert_eq!(model.detail_cursor_line(), Some(6));
+        let entry = model.detail_entry_for_cursor().unwrap();
+        assert!(entry.thinking_text().is_some());
+
+        update(&mut model, Message::StartExport);
+        assert!(model.export_include_thinking);
+        update(&mut model, Message::ToggleExportThinking);
+        assert!(!model.export_include_thinking);
+    }
+
+    #[test]
+    fn test_update_help_scroll() {
+        let mut model = create_test_model();
+        model.help_max_scroll = 2;
+
+        update(&mut model, Message::ShowHelp);
+        update(&mut model, Message::HelpScrollUp);
+        assert_eq!(model.help_scroll, 0);
+        for _ in 0..5 {
+            update(&mut model, Message::HelpScrollDown);
+        }
+        assert_eq!(model.help_scroll, 2);
+
+        // 開き直すと先頭から
+        update(&mut model, Message::CloseHelp);
+        update(&mut model, Message::ShowHelp);
+        assert_eq!(model.help_scroll, 0);
+    }
+
+    #[test]
+    fn test_update_filter_date_inputs() {
+        let mut model = create_test_model();
+        update(&mut model, Message::StartFilter);
+        update(&mut model, Message::FilterDatePresetNext);
+        assert_eq!(model.date_preset_index, 1);
+
+        // From 欄では文字がそのまま入力される
+        update(&mut model, Message::FilterNextField);
+        assert!(model.is_typing());
+        for c in "-3x".chars() {
+            update(&mut model, Message::FilterInput(c));
+        }
+        assert_eq!(model.filter_from_input, "-3x");
+        assert!(model.filter_error.is_some());
+
+        // 不正な入力では適用せずパネルに留まる
+        update(&mut model, Message::ApplyFilter);
+        assert_eq!(model.view_mode, ViewMode::Filter);
+
+        update(&mut model, Message::FilterBackspace);
+        update(&mut model, Message::FilterInput('d'));
+        assert!(model.filter_error.is_none());
+        // 日付範囲のフィールド以外ではプリセットは変わらない
+        update(&mut model, Message::FilterDatePresetNext);
+        assert_eq!(model.date_preset_index, 1);
+
+        update(&mut model, Message::ApplyFilter);
+        assert_eq!(model.view_mode, ViewMode::SessionList);
+        let from = model.filter_criteria.date_range.from.unwrap();
+        assert!(from < Utc::now() - chrono::Duration::days(2));
+        assert!(model.filter_criteria.date_range.to.is_none());
+        // テスト用セッションはすべて現在時刻
+        assert_eq!(model.filtered_indices.len(), 5);
+    }
+
+    #[test]
+    fn test_update_filter_source_and_metadata() {
+        let mut model = create_test_model();
+        model.sessions[4].source = SessionSource::Codex;
+        update(&mut model, Message::StartFilter);
+        for _ in 0..4 {
+            update(&mut model, Message::FilterNextField);
+        }
+        assert_eq!(model.filter_field, FilterField::Source);
+        assert!(!model.is_typing());
+
+        // j / k で All → Claude → Codex を切り替える
+        update(&mut model, Message::FilterDatePresetPrev);
+        assert_eq!(model.filter_source, Some(SessionSource::Codex));
+        assert_eq!(model.date_preset_index, 0);
+        update(&mut model, Message::ApplyFilter);
+        assert_eq!(model.filtered_indices, vec![4]);
+
+        // 集計メタデータがなければモデル名の条件にはマッチしない
+        update(&mut model, Message::StartFilter);
+        assert_eq!(model.filter_source, Some(SessionSource::Codex));
+        update(&mut model, Message::FilterDatePresetNext);
+        update(&mut model, Message::FilterNextField);
+        assert!(model.is_typing());
+        update(&mut model, Message::FilterInput('g'));
+        update(&mut model, Message::ApplyFilter);
+        assert_eq!(model.filter_criteria.source, None);
+        assert_eq!(model.filter_criteria.model.as_deref(), Some("g"));
+        assert!(model.filtered_indices.is_empty());
+
+        model.session_stats.insert(
+            (SessionSource::Claude, None, "session-1".to_string()),
+            crate::domain::SessionStats {
+                models: vec!["gpt-5".to_string()],
+                ..Default::default()
+            },
+        );
+        update(&mut model, Message::ApplyFilter);
+        assert_eq!(model.filtered_indices, vec![1]);
+
+        update(&mut model, Message::ClearFilter);
+        assert!(model.filter_model_input.is_empty());
+        assert!(!model.filter_criteria.is_set());
+    }
+
+    #[test]
+    fn test_update_filter_usage_thresholds() {
+        let mut model = create_test_model();
+        model.session_stats.insert(
+            (SessionSource::Claude, None, "session-2".to_string()),
+            crate::domain::SessionStats {
+                message_count: 30,
+                total_tokens: 500_000,
+                cost_usd: Some(4.0),
+                ..Default::default()
+            },
+        );
+        update(&mut model, Message::StartFilter);
+        while model.filter_field != FilterField::Cost {
+            update(&mut model, Message::FilterNextField);
+        }
+        for c in ">x".chars() {
+            update(&mut model, Message::FilterInput(c));
+        }
+        assert_eq!(
+            model.filter_error.as_deref(),
+            Some("Cost: invalid threshold '>x'")
+        );
+        update(&mut model, Message::ApplyFilter);
+        assert_eq!(model.view_mode, ViewMode::Filter);
+
+        update(&mut model, Message::FilterBackspace);
+        update(&mut model, Message::FilterInput('1'));
+        assert!(model.filter_error.is_none());
+        update(&mut model, Message::FilterNextField);
+        for c in "100k".chars() {
+            update(&mut model, Message::FilterInput(c));
+        }
+        update(&mut model, Message::ApplyFilter);
+        assert_eq!(model.view_mode, ViewMode::SessionList);
+        assert_eq!(model.filtered_indices, vec![2]);
+
+        // 再表示時は適用中の条件が入力欄に戻る
+        update(&mut model, Message::StartFilter);
+        assert_eq!(model.filter_cost_input, ">1");
+        assert_eq!(model.filter_tokens_input, ">=100000");
+    }
+
+    #[test]
+    fn test_update_cycle_sort() {
+        let groups: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| crate::tea::ProjectGroup {
+                project_path: format!("/{}", name),
+                project_name: name.to_string(),
+                sessions: vec![SessionListItem {
+                    session_id: name.to_string(),
+                    source: SessionSource::Claude,
+                    root: None,
+                    project_name: name.to_string(),
+                    project_path: format!("/{}", name),
+                    latest_user_message: String::new(),
+                    first_user_message: String::new(),
+                    formatted_time: String::new(),
+                    datetime: Utc::now() - chrono::Duration::days(i as i64),
+                }],
+            })
+            .collect();
+        let mut model = Model::new().with_project_groups(groups);
+        for (name, messages) in [("a", 1), ("b", 30), ("c", 5)] {
+            model.session_stats.insert(
+                (SessionSource::Claude, None, name.to_string()),
+                crate::domain::SessionStats {
+                    message_count: messages,
+                    ..Default::default()
+                },
+            );
+        }
+        let projects = |model: &Model| -> Vec<String> {
+            model
+                .tree_items
+                .iter()
+                .filter(|item| item.kind == TreeNodeKind::Project)
+                .map(|item| item.project_name.clone())
+                .collect()
+        };
+        assert_eq!(projects(&model), vec!["a", "b", "c"]);
+        model.selected_index = 1;
+
+        update(&mut model, Message::CycleSort);
+        assert_eq!(model.sort_mode, crate::search::SortMode::Oldest);
+        assert_eq!(projects(&model), vec!["c", "b", "a"]);
+        update(&mut model, Message::CycleSort);
+        assert_eq!(model.sort_mode, crate::search::SortMode::Messages);
+        assert_eq!(projects(&model), vec!["b", "c", "a"]);
+        // 選択中のプロジェクトは並べ替え後も選択されたまま
+        assert_eq!(model.selected_index, 0);
+        assert_eq!(model.sessions[0].session_id, "b");
+
+        // 絞り込み中も並び順に従う
+        model.filter_messages_input = "<10".to_string();
+        model.apply_filter();
+        assert_eq!(projects(&model), vec!["c", "a"]);
+        update(&mut model, Message::CycleSort);
+        update(&mut model, Message::CycleSort);
+        update(&mut model, Message::CycleSort);
+        assert_eq!(model.sort_mode, crate::search::SortMode::Duration);
+        update(&mut model, Message::CycleSort);
+        assert_eq!(projects(&model), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_update_toggle_list_mode_keeps_selection() {
+        let session = |project: &str, id: &str, days: i64| SessionListItem {
+            session_id: id.to_string(),
+            source: SessionSource::Claude,
+            root: None,
+            project_name: project.to_string(),
+            project_path: format!("/{}", project),
+            latest_user_message: String::new(),
+            first_user_message: String::new(),
+            formatted_time: String::new(),
+            datetime: Utc::now() - chrono::Duration::days(days),
+        };
+        let groups = vec![
+            crate::tea::ProjectGroup {
+                project_path: "/a".to_string(),
+                project_name: "a".to_string(),
+                sessions: vec![session("a", "a-1", 0), session("a", "a-2", 2)],
+            },
+            crate::tea::ProjectGroup {
+                project_path: "/b".to_string(),
+                project_name: "b".to_string(),
+                sessions: vec![session("b", "b-1", 1)],
+            },
+        ];
+        let mut model = Model::new().with_project_groups(groups);
+        let ids = |model: &Model| -> Vec<String> {
+            model
+                .tree_items
+                .iter()
+                .filter_map(|item| item.session.as_ref())
+                .map(|s| s.session_id.clone())
+                .collect()
+        };
+
+        // プロジェクト選択中はそのプロジェクトの先頭セッションを選ぶ
+        model.selected_index = 1;
+        update(&mut model, Message::ToggleListMode);
+        assert_eq!(model.list_mode, crate::tea::ListMode::Flat);
+        assert_eq!(ids(&model), vec!["a-1", "b-1", "a-2"]);
+        assert_eq!(model.selected_session().unwrap().session_id, "b-1");
+
+        // ツリーに戻ると親プロジェクトを展開して同じセッションを選ぶ
+        update(&mut model, Message::MoveDown);
+        update(&mut model, Message::ToggleListMode);
+        assert_eq!(model.list_mode, crate::tea::ListMode::Tree);
+        assert!(model.expanded_projects.contains("/a"));
+        assert_eq!(model.selected_session().unwrap().session_id, "a-2");
+        assert!(model.preview_session.is_some());
+    }
+
+    #[test]
+    fn test_update_cycle_currency() {
+        use crate::domain::Currency;
+
+        let mut model = Model::new();
+        model.currencies = vec![Currency::USD, Currency::EUR, Currency::JPY];
+        update(&mut model, Message::ToggleCurrency);
+        assert_eq!(model.currency, Currency::EUR);
+        update(&mut model, Message::ToggleCurrency);
+        assert_eq!(model.currency, Currency::JPY);
+        update(&mut model, Message::ToggleCurrency);
+        assert_eq!(model.currency, Currency::USD);
+
+        // 一覧にない通貨からは先頭に戻る
+        model.currency = Currency::GBP;
+        update(&mut model, Message::ToggleCurrency);
+        assert_eq!(model.currency, Currency::USD);
+    }
+
+    #[test]
+    fn test_update_dashboard_discards_stale_report() {
+        use crate::domain::UsageReport;
+        use std::sync::Arc;
+
+        let mut model = create_test_model();
+        update(&mut model, Message::ShowDashboard);
+        assert_eq!(model.view_mode, ViewMode::Dashboard);
+        assert!(model.dashboard_pending);
+        let stale = model.dashboard_generation;
+
+        // 集計中に開き直した場合、前回の集計結果は使わない
+        update(&mut model, Message::CloseDashboard);
+        update(&mut model, Message::ShowDashboard);
+        update(
+            &mut model,
+            Message::DashboardReady {
+                generation: stale,
+                report: Arc::new(UsageReport::new()),
+            },
+        );
+        assert!(model.dashboard_report.is_none());
+        assert!(model.dashboard_pending);
+
+        let generation = model.dashboard_generation;
+        update(
+            &mut model,
+            Message::DashboardReady {
+                generation,
+                report: Arc::new(UsageReport::new()),
+            },
+        );
+        assert!(model.dashboard_report.is_some());
+        assert!(!model.dashboard_pending);
+
+        update(&mut model, Message::ToggleDashboardMetric);
+        assert_eq!(model.dashboard_metric, crate::tea::DashboardMetric::Tokens);
+        update(&mut model, Message::CloseDashboard);
+        assert_eq!(model.view_mode, ViewMode::SessionList);
+    }
 }
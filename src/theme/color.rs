@@ -0,0 +1,212 @@
+use std::env;
+use std::str::FromStr;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+/// 端末が表示できる色数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub enum ColorSupport {
+    /// 24bit カラー
+    #[default]
+    TrueColor,
+    /// 256 色
+    Ansi256,
+    /// 16 色
+    Ansi16,
+}
+
+impl FromStr for ColorSupport {
+    type Err = String;
+
+    /// "truecolor" / "256" / "16"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "truecolor" | "24bit" => Ok(Self::TrueColor),
+            "256" => Ok(Self::Ansi256),
+            "16" => Ok(Self::Ansi16),
+            _ => Err(format!(
+                "invalid color support '{}' (expected truecolor, 256 or 16)",
+                s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for ColorSupport {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl ColorSupport {
+    /// 環境変数（COLORTERM / TERM など）から推定
+    pub fn detect() -> Self {
+        Self::detect_with(|key| env::var(key).ok())
+    }
+
+    fn detect_with(env: impl Fn(&str) -> Option<String>) -> Self {
+        let colorterm = env("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return Self::TrueColor;
+        }
+        // COLORTERM を設定しない 24bit 対応端末
+        if env("WT_SESSION").is_some()
+            || matches!(
+                env("TERM_PROGRAM").as_deref(),
+                Some("iTerm.app" | "WezTerm" | "vscode")
+            )
+        {
+            return Self::TrueColor;
+        }
+        let term = env("TERM").unwrap_or_default();
+        if term.ends_with("-direct") {
+            Self::TrueColor
+        } else if term.contains("256color") {
+            Self::Ansi256
+        } else {
+            Self::Ansi16
+        }
+    }
+
+    /// 表示できる色に変換（近い色を選ぶ）
+    pub fn downsample(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::TrueColor, _) => color,
+            (Self::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (Self::Ansi256, _) => color,
+            (Self::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+            (Self::Ansi16, Color::Indexed(index)) => {
+                let (r, g, b) = indexed_rgb(index);
+                nearest_16(r, g, b)
+            }
+            (Self::Ansi16, _) => color,
+        }
+    }
+}
+
+/// 16 色の名前と xterm での RGB 値
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0x00, 0x00, 0x00)),
+    (Color::Red, (0xCD, 0x00, 0x00)),
+    (Color::Green, (0x00, 0xCD, 0x00)),
+    (Color::Yellow, (0xCD, 0xCD, 0x00)),
+    (Color::Blue, (0x00, 0x00, 0xEE)),
+    (Color::Magenta, (0xCD, 0x00, 0xCD)),
+    (Color::Cyan, (0x00, 0xCD, 0xCD)),
+    (Color::Gray, (0xE5, 0xE5, 0xE5)),
+    (Color::DarkGray, (0x7F, 0x7F, 0x7F)),
+    (Color::LightRed, (0xFF, 0x00, 0x00)),
+    (Color::LightGreen, (0x00, 0xFF, 0x00)),
+    (Color::LightYellow, (0xFF, 0xFF, 0x00)),
+    (Color::LightBlue, (0x5C, 0x5C, 0xFF)),
+    (Color::LightMagenta, (0xFF, 0x00, 0xFF)),
+    (Color::LightCyan, (0x00, 0xFF, 0xFF)),
+    (Color::White, (0xFF, 0xFF, 0xFF)),
+];
+
+/// 256 色パレットの 6x6x6 キューブの各段階
+const CUBE_LEVELS: [u8; 6] = [0x00, 0x5F, 0x87, 0xAF, 0xD7, 0xFF];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI_16
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| {
+        (0..6)
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - v as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (16 + 36 * ri + 6 * gi + bi) as u8;
+
+    // グレースケール（232-255: 8, 18, ..., 238）
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 232 + gray_step;
+
+    if distance(indexed_rgb(gray), (r, g, b)) < distance(indexed_rgb(cube), (r, g, b)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+/// 256 色パレットの番号を RGB に変換
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI_16[index as usize].1,
+        16..=231 => {
+            let i = (index - 16) as usize;
+            (
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[(i / 6) % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        }
+        _ => {
+            let v = 8 + (index - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_detect_color_support() {
+        let detect = |vars: &[(&str, &str)]| {
+            let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+            ColorSupport::detect_with(|key| vars.get(key).map(|v| v.to_string()))
+        };
+        assert_eq!(
+            detect(&[("COLORTERM", "truecolor"), ("TERM", "xterm")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(
+            detect(&[("TERM_PROGRAM", "WezTerm")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), ColorSupport::Ansi256);
+        assert_eq!(detect(&[("TERM", "linux")]), ColorSupport::Ansi16);
+        assert_eq!(detect(&[]), ColorSupport::Ansi16);
+    }
+
+    #[test]
+    fn test_downsample_colors() {
+        let rgb = Color::Rgb(0x5C, 0xC8, 0xFF);
+        assert_eq!(ColorSupport::TrueColor.downsample(rgb), rgb);
+        assert_eq!(ColorSupport::Ansi256.downsample(rgb), Color::Indexed(81));
+        assert_eq!(ColorSupport::Ansi16.downsample(rgb), Color::Cyan);
+
+        // 暗い灰色はグレースケールに寄せる
+        assert_eq!(
+            ColorSupport::Ansi256.downsample(Color::Rgb(0x15, 0x1D, 0x27)),
+            Color::Indexed(234)
+        );
+        assert_eq!(
+            ColorSupport::Ansi16.downsample(Color::Indexed(196)),
+            Color::LightRed
+        );
+        // 名前付きの色はそのまま
+        assert_eq!(ColorSupport::Ansi16.downsample(Color::Reset), Color::Reset);
+        assert_eq!("256".parse(), Ok(ColorSupport::Ansi256));
+        assert!("88".parse::<ColorSupport>().is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::theme::{NamedTheme, PALETTE_FIELDS, Theme};
+
+/// テーマファイル（themes/<name>.toml）の内容
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    /// 指定しなかった色を引き継ぐ同梱テーマ
+    #[serde(default = "default_base")]
+    base: String,
+    /// Palette のフィールド名 → 色（"#RRGGBB"、ANSI の色名、0-255 の番号）
+    #[serde(default)]
+    colors: BTreeMap<String, String>,
+}
+
+fn default_base() -> String {
+    "dark".to_string()
+}
+
+/// テーマファイルの TOML を解析
+pub fn parse_theme(text: &str) -> Result<Theme, String> {
+    let file: ThemeFile = toml::from_str(text).map_err(|e| e.message().to_string())?;
+    let mut theme = Theme::from_name(&file.base)
+        .ok_or_else(|| format!("unknown base theme '{}'", file.base))?;
+
+    for (field, value) in &file.colors {
+        let color: Color = value
+            .parse()
+            .map_err(|_| format!("colors.{}: invalid color '{}'", field, value))?;
+        if !theme.palette.set(field, color) {
+            return Err(format!(
+                "colors.{}: unknown field (expected one of {})",
+                field,
+                PALETTE_FIELDS.join(", ")
+            ));
+        }
+    }
+    Ok(theme)
+}
+
+/// ディレクトリ内の *.toml をテーマとして読み込み（ファイル名がテーマ名）
+pub fn load_themes(dir: &Path) -> Result<Vec<NamedTheme>, String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    let mut themes = Vec::new();
+    for path in paths {
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if Theme::from_name(name).is_some() {
+            return Err(format!(
+                "{}: '{}' is a built-in theme name",
+                path.display(),
+                name
+            ));
+        }
+        let theme = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| parse_theme(&text))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        themes.push(NamedTheme::new(name, theme));
+    }
+    Ok(themes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::ThemeMode;
+
+    #[test]
+    fn test_parse_theme() {
+        let theme = parse_theme(
+            r##"
+base = "light"
+
+[colors]
+accent = "#FF8800"
+error = "bright red"
+border = "244"
+"##,
+        )
+        .unwrap();
+        assert_eq!(theme.mode, ThemeMode::Light);
+        assert_eq!(theme.palette.accent, Color::Rgb(0xFF, 0x88, 0x00));
+        assert_eq!(theme.palette.error, Color::LightRed);
+        assert_eq!(theme.palette.border, Color::Indexed(244));
+        assert_eq!(theme.palette.bg, Theme::light().palette.bg);
+
+        for text in [
+            "base = \"neon\"",
+            "[colors]\naccent = \"#12345\"",
+            "[colors]\nbackground = \"red\"",
+            "name = \"mine\"",
+        ] {
+            assert!(parse_theme(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_load_themes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ocean.toml"), "[colors]\nbg = \"#001020\"").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let themes = load_themes(dir.path()).unwrap();
+        assert_eq!(themes.len(), 1);
+        assert_eq!(themes[0].name, "ocean");
+        assert_eq!(themes[0].theme.palette.bg, Color::Rgb(0x00, 0x10, 0x20));
+
+        std::fs::write(dir.path().join("dark.toml"), "").unwrap();
+        assert!(load_themes(dir.path()).is_err());
+        assert!(load_themes(&dir.path().join("missing")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,421 @@
+mod color;
+mod file;
+
+use ratatui::style::Color;
+
+pub use color::ColorSupport;
+pub use file::{load_themes, parse_theme};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThemeMode {
+    Dark,
+    Light,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Palette {
+    pub bg: Color,
+    pub surface: Color,
+    pub text: Color,
+    pub text_muted: Color,
+    pub text_dim: Color,
+    pub border: Color,
+    pub accent: Color,
+    pub accent_alt: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    pub selection_bg: Color,
+    pub selection_fg: Color,
+    pub badge_bg: Color,
+    pub badge_fg: Color,
+    pub input_bg: Color,
+    pub input_fg: Color,
+    pub cursor: Color,
+}
+
+/// 設定ファイルで指定できる Palette のフィールド名
+pub const PALETTE_FIELDS: [&str; 18] = [
+    "bg",
+    "surface",
+    "text",
+    "text_muted",
+    "text_dim",
+    "border",
+    "accent",
+    "accent_alt",
+    "success",
+    "warning",
+    "error",
+    "selection_bg",
+    "selection_fg",
+    "badge_bg",
+    "badge_fg",
+    "input_bg",
+    "input_fg",
+    "cursor",
+];
+
+impl Palette {
+    /// フィールド名で色を変更（未知の名前は false）
+    pub fn set(&mut self, field: &str, color: Color) -> bool {
+        let slot = match field {
+            "bg" => &mut self.bg,
+            "surface" => &mut self.surface,
+            "text" => &mut self.text,
+            "text_muted" => &mut self.text_muted,
+            "text_dim" => &mut self.text_dim,
+            "border" => &mut self.border,
+            "accent" => &mut self.accent,
+            "accent_alt" => &mut self.accent_alt,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "selection_bg" => &mut self.selection_bg,
+            "selection_fg" => &mut self.selection_fg,
+            "badge_bg" => &mut self.badge_bg,
+            "badge_fg" => &mut self.badge_fg,
+            "input_bg" => &mut self.input_bg,
+            "input_fg" => &mut self.input_fg,
+            "cursor" => &mut self.cursor,
+            _ => return false,
+        };
+        *slot = color;
+        true
+    }
+
+    /// すべての色を変換
+    fn map(self, f: impl Fn(Color) -> Color) -> Self {
+        Self {
+            bg: f(self.bg),
+            surface: f(self.surface),
+            text: f(self.text),
+            text_muted: f(self.text_muted),
+            text_dim: f(self.text_dim),
+            border: f(self.border),
+            accent: f(self.accent),
+            accent_alt: f(self.accent_alt),
+            success: f(self.success),
+            warning: f(self.warning),
+            error: f(self.error),
+            selection_bg: f(self.selection_bg),
+            selection_fg: f(self.selection_fg),
+            badge_bg: f(self.badge_bg),
+            badge_fg: f(self.badge_fg),
+            input_bg: f(self.input_bg),
+            input_fg: f(self.input_fg),
+            cursor: f(self.cursor),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Theme {
+    pub mode: ThemeMode,
+    pub palette: Palette,
+    /// 端末の色数（パレット以外の色もこれに合わせて変換する）
+    pub colors: ColorSupport,
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            mode: ThemeMode::Dark,
+            palette: Palette {
+                bg: Color::Rgb(0x0B, 0x11, 0x18),
+                surface: Color::Rgb(0x15, 0x1D, 0x27),
+                text: Color::Rgb(0xE6, 0xED, 0xF3),
+                text_muted: Color::Rgb(0xB7, 0xC0, 0xCC),
+                text_dim: Color::Rgb(0x8A, 0x94, 0xA6),
+                border: Color::Rgb(0x2B, 0x35, 0x45),
+                accent: Color::Rgb(0x5C, 0xC8, 0xFF),
+                accent_alt: Color::Rgb(0x8F, 0xA8, 0xFF),
+                success: Color::Rgb(0x6B, 0xD2, 0x8A),
+                warning: Color::Rgb(0xF5, 0xC8, 0x6A),
+                error: Color::Rgb(0xFF, 0x8A, 0x8A),
+                selection_bg: Color::Rgb(0x27, 0x4A, 0x7A),
+                selection_fg: Color::Rgb(0xF8, 0xFA, 0xFF),
+                badge_bg: Color::Rgb(0x1F, 0x2A, 0x37),
+                badge_fg: Color::Rgb(0xE6, 0xED, 0xF3),
+                input_bg: Color::Rgb(0x1A, 0x23, 0x30),
+                input_fg: Color::Rgb(0xF6, 0xF8, 0xFA),
+                cursor: Color::Rgb(0x5C, 0xC8, 0xFF),
+            },
+            colors: ColorSupport::TrueColor,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            mode: ThemeMode::Light,
+            palette: Palette {
+                bg: Color::Rgb(0xF8, 0xFA, 0xFC),
+                surface: Color::Rgb(0xEE, 0xF2, 0xF7),
+                text: Color::Rgb(0x0F, 0x17, 0x2A),
+                text_muted: Color::Rgb(0x47, 0x55, 0x69),
+                text_dim: Color::Rgb(0x64, 0x74, 0x8B),
+                border: Color::Rgb(0xCB, 0xD5, 0xE1),
+                accent: Color::Rgb(0x0B, 0x76, 0xD1),
+                accent_alt: Color::Rgb(0x25, 0x63, 0xEB),
+                success: Color::Rgb(0x15, 0x80, 0x3D),
+                warning: Color::Rgb(0xB4, 0x53, 0x09),
+                error: Color::Rgb(0xB4, 0x23, 0x18),
+                selection_bg: Color::Rgb(0xD6, 0xE4, 0xFF),
+                selection_fg: Color::Rgb(0x0B, 0x1B, 0x2B),
+                badge_bg: Color::Rgb(0xDC, 0xE7, 0xF3),
+                badge_fg: Color::Rgb(0x0F, 0x17, 0x2A),
+                input_bg: Color::Rgb(0xE2, 0xE8, 0xF0),
+                input_fg: Color::Rgb(0x0F, 0x17, 0x2A),
+                cursor: Color::Rgb(0x0B, 0x76, 0xD1),
+            },
+            colors: ColorSupport::TrueColor,
+        }
+    }
+
+    /// Solarized（dark）
+    pub fn solarized() -> Self {
+        Self {
+            mode: ThemeMode::Dark,
+            palette: Palette {
+                bg: Color::Rgb(0x00, 0x2B, 0x36),
+                surface: Color::Rgb(0x07, 0x36, 0x42),
+                text: Color::Rgb(0x93, 0xA1, 0xA1),
+                text_muted: Color::Rgb(0x83, 0x94, 0x96),
+                text_dim: Color::Rgb(0x65, 0x7B, 0x83),
+                border: Color::Rgb(0x58, 0x6E, 0x75),
+                accent: Color::Rgb(0x26, 0x8B, 0xD2),
+                accent_alt: Color::Rgb(0x6C, 0x71, 0xC4),
+                success: Color::Rgb(0x85, 0x99, 0x00),
+                warning: Color::Rgb(0xB5, 0x89, 0x00),
+                error: Color::Rgb(0xDC, 0x32, 0x2F),
+                selection_bg: Color::Rgb(0x17, 0x4C, 0x5E),
+                selection_fg: Color::Rgb(0xEE, 0xE8, 0xD5),
+                badge_bg: Color::Rgb(0x07, 0x36, 0x42),
+                badge_fg: Color::Rgb(0x93, 0xA1, 0xA1),
+                input_bg: Color::Rgb(0x07, 0x36, 0x42),
+                input_fg: Color::Rgb(0xEE, 0xE8, 0xD5),
+                cursor: Color::Rgb(0x2A, 0xA1, 0x98),
+            },
+            colors: ColorSupport::TrueColor,
+        }
+    }
+
+    /// 黒背景に原色のみの高コントラスト
+    pub fn high_contrast() -> Self {
+        Self {
+            mode: ThemeMode::Dark,
+            palette: Palette {
+                bg: Color::Rgb(0x00, 0x00, 0x00),
+                surface: Color::Rgb(0x00, 0x00, 0x00),
+                text: Color::Rgb(0xFF, 0xFF, 0xFF),
+                text_muted: Color::Rgb(0xF0, 0xF0, 0xF0),
+                text_dim: Color::Rgb(0xD0, 0xD0, 0xD0),
+                border: Color::Rgb(0xFF, 0xFF, 0xFF),
+                accent: Color::Rgb(0x00, 0xFF, 0xFF),
+                accent_alt: Color::Rgb(0xFF, 0xFF, 0x00),
+                success: Color::Rgb(0x00, 0xFF, 0x00),
+                warning: Color::Rgb(0xFF, 0xFF, 0x00),
+                error: Color::Rgb(0xFF, 0x55, 0x55),
+                selection_bg: Color::Rgb(0xFF, 0xFF, 0xFF),
+                selection_fg: Color::Rgb(0x00, 0x00, 0x00),
+                badge_bg: Color::Rgb(0xFF, 0xFF, 0xFF),
+                badge_fg: Color::Rgb(0x00, 0x00, 0x00),
+                input_bg: Color::Rgb(0x00, 0x00, 0x00),
+                input_fg: Color::Rgb(0xFF, 0xFF, 0xFF),
+                cursor: Color::Rgb(0xFF, 0xFF, 0x00),
+            },
+            colors: ColorSupport::TrueColor,
+        }
+    }
+
+    /// 端末の 16 色のみ（背景は端末の既定色）
+    pub fn ansi() -> Self {
+        Self {
+            mode: ThemeMode::Dark,
+            palette: Palette {
+                bg: Color::Reset,
+                surface: Color::Reset,
+                text: Color::Reset,
+                text_muted: Color::Gray,
+                text_dim: Color::DarkGray,
+                border: Color::DarkGray,
+                accent: Color::Cyan,
+                accent_alt: Color::Blue,
+                success: Color::Green,
+                warning: Color::Yellow,
+                error: Color::Red,
+                selection_bg: Color::Blue,
+                selection_fg: Color::White,
+                badge_bg: Color::DarkGray,
+                badge_fg: Color::White,
+                input_bg: Color::Reset,
+                input_fg: Color::Reset,
+                cursor: Color::Cyan,
+            },
+            colors: ColorSupport::TrueColor,
+        }
+    }
+
+    /// 同梱のテーマ一覧
+    pub fn presets() -> Vec<NamedTheme> {
+        [
+            ("dark", Self::dark()),
+            ("light", Self::light()),
+            ("solarized", Self::solarized()),
+            ("high-contrast", Self::high_contrast()),
+            ("ansi", Self::ansi()),
+        ]
+        .into_iter()
+        .map(|(name, theme)| NamedTheme::new(name, theme))
+        .collect()
+    }
+
+    /// 名前から同梱のテーマを取得
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        Self::presets()
+            .into_iter()
+            .find(|preset| preset.name == name)
+            .map(|preset| preset.theme)
+    }
+
+    /// 端末の色数に合わせて変換
+    pub fn with_colors(self, colors: ColorSupport) -> Self {
+        Self {
+            palette: self.palette.map(|color| colors.downsample(color)),
+            colors,
+            ..self
+        }
+    }
+
+    /// パレット以外の色（シンタックスハイライトなど）を端末の色数に合わせる
+    pub fn color(&self, color: Color) -> Color {
+        self.colors.downsample(color)
+    }
+}
+
+/// 名前付きのテーマ
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedTheme {
+    pub name: String,
+    pub theme: Theme,
+}
+
+impl NamedTheme {
+    pub fn new(name: impl Into<String>, theme: Theme) -> Self {
+        Self {
+            name: name.into(),
+            theme,
+        }
+    }
+}
+
+/// テーマ名の特別な値（端末の色数に応じて dark か ansi）
+pub const AUTO_THEME: &str = "auto";
+
+/// Ctrl+t で切り替えるテーマの一覧と現在位置
+#[derive(Debug, Clone)]
+pub struct ThemeCycle {
+    themes: Vec<NamedTheme>,
+    index: usize,
+}
+
+impl Default for ThemeCycle {
+    fn default() -> Self {
+        Self::new(Vec::new(), ColorSupport::TrueColor)
+    }
+}
+
+impl ThemeCycle {
+    /// 同梱のテーマ → ユーザー定義テーマの順（端末の色数に合わせて変換済み）
+    pub fn new(user_themes: Vec<NamedTheme>, colors: ColorSupport) -> Self {
+        let themes = Theme::presets()
+            .into_iter()
+            .chain(user_themes)
+            .map(|named| NamedTheme {
+                theme: named.theme.with_colors(colors),
+                ..named
+            })
+            .collect();
+        let mut cycle = Self { themes, index: 0 };
+        cycle.select(AUTO_THEME);
+        cycle
+    }
+
+    /// 名前で選択（見つからなければ false で現在のまま）
+    pub fn select(&mut self, name: &str) -> bool {
+        let name = if name == AUTO_THEME {
+            match self.themes.first().map(|named| named.theme.colors) {
+                Some(ColorSupport::Ansi16) => "ansi",
+                _ => "dark",
+            }
+        } else {
+            name
+        };
+        match self.themes.iter().position(|named| named.name == name) {
+            Some(index) => {
+                self.index = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 選択中のテーマ
+    pub fn current(&self) -> &NamedTheme {
+        &self.themes[self.index]
+    }
+
+    /// 次のテーマに切り替え
+    pub fn advance(&mut self) -> &NamedTheme {
+        self.index = (self.index + 1) % self.themes.len();
+        self.current()
+    }
+
+    /// テーマ名の一覧
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.themes.iter().map(|named| named.name.as_str())
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_cycle() {
+        let custom = NamedTheme::new("mine", Theme::light());
+        let mut cycle = ThemeCycle::new(vec![custom], ColorSupport::TrueColor);
+        assert_eq!(cycle.current().name, "dark");
+        assert_eq!(
+            cycle.names().collect::<Vec<_>>(),
+            vec![
+                "dark",
+                "light",
+                "solarized",
+                "high-contrast",
+                "ansi",
+                "mine"
+            ]
+        );
+
+        assert!(cycle.select("mine"));
+        assert_eq!(cycle.advance().name, "dark");
+        assert!(!cycle.select("neon"));
+        assert_eq!(cycle.current().name, "dark");
+
+        // 16 色の端末では auto は ansi、ほかのテーマも 16 色に変換
+        let cycle = ThemeCycle::new(Vec::new(), ColorSupport::Ansi16);
+        assert_eq!(cycle.current().name, "ansi");
+        let mut cycle = cycle;
+        let dark = cycle.advance().theme;
+        assert_eq!(dark.colors, ColorSupport::Ansi16);
+        assert!(!matches!(dark.palette.accent, Color::Rgb(..)));
+    }
+}
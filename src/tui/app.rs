@@ -1,39 +1,24 @@
-use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use std::sync::mpsc::{self, Receiver, Sender};
 
-use chrono::{DateTime, TimeZone, Utc};
 use crossterm::{execute, clipboard::CopyToClipboard};
 use ratatui::layout::{Constraint, Layout, Rect};
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
 use crate::KathaError;
-use crate::config::{ClaudePaths, CodexPaths};
-use crate::data::{CodexHistoryReader, CodexSessionInfo, CodexSessionReader, HistoryReader, SessionReader};
+use crate::config::{CachePaths, Config, UiState};
+use crate::data::SessionCatalog;
+use crate::domain::UsageReport;
 use crate::export::{
     ExportFormat, Exporter, JsonExporter, MarkdownExporter, generate_filename, write_to_file,
 };
-use crate::tea::{
-    ExportStatus, Message, Model, ProjectGroup, SessionListItem, SessionSource, TreeNodeKind,
-    ViewMode, update,
+use crate::search::{ContentSearchTarget, ContentSearcher, SearchIndex};
+use crate::tea::{ExportStatus, Message, Model, TreeNodeKind, ViewMode, update};
+use crate::tui::{EventHandler, KeyContext, Keymap, Terminal};
+use crate::views::{
+    help_max_scroll, render_code_picker, render_dashboard, render_export_dialog, render_help,
+    render_session_detail, render_session_list,
 };
-use crate::theme::Theme;
-use crate::tui::{EventHandler, Terminal};
-use crate::views::{render_export_dialog, render_help, render_session_detail, render_session_list};
-
-#[derive(Debug, Clone)]
-struct HistoryItem {
-    session_id: String,
-    project_path: String,
-    display: String,
-    timestamp: i64,
-    source: SessionSource,
-}
-
-fn datetime_from_millis(timestamp_ms: i64) -> DateTime<Utc> {
-    Utc.timestamp_millis_opt(timestamp_ms)
-        .single()
-        .unwrap_or(DateTime::<Utc>::UNIX_EPOCH)
-}
 
 /// アプリケーション
 pub struct App {
@@ -43,12 +28,12 @@ pub struct App {
     terminal: Terminal,
     /// イベントハンドラ
     event_handler: EventHandler,
-    /// Claude パス設定
-    paths: Option<ClaudePaths>,
-    /// Codex パス設定
-    codex_paths: Option<CodexPaths>,
-    /// Codex セッション一覧
-    codex_sessions: HashMap<String, CodexSessionInfo>,
+    /// 設定
+    config: Config,
+    /// 前回の UI 状態
+    state: UiState,
+    /// セッション一覧と読み込み元のパス
+    catalog: SessionCatalog,
     /// 非同期メッセージ送信用
     async_tx: Sender<Message>,
     /// 非同期メッセージ受信用
@@ -57,140 +42,83 @@ pub struct App {
 
 impl App {
     /// 新規作成
-    pub fn new() -> Result<Self, KathaError> {
+    pub fn new(config: Config) -> Result<Self, KathaError> {
         let terminal = Terminal::new()?;
-        let event_handler = EventHandler::new();
-        let mut model = Model::new();
-        model.theme = Theme::from_env();
+        let event_handler = EventHandler::new()
+            .with_timeout(config.ui.poll_interval())
+            .with_keymap(
+                Keymap::from_config(&config.keybindings).map_err(KathaError::ConfigError)?,
+            );
+        let state = Self::load_state();
+        let model = Self::initial_model(&config, &state, event_handler.keymap());
         let (async_tx, async_rx) = mpsc::channel();
 
         Ok(Self {
             model,
             terminal,
             event_handler,
-            paths: None,
-            codex_paths: None,
-            codex_sessions: HashMap::new(),
+            config,
+            state,
+            catalog: SessionCatalog::default(),
             async_tx,
             async_rx,
         })
     }
 
-    /// セッションを読み込み
-    pub fn load_sessions(&mut self) -> Result<(), KathaError> {
-        let paths = ClaudePaths::new()?;
-        let mut history_items: HashMap<String, Vec<HistoryItem>> = HashMap::new();
-
-        if paths.history_exists() {
-            let entries = HistoryReader::read_all(&paths.history_file)?;
-            for entry in entries {
-                // is_valid() でフィルタ済みなので session_id は常に存在
-                if let Some(session_id) = entry.session_id.clone() {
-                    let project = entry.project().to_string();
-                    history_items
-                        .entry(project.clone())
-                        .or_insert_with(Vec::new)
-                        .push(HistoryItem {
-                            session_id,
-                            project_path: project,
-                            display: entry.display().to_string(),
-                            timestamp: entry.timestamp.unwrap_or(0),
-                            source: SessionSource::Claude,
-                        });
-                }
-            }
+    /// 前回の UI 状態を読み込み（読めない場合は既定値）
+    fn load_state() -> UiState {
+        CachePaths::new()
+            .and_then(|paths| UiState::load(&paths.state_file))
+            .unwrap_or_else(|e| {
+                warn!("Ignoring saved UI state: {}", e);
+                UiState::default()
+            })
+    }
+
+    /// UI 状態を保存（失敗しても続行）
+    fn save_state(&mut self) {
+        self.state.sort = Some(self.model.sort_mode);
+        self.state.config_sort = Some(self.config.list.sort);
+        let result = CachePaths::new().and_then(|paths| self.state.save(&paths.state_file));
+        if let Err(e) = result {
+            warn!("Failed to save UI state: {}", e);
         }
+    }
 
-        self.paths = Some(paths);
-        self.codex_paths = None;
-        self.codex_sessions.clear();
-
-        if let Ok(codex_paths) = CodexPaths::new() {
-            self.codex_sessions =
-                CodexSessionReader::build_session_index(&codex_paths.sessions_dir)?;
-
-            if codex_paths.history_exists() {
-                let entries = CodexHistoryReader::read_all(&codex_paths.history_file)?;
-                for entry in entries {
-                    // is_valid() でフィルタ済みなので session_id は常に存在
-                    if let Some(session_id) = entry.session_id.clone() {
-                        let project_path = self
-                            .codex_sessions
-                            .get(&session_id)
-                            .and_then(|info| info.cwd.clone())
-                            .unwrap_or_else(|| "Codex".to_string());
-                        history_items
-                            .entry(project_path.clone())
-                            .or_insert_with(Vec::new)
-                            .push(HistoryItem {
-                                session_id,
-                                project_path,
-                                display: entry.text().to_string(),
-                                timestamp: entry.ts() * 1000,
-                                source: SessionSource::Codex,
-                            });
-                    }
-                }
-            }
+    /// 設定と前回の UI 状態、キーバインドを反映した初期状態
+    fn initial_model(config: &Config, state: &UiState, keymap: &Keymap) -> Model {
+        let mut model = Model::new();
+        model.themes = config.theme_cycle();
+        model.theme = model.themes.current().theme;
+        model.list_width_percent = config.ui.list_width_percent;
+        model.currency = config.currency.default;
+        model.currencies = config.currencies();
+        model.exchange_rates = config.exchange_rates.clone();
+        model.date_presets = config.list.date_presets.clone();
+        model.export_format = config.export.format;
+        model.sort_mode = state.sort_mode(config.list.sort);
+        model.tool_output_key = keymap.key_label(KeyContext::Detail, "toggle_tool_output");
+        model.thinking_key = keymap.key_label(KeyContext::Detail, "toggle_thinking");
+        model
+    }
 
-            self.codex_paths = Some(codex_paths);
-        }
+    /// 設定されたデータディレクトリからセッションを読み込み
+    pub fn load_sessions(&mut self) -> Result<(), KathaError> {
+        self.catalog = SessionCatalog::load(&self.config.data)?;
 
-        if history_items.is_empty() {
-            self.model = Model::new();
+        if self.catalog.project_groups.is_empty() {
+            self.model =
+                Self::initial_model(&self.config, &self.state, self.event_handler.keymap());
             update(&mut self.model, Message::Initialized);
             return Ok(());
         }
 
-        // ProjectGroup に変換（全セッションを含む）
-        let mut project_groups: Vec<ProjectGroup> = Vec::new();
-
-        for (project, mut entries) in history_items {
-            entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-            let project_name = project.rsplit('/').next().unwrap_or(&project).to_string();
-
-            // 全エントリを SessionListItem に変換
-            // history.jsonl is newest-first; keep only the latest entry per session.
-            let mut seen_session_keys: HashSet<(SessionSource, String)> = HashSet::new();
-            let sessions: Vec<SessionListItem> = entries
-                .iter()
-                .filter(|entry| {
-                    seen_session_keys.insert((entry.source, entry.session_id.clone()))
-                })
-                .map(|entry| {
-                    let datetime = datetime_from_millis(entry.timestamp);
-                    let formatted_time = datetime.format("%Y-%m-%d %H:%M").to_string();
-
-                    SessionListItem {
-                        session_id: entry.session_id.clone(),
-                        source: entry.source,
-                        project_name: project_name.clone(),
-                        project_path: entry.project_path.clone(),
-                        latest_user_message: entry.display.clone(),
-                        formatted_time,
-                        datetime,
-                    }
-                })
-                .collect();
-
-            if !sessions.is_empty() {
-                project_groups.push(ProjectGroup {
-                    project_path: project.clone(),
-                    project_name,
-                    sessions,
-                });
-            }
-        }
-
-        // 各プロジェクトの最新セッションの時刻でソート（新しい順）
-        project_groups.sort_by(|a, b| {
-            let a_latest = a.sessions.first().map(|s| &s.datetime);
-            let b_latest = b.sessions.first().map(|s| &s.datetime);
-            b_latest.cmp(&a_latest)
-        });
-
-        self.model = Model::new().with_project_groups(project_groups);
+        self.model = Self::initial_model(&self.config, &self.state, self.event_handler.keymap())
+            .with_project_groups(self.catalog.project_groups.clone());
+        self.model.apply_sort();
         update(&mut self.model, Message::Initialized);
+        self.start_index_update();
+        self.start_budget_update();
 
         Ok(())
     }
@@ -216,17 +144,23 @@ impl App {
         };
 
         let format = self.model.export_format;
+        let include_thinking = self.model.export_include_thinking;
+        let export_dir = self.config.export.dir.clone();
         let tx = self.async_tx.clone();
 
         std::thread::spawn(move || {
             let content = match format {
-                ExportFormat::Markdown => MarkdownExporter::new().export(&session),
-                ExportFormat::Json => JsonExporter::new().export(&session),
+                ExportFormat::Markdown => MarkdownExporter::new()
+                    .include_thinking(include_thinking)
+                    .export(&session),
+                ExportFormat::Json => JsonExporter::new()
+                    .include_thinking(include_thinking)
+                    .export(&session),
             };
 
             let filename = generate_filename(&session, format);
 
-            let msg = match write_to_file(&content, &filename, None) {
+            let msg = match write_to_file(&content, &filename, export_dir.as_deref()) {
                 Ok(path) => Message::ExportCompleted(path),
                 Err(e) => Message::ExportFailed(e.to_string()),
             };
@@ -236,6 +170,111 @@ impl App {
         });
     }
 
+    /// 本文検索の対象セッションファイル一覧
+    fn content_search_targets(&self) -> Vec<ContentSearchTarget> {
+        self.catalog.content_search_targets(&self.model.sessions)
+    }
+
+    /// 本文検索を別スレッドで開始
+    fn start_content_search(&self) {
+        let targets = self.content_search_targets();
+        let query = self.model.search_query.clone();
+        let tx = self.async_tx.clone();
+
+        std::thread::spawn(move || {
+            let result = ContentSearcher::search(&targets, &query);
+            // エラーは無視（アプリ終了中の可能性があるため）
+            let _ = tx.send(Message::ContentSearchCompleted(result));
+        });
+    }
+
+    /// 本文検索用インデックスを別スレッドで読み込み・更新
+    fn start_index_update(&self) {
+        let cache_paths = match CachePaths::new() {
+            Ok(paths) => paths,
+            Err(e) => {
+                warn!("Search index disabled: {}", e);
+                return;
+            }
+        };
+        let targets = self.content_search_targets();
+        let tx = self.async_tx.clone();
+
+        std::thread::spawn(move || {
+            let index_file = &cache_paths.search_index_file;
+            let mut index = SearchIndex::load(index_file).unwrap_or_else(|e| {
+                warn!("Rebuilding search index: {}", e);
+                SearchIndex::new()
+            });
+
+            let stats = index.update(&targets);
+            debug!("Search index updated: {:?}", stats);
+            if stats.is_changed()
+                && let Err(e) = index.save(index_file)
+            {
+                warn!("Failed to save search index: {}", e);
+            }
+
+            // エラーは無視（アプリ終了中の可能性があるため）
+            let _ = tx.send(Message::SearchIndexReady(Arc::new(index)));
+        });
+    }
+
+    /// 今月の予算の消化状況を別スレッドで集計（予算未設定なら何もしない）
+    fn start_budget_update(&self) {
+        if self.config.budgets.is_empty() {
+            return;
+        }
+        let budgets = self.config.budgets.clone();
+        let catalog = self.catalog.clone();
+        let tx = self.async_tx.clone();
+
+        std::thread::spawn(move || {
+            let report = catalog.budget_report(&budgets, chrono::Local::now().date_naive());
+            // エラーは無視（アプリ終了中の可能性があるため）
+            let _ = tx.send(Message::BudgetReady(Arc::new(report)));
+        });
+    }
+
+    /// フィルタ後のセッションの使用量とコストを別スレッドで集計
+    /// 日付フィルタがある場合は期間内のエントリのみを集計
+    fn start_dashboard_build(&self) {
+        let sessions: Vec<_> = self
+            .model
+            .filtered_sessions()
+            .into_iter()
+            .cloned()
+            .collect();
+        let catalog = self.catalog.clone();
+        let period = self.model.filter_criteria.date_range.clone();
+        let generation = self.model.dashboard_generation;
+        let tx = self.async_tx.clone();
+
+        std::thread::spawn(move || {
+            let mut report = UsageReport::new();
+            for item in &sessions {
+                match catalog.read_session(item) {
+                    Ok(session) => report.add_session_between(
+                        &item.project_name,
+                        &session,
+                        period.from,
+                        period.to,
+                    ),
+                    Err(e) => {
+                        debug!("Skipping session {} in dashboard: {}", item.session_id, e);
+                        report.failed_sessions += 1;
+                    }
+                }
+            }
+
+            // エラーは無視（アプリ終了中の可能性があるため）
+            let _ = tx.send(Message::DashboardReady {
+                generation,
+                report: Arc::new(report),
+            });
+        });
+    }
+
     /// 選択中のセッションを読み込み
     fn load_current_session(&self) -> Option<Message> {
         // ツリーアイテムからセッション情報を取得
@@ -247,58 +286,9 @@ impl App {
             self.model.selected_session()?
         };
 
-        match selected.source {
-            SessionSource::Claude => {
-                let paths = self.paths.as_ref()?;
-                let session_file = SessionReader::session_file_path(
-                    &paths.projects_dir,
-                    &selected.project_path,
-                    &selected.session_id,
-                );
-
-                if !session_file.exists() {
-                    return Some(Message::SessionLoadFailed(format!(
-                        "Session file not found: {}",
-                        session_file.display()
-                    )));
-                }
-
-                match SessionReader::read_session(
-                    &session_file,
-                    &selected.session_id,
-                    &selected.project_path,
-                ) {
-                    Ok(session) => Some(Message::SessionLoaded(session)),
-                    Err(e) => Some(Message::SessionLoadFailed(e.to_string())),
-                }
-            }
-            SessionSource::Codex => {
-                let info = match self.codex_sessions.get(&selected.session_id) {
-                    Some(info) => info,
-                    None => {
-                        return Some(Message::SessionLoadFailed(format!(
-                            "Codex session not found: {}",
-                            selected.session_id
-                        )));
-                    }
-                };
-
-                if !info.path.exists() {
-                    return Some(Message::SessionLoadFailed(format!(
-                        "Codex session file not found: {}",
-                        info.path.display()
-                    )));
-                }
-
-                match CodexSessionReader::read_session(
-                    &info.path,
-                    &selected.session_id,
-                    &selected.project_path,
-                ) {
-                    Ok(session) => Some(Message::SessionLoaded(session)),
-                    Err(e) => Some(Message::SessionLoadFailed(e.to_string())),
-                }
-            }
+        match self.catalog.read_session(selected) {
+            Ok(session) => Some(Message::SessionLoaded(session)),
+            Err(e) => Some(Message::SessionLoadFailed(e.to_string())),
         }
     }
 
@@ -324,13 +314,17 @@ impl App {
             text
         };
 
-        execute!(
-            std::io::stdout(),
-            CopyToClipboard::to_clipboard_from(content)
-        )
-        .map_err(|e| format!("Clipboard copy failed: {}", e))?;
+        copy_to_clipboard(content)
+    }
 
-        Ok(())
+    fn copy_code_snippet(&self) -> Result<(), String> {
+        let snippet = self
+            .model
+            .code_picker
+            .as_ref()
+            .and_then(|picker| picker.selected_snippet())
+            .ok_or_else(|| "No code block selected".to_string())?;
+        copy_to_clipboard(snippet.text.clone())
     }
 
     fn selected_detail_entry(&self) -> Result<&crate::domain::SessionEntry, String> {
@@ -364,12 +358,21 @@ impl App {
                     .map_err(|e| KathaError::Terminal(e.to_string()))?;
                 Rect::new(0, 0, size.width, size.height)
             };
-            let needs_detail = matches!(view_mode, ViewMode::SessionDetail)
-                || (matches!(view_mode, ViewMode::Help | ViewMode::Export)
-                    && matches!(self.model.previous_view_mode, ViewMode::SessionDetail));
+            let needs_detail =
+                matches!(view_mode, ViewMode::SessionDetail | ViewMode::DetailSearch)
+                    || (matches!(
+                        view_mode,
+                        ViewMode::Help | ViewMode::Export | ViewMode::CodePicker
+                    ) && matches!(self.model.previous_view_mode, ViewMode::SessionDetail));
             if needs_detail {
                 self.update_detail_viewport(area);
             }
+            let help_entries = if view_mode == ViewMode::Help {
+                self.event_handler.keymap().help_entries()
+            } else {
+                Vec::new()
+            };
+            self.model.help_max_scroll = help_max_scroll(area, help_entries.len());
 
             self.terminal
                 .inner()
@@ -377,7 +380,7 @@ impl App {
                     ViewMode::SessionList | ViewMode::Search | ViewMode::Filter => {
                         render_session_list(frame, &self.model);
                     }
-                    ViewMode::SessionDetail => {
+                    ViewMode::SessionDetail | ViewMode::DetailSearch => {
                         render_session_detail(frame, &self.model);
                     }
                     ViewMode::Help => {
@@ -389,9 +392,12 @@ impl App {
                             ViewMode::SessionDetail => {
                                 render_session_detail(frame, &self.model);
                             }
+                            ViewMode::Dashboard => {
+                                render_dashboard(frame, &self.model);
+                            }
                             _ => {}
                         }
-                        render_help(frame, &self.model);
+                        render_help(frame, &self.model, &help_entries);
                     }
                     ViewMode::Export => {
                         // 元のビューを描画してからエクスポートダイアログをオーバーレイ
@@ -406,14 +412,24 @@ impl App {
                         }
                         render_export_dialog(frame, &self.model);
                     }
+                    ViewMode::CodePicker => {
+                        // 詳細画面を描画してからコード片選択をオーバーレイ
+                        render_session_detail(frame, &self.model);
+                        render_code_picker(frame, &self.model);
+                    }
+                    ViewMode::Dashboard => {
+                        render_dashboard(frame, &self.model);
+                    }
                 })
                 .map_err(|e| KathaError::Terminal(e.to_string()))?;
 
             // 3. イベント処理
             trace!("Waiting for event...");
-            let msg = self
-                .event_handler
-                .poll(view_mode, self.model.export_status.as_ref())?;
+            let msg = self.event_handler.poll(
+                view_mode,
+                self.model.export_status.as_ref(),
+                self.model.is_typing(),
+            )?;
             trace!("Event message: {:?}", msg);
 
             // キー入力があったらエラーメッセージをクリア（セッション一覧画面のみ）
@@ -433,10 +449,16 @@ impl App {
                 update(&mut self.model, msg);
 
                 // Session ノードの場合はセッション読み込みを実行
-                if is_session_node {
-                    if let Some(load_msg) = self.load_current_session() {
-                        update(&mut self.model, load_msg);
-                    }
+                if is_session_node
+                    && let Some(load_msg) = self.load_current_session()
+                {
+                    update(&mut self.model, load_msg);
+                }
+            } else if matches!(msg, Message::ConfirmSearch) {
+                update(&mut self.model, msg);
+                // 本文検索が要求された場合は別スレッドで実行
+                if self.model.content_search_pending {
+                    self.start_content_search();
                 }
             } else if matches!(msg, Message::ConfirmExport) {
                 // current_session が存在し、Selecting 状態の場合のみ export を開始
@@ -478,6 +500,22 @@ impl App {
                 if let Err(error) = self.copy_selected_message(with_meta) {
                     update(&mut self.model, Message::ShowError(error));
                 }
+            } else if matches!(msg, Message::ShowDashboard) {
+                update(&mut self.model, msg);
+                // 集計は別スレッドで実行
+                if self.model.dashboard_pending {
+                    self.start_dashboard_build();
+                }
+            } else if matches!(msg, Message::CycleSort) {
+                update(&mut self.model, msg);
+                // 並び順は次回起動時にも使う
+                self.save_state();
+            } else if matches!(msg, Message::CopyCodeSnippet) {
+                let result = self.copy_code_snippet();
+                update(&mut self.model, Message::CloseCodePicker);
+                if let Err(error) = result {
+                    update(&mut self.model, Message::ShowError(error));
+                }
             } else {
                 update(&mut self.model, msg);
             }
@@ -496,3 +534,12 @@ impl App {
         Ok(())
     }
 }
+
+/// OSC52 でクリップボードにコピー
+fn copy_to_clipboard(content: String) -> Result<(), String> {
+    execute!(
+        std::io::stdout(),
+        CopyToClipboard::to_clipboard_from(content)
+    )
+    .map_err(|e| format!("Clipboard copy failed: {}", e))
+}
@@ -4,12 +4,15 @@ use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 
 use crate::KathaError;
 use crate::tea::{ExportStatus, Message, ViewMode};
+use crate::tui::keymap::{KeyContext, Keymap};
 
 /// イベントハンドラ
 /// キーイベントを TEA の Message に変換
 pub struct EventHandler {
     /// ポーリングタイムアウト
     timeout: Duration,
+    /// キーバインド
+    keymap: Keymap,
     /// 詳細画面での連続移動時刻
     last_detail_move: Option<Instant>,
     /// 詳細画面での連続移動方向
@@ -35,17 +38,37 @@ impl EventHandler {
     pub fn new() -> Self {
         Self {
             timeout: Duration::from_millis(100),
+            keymap: Keymap::default(),
             last_detail_move: None,
             last_detail_direction: None,
             detail_move_speed: 1,
         }
     }
 
+    /// 入力待ちのタイムアウトを設定
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// キーバインドを設定
+    pub fn with_keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// 現在のキーバインド
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
     /// イベントをポーリングして Message に変換
+    /// typing が true なら修飾キーなしの文字は割り当てより入力を優先
     pub fn poll(
         &mut self,
         view_mode: ViewMode,
         export_status: Option<&ExportStatus>,
+        typing: bool,
     ) -> Result<Message, KathaError> {
         if view_mode != ViewMode::SessionDetail {
             self.reset_detail_move();
@@ -61,6 +84,9 @@ impl EventHandler {
             && let Event::Key(key) =
                 event::read().map_err(|e| KathaError::Terminal(e.to_string()))?
         {
+            if typing && Self::is_typed_char(key) {
+                return Ok(Self::text_input(key, view_mode));
+            }
             return Ok(self.key_to_message(key, view_mode, export_status));
         }
         Ok(Message::None)
@@ -73,85 +99,29 @@ impl EventHandler {
         view_mode: ViewMode,
         export_status: Option<&ExportStatus>,
     ) -> Message {
-        // Ctrl+C は常に終了
-        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
-            return Message::Quit;
-        }
-        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('t') {
-            return Message::ToggleTheme;
-        }
-
-        match view_mode {
-            ViewMode::SessionList => self.session_list_key(key),
-            ViewMode::SessionDetail => self.session_detail_key(key),
-            ViewMode::Search => self.search_mode_key(key),
-            ViewMode::Filter => self.filter_mode_key(key),
-            ViewMode::Help => self.help_mode_key(key),
-            ViewMode::Export => self.export_mode_key(key, export_status),
-        }
-    }
-
-    /// セッション一覧画面のキーマッピング
-    fn session_list_key(&self, key: KeyEvent) -> Message {
-        match key.code {
-            // 終了
-            KeyCode::Char('q') => Message::Quit,
-            // Esc でフィルタクリア、またはクリア済みなら終了
-            KeyCode::Esc => Message::ClearFilter,
-            // 上に移動
-            KeyCode::Char('k') | KeyCode::Up => Message::MoveUp,
-            // 下に移動
-            KeyCode::Char('j') | KeyCode::Down => Message::MoveDown,
-            // Enter で詳細画面へ（プロジェクトの場合は展開/折りたたみ）
-            KeyCode::Enter => Message::EnterDetail,
-            // l / → で展開
-            KeyCode::Char('l') | KeyCode::Right => Message::ExpandCurrentProject,
-            // h / ← で折りたたみ
-            KeyCode::Char('h') | KeyCode::Left => Message::CollapseCurrentProject,
-            // E ですべて展開
-            KeyCode::Char('E') => Message::ExpandAll,
-            // C ですべて折りたたみ
-            KeyCode::Char('C') => Message::CollapseAll,
-            // 検索モード開始
-            KeyCode::Char('/') => Message::StartSearch,
-            // フィルタモード開始
-            KeyCode::Char('f') => Message::StartFilter,
-            // エクスポートダイアログ表示
-            KeyCode::Char('e') => Message::StartExport,
-            // 通貨切り替え
-            KeyCode::Char('u') => Message::ToggleCurrency,
-            // ヘルプ表示
-            KeyCode::Char('?') => Message::ShowHelp,
-            _ => Message::None,
-        }
-    }
+        let binding = self.keymap.lookup(view_mode, key);
+        let msg = match binding {
+            // エラー状態・Exporting 状態ではキャンセルのみ受け付ける
+            Some(binding)
+                if binding.context == KeyContext::Export
+                    && !matches!(export_status, Some(ExportStatus::Selecting))
+                    && !matches!(binding.action.message(), Message::CancelExport) =>
+            {
+                Message::None
+            }
+            Some(binding) => binding.action.message(),
+            None => Self::text_input(key, view_mode),
+        };
 
-    /// セッション詳細画面のキーマッピング
-    fn session_detail_key(&mut self, key: KeyEvent) -> Message {
-        let msg = match key.code {
-            // Esc または q で一覧に戻る
-            KeyCode::Esc | KeyCode::Char('q') => Message::BackToList,
-            // 上にスクロール
-            KeyCode::Char('k') | KeyCode::Up => {
-                let step = self.detail_move_step(DetailMoveDirection::Up);
-                Message::ScrollUp(step)
+        // 詳細画面では連続スクロールで加速
+        let msg = match msg {
+            Message::ScrollUp(_) if view_mode == ViewMode::SessionDetail => {
+                Message::ScrollUp(self.detail_move_step(DetailMoveDirection::Up))
             }
-            // 下にスクロール
-            KeyCode::Char('j') | KeyCode::Down => {
-                let step = self.detail_move_step(DetailMoveDirection::Down);
-                Message::ScrollDown(step)
+            Message::ScrollDown(_) if view_mode == ViewMode::SessionDetail => {
+                Message::ScrollDown(self.detail_move_step(DetailMoveDirection::Down))
             }
-            // 選択中メッセージをコピー
-            KeyCode::Char('y') => Message::CopySelectedMessage,
-            // 選択中メッセージをメタ情報付きでコピー
-            KeyCode::Char('Y') => Message::CopySelectedMessageWithMeta,
-            // エクスポートダイアログ表示
-            KeyCode::Char('e') => Message::StartExport,
-            // 通貨切り替え
-            KeyCode::Char('u') => Message::ToggleCurrency,
-            // ヘルプ表示
-            KeyCode::Char('?') => Message::ShowHelp,
-            _ => Message::None,
+            msg => msg,
         };
 
         if !matches!(msg, Message::ScrollUp(_) | Message::ScrollDown(_)) {
@@ -161,62 +131,28 @@ impl EventHandler {
         msg
     }
 
-    /// 検索モードのキーマッピング
-    fn search_mode_key(&self, key: KeyEvent) -> Message {
-        match key.code {
-            KeyCode::Esc => Message::CancelSearch,
-            KeyCode::Enter => Message::ConfirmSearch,
-            KeyCode::Backspace => Message::SearchBackspace,
-            KeyCode::Char(c) => Message::SearchInput(c),
-            _ => Message::None,
-        }
-    }
-
-    /// フィルタモードのキーマッピング
-    fn filter_mode_key(&self, key: KeyEvent) -> Message {
-        match key.code {
-            KeyCode::Tab => Message::FilterNextField,
-            KeyCode::Char('j') | KeyCode::Down => Message::FilterDatePresetNext,
-            KeyCode::Char('k') | KeyCode::Up => Message::FilterDatePresetPrev,
-            KeyCode::Enter => Message::ApplyFilter,
-            KeyCode::Esc => Message::CancelFilter,
-            KeyCode::Char('c') => Message::ClearFilter,
-            KeyCode::Backspace => Message::FilterProjectBackspace,
-            KeyCode::Char(c) => Message::FilterProjectInput(c),
-            _ => Message::None,
-        }
-    }
-
-    /// ヘルプモードのキーマッピング
-    fn help_mode_key(&self, key: KeyEvent) -> Message {
-        match key.code {
-            // Esc または q または ? でヘルプを閉じる
-            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => Message::CloseHelp,
-            _ => Message::None,
-        }
+    /// 修飾キーなしの文字入力か
+    fn is_typed_char(key: KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char(_))
+            && !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
     }
 
-    /// エクスポートモードのキーマッピング
-    fn export_mode_key(&self, key: KeyEvent, export_status: Option<&ExportStatus>) -> Message {
-        // エラー状態・Exporting 状態では Esc のみ受け付ける
-        let is_exportable = matches!(export_status, Some(ExportStatus::Selecting));
-
-        match key.code {
-            // Esc でキャンセル（常に有効）
-            KeyCode::Esc | KeyCode::Char('q') => Message::CancelExport,
-            // 以下は Selecting 状態のみ有効
-            // Tab または j/k で形式切り替え
-            KeyCode::Tab
-            | KeyCode::Char('j')
-            | KeyCode::Char('k')
-            | KeyCode::Left
-            | KeyCode::Right
-                if is_exportable =>
-            {
-                Message::ToggleExportFormat
-            }
-            // Enter でエクスポート実行
-            KeyCode::Enter if is_exportable => Message::ConfirmExport,
+    /// キーマップにないキーを入力欄への文字入力として扱う
+    fn text_input(key: KeyEvent, view_mode: ViewMode) -> Message {
+        let typed = match key.code {
+            KeyCode::Char(c) if Self::is_typed_char(key) => Some(c),
+            KeyCode::Backspace => None,
+            _ => return Message::None,
+        };
+        match (view_mode, typed) {
+            (ViewMode::Search, Some(c)) => Message::SearchInput(c),
+            (ViewMode::Search, None) => Message::SearchBackspace,
+            (ViewMode::DetailSearch, Some(c)) => Message::DetailFindInput(c),
+            (ViewMode::DetailSearch, None) => Message::DetailFindBackspace,
+            (ViewMode::Filter, Some(c)) => Message::FilterInput(c),
+            (ViewMode::Filter, None) => Message::FilterBackspace,
             _ => Message::None,
         }
     }
@@ -409,6 +345,94 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_session_detail_toggle_keys() {
+        let mut handler = EventHandler::new();
+
+        let key = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::SessionDetail, None),
+            Message::ToggleToolOutput
+        ));
+
+        let key = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::SessionDetail, None),
+            Message::ToggleThinking
+        ));
+
+        let key = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::Export, Some(&ExportStatus::Selecting)),
+            Message::ToggleExportThinking
+        ));
+    }
+
+    #[test]
+    fn test_code_picker_keys() {
+        let mut handler = EventHandler::new();
+
+        let key = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::SessionDetail, None),
+            Message::OpenCodePicker
+        ));
+
+        let key = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::CodePicker, None),
+            Message::CodePickerNext
+        ));
+
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::CodePicker, None),
+            Message::CopyCodeSnippet
+        ));
+
+        let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::CodePicker, None),
+            Message::CloseCodePicker
+        ));
+    }
+
+    #[test]
+    fn test_session_detail_find_keys() {
+        let mut handler = EventHandler::new();
+
+        let key = KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::SessionDetail, None),
+            Message::StartDetailFind
+        ));
+
+        let key = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::SessionDetail, None),
+            Message::NextDetailMatch
+        ));
+
+        let key = KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::SessionDetail, None),
+            Message::PrevDetailMatch
+        ));
+
+        // 入力中は n もそのまま入力される
+        let key = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::DetailSearch, None),
+            Message::DetailFindInput('n')
+        ));
+
+        let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::DetailSearch, None),
+            Message::CancelDetailFind
+        ));
+    }
+
     #[test]
     fn test_session_detail_scroll_down() {
         let mut handler = EventHandler::new();
@@ -468,6 +492,13 @@ mod tests {
             Message::SearchBackspace
         ));
 
+        // Ctrl+r でマッチ方式切り替え
+        let key = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::Search, None),
+            Message::CycleMatchMode
+        ));
+
         // Enter
         let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::empty());
         assert!(matches!(
@@ -481,6 +512,13 @@ mod tests {
             handler.key_to_message(key, ViewMode::Search, None),
             Message::CancelSearch
         ));
+
+        // Tab で検索範囲切り替え
+        let key = KeyEvent::new(KeyCode::Tab, KeyModifiers::empty());
+        assert!(matches!(
+            handler.key_to_message(key, ViewMode::Search, None),
+            Message::ToggleSearchScope
+        ));
     }
 
     #[test]
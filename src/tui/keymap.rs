@@ -0,0 +1,740 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::tea::{Message, ViewMode};
+
+/// 設定ファイルのキーバインド（コンテキスト名 → キー → 操作名）
+pub type KeybindingsConfig = BTreeMap<String, BTreeMap<String, String>>;
+
+/// 設定ファイルで割り当てを解除する操作名
+const UNBIND: &str = "none";
+
+/// キーの組み合わせ（Shift は文字の大小で表すため保持しない）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        Self { code, modifiers }
+    }
+
+    /// 修飾キーなし
+    pub fn plain(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    /// 修飾キーなしの文字入力か
+    fn is_text_input(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_)) && self.modifiers.is_empty()
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    /// "j", "Enter", "ctrl+r", "shift+tab" の形式
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid key '{}'", s);
+        // "+" 自体や "ctrl++" のように末尾が "+" のキー
+        let (prefix, key) = match s.strip_suffix("++") {
+            Some(prefix) => (Some(prefix), "+"),
+            None if s == "+" => (None, "+"),
+            None => match s.rsplit_once('+') {
+                Some((prefix, key)) => (Some(prefix), key),
+                None => (None, s),
+            },
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut shift = false;
+        for modifier in prefix.into_iter().flat_map(|prefix| prefix.split('+')) {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" | "meta" => modifiers |= KeyModifiers::ALT,
+                "shift" => shift = true,
+                _ => return Err(invalid()),
+            }
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if shift => KeyCode::Char(c.to_ascii_uppercase()),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" if shift => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(invalid()),
+                },
+            },
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            code => write!(f, "{}", code),
+        }
+    }
+}
+
+/// キーバインドが有効な画面
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    /// 全画面共通（画面ごとの割り当てより優先）
+    Global,
+    List,
+    Detail,
+    Search,
+    DetailSearch,
+    Filter,
+    Help,
+    Export,
+    CodePicker,
+    Dashboard,
+}
+
+impl KeyContext {
+    const ALL: [KeyContext; 10] = [
+        KeyContext::Global,
+        KeyContext::List,
+        KeyContext::Detail,
+        KeyContext::Search,
+        KeyContext::DetailSearch,
+        KeyContext::Filter,
+        KeyContext::Help,
+        KeyContext::Export,
+        KeyContext::CodePicker,
+        KeyContext::Dashboard,
+    ];
+
+    /// 設定ファイルでの名前
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyContext::Global => "global",
+            KeyContext::List => "list",
+            KeyContext::Detail => "detail",
+            KeyContext::Search => "search",
+            KeyContext::DetailSearch => "detail_search",
+            KeyContext::Filter => "filter",
+            KeyContext::Help => "help",
+            KeyContext::Export => "export",
+            KeyContext::CodePicker => "code_picker",
+            KeyContext::Dashboard => "dashboard",
+        }
+    }
+
+    /// ヘルプでの表示名
+    pub fn label(self) -> &'static str {
+        match self {
+            KeyContext::Global => "All",
+            KeyContext::List => "List",
+            KeyContext::Detail => "Detail",
+            KeyContext::Search => "Search",
+            KeyContext::DetailSearch => "Find",
+            KeyContext::Filter => "Filter",
+            KeyContext::Help => "Help",
+            KeyContext::Export => "Export",
+            KeyContext::CodePicker => "Code",
+            KeyContext::Dashboard => "Dashboard",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|context| context.name() == name)
+    }
+
+    /// 文字入力を受け付ける画面か（修飾キーなしの文字は割り当てられない）
+    fn accepts_text(self) -> bool {
+        matches!(self, KeyContext::Search | KeyContext::DetailSearch)
+    }
+}
+
+impl From<ViewMode> for KeyContext {
+    fn from(view_mode: ViewMode) -> Self {
+        match view_mode {
+            ViewMode::SessionList => KeyContext::List,
+            ViewMode::SessionDetail => KeyContext::Detail,
+            ViewMode::Search => KeyContext::Search,
+            ViewMode::DetailSearch => KeyContext::DetailSearch,
+            ViewMode::Filter => KeyContext::Filter,
+            ViewMode::Help => KeyContext::Help,
+            ViewMode::Export => KeyContext::Export,
+            ViewMode::CodePicker => KeyContext::CodePicker,
+            ViewMode::Dashboard => KeyContext::Dashboard,
+        }
+    }
+}
+
+/// キーに割り当てられる操作
+#[derive(Debug)]
+pub struct Action {
+    /// 設定ファイルでの名前
+    pub name: &'static str,
+    /// ヘルプでの説明
+    pub description: &'static str,
+    message: fn() -> Message,
+}
+
+impl Action {
+    /// 送信する Message
+    pub fn message(&self) -> Message {
+        (self.message)()
+    }
+
+    fn from_name(name: &str) -> Option<&'static Action> {
+        ACTIONS.iter().find(|action| action.name == name)
+    }
+}
+
+macro_rules! actions {
+    ($($name:literal, $description:literal => $message:expr;)*) => {
+        &[$(Action {
+            name: $name,
+            description: $description,
+            message: || $message,
+        }),*]
+    };
+}
+
+/// 割り当て可能な操作の一覧
+const ACTIONS: &[Action] = actions! {
+    "quit", "Quit" => Message::Quit;
+    "toggle_theme", "Next theme" => Message::ToggleTheme;
+    "show_help", "Show help" => Message::ShowHelp;
+    "move_down", "Move down" => Message::MoveDown;
+    "move_up", "Move up" => Message::MoveUp;
+    "enter_detail", "View detail / toggle project" => Message::EnterDetail;
+    "expand_project", "Expand project" => Message::ExpandCurrentProject;
+    "collapse_project", "Collapse project" => Message::CollapseCurrentProject;
+    "expand_all", "Expand all projects" => Message::ExpandAll;
+    "collapse_all", "Collapse all projects" => Message::CollapseAll;
+    "clear_filter", "Clear filter (or quit)" => Message::ClearFilter;
+    "start_search", "Search mode" => Message::StartSearch;
+    "start_filter", "Filter panel" => Message::StartFilter;
+    "cycle_sort", "Cycle sort order" => Message::CycleSort;
+    "toggle_list_mode", "Tree / flat list" => Message::ToggleListMode;
+    "show_dashboard", "Usage dashboard" => Message::ShowDashboard;
+    "start_export", "Export session" => Message::StartExport;
+    "toggle_currency", "Cycle currency" => Message::ToggleCurrency;
+    "back_to_list", "Back to list" => Message::BackToList;
+    "scroll_down", "Scroll down" => Message::ScrollDown(1);
+    "scroll_up", "Scroll up" => Message::ScrollUp(1);
+    "find_in_session", "Find in session" => Message::StartDetailFind;
+    "next_match", "Next match" => Message::NextDetailMatch;
+    "prev_match", "Previous match" => Message::PrevDetailMatch;
+    "toggle_tool_output", "Expand tool output" => Message::ToggleToolOutput;
+    "toggle_thinking", "Expand thinking" => Message::ToggleThinking;
+    "copy_message", "Copy message" => Message::CopySelectedMessage;
+    "copy_message_with_meta", "Copy message with meta" => Message::CopySelectedMessageWithMeta;
+    "open_code_picker", "Copy a code block" => Message::OpenCodePicker;
+    "cancel_search", "Cancel search" => Message::CancelSearch;
+    "confirm_search", "Apply search" => Message::ConfirmSearch;
+    "toggle_search_scope", "Title/content search" => Message::ToggleSearchScope;
+    "cycle_match_mode", "Substring/regex/fuzzy" => Message::CycleMatchMode;
+    "cancel_find", "Cancel find" => Message::CancelDetailFind;
+    "confirm_find", "Confirm find" => Message::ConfirmDetailFind;
+    "filter_next_field", "Switch field" => Message::FilterNextField;
+    "filter_preset_next", "Next date preset / source" => Message::FilterDatePresetNext;
+    "filter_preset_prev", "Previous date preset / source" => Message::FilterDatePresetPrev;
+    "apply_filter", "Apply filter" => Message::ApplyFilter;
+    "cancel_filter", "Cancel filter" => Message::CancelFilter;
+    "close_help", "Close help" => Message::CloseHelp;
+    "help_scroll_down", "Scroll help down" => Message::HelpScrollDown;
+    "help_scroll_up", "Scroll help up" => Message::HelpScrollUp;
+    "cancel_export", "Cancel export" => Message::CancelExport;
+    "toggle_export_format", "Switch format" => Message::ToggleExportFormat;
+    "toggle_export_thinking", "Include thinking" => Message::ToggleExportThinking;
+    "confirm_export", "Export" => Message::ConfirmExport;
+    "close_code_picker", "Close code picker" => Message::CloseCodePicker;
+    "code_picker_next", "Next code block" => Message::CodePickerNext;
+    "code_picker_prev", "Previous code block" => Message::CodePickerPrev;
+    "copy_code_snippet", "Copy code block" => Message::CopyCodeSnippet;
+    "toggle_dashboard_metric", "Chart cost / tokens" => Message::ToggleDashboardMetric;
+    "close_dashboard", "Close dashboard" => Message::CloseDashboard;
+};
+
+/// 既定のキーバインド（コンテキスト・キー・操作名）
+const DEFAULT_BINDINGS: &[(KeyContext, &[&str], &str)] = &[
+    (KeyContext::Global, &["ctrl+c"], "quit"),
+    (KeyContext::Global, &["ctrl+t"], "toggle_theme"),
+    // 一覧
+    (KeyContext::List, &["j", "Down"], "move_down"),
+    (KeyContext::List, &["k", "Up"], "move_up"),
+    (KeyContext::List, &["Enter"], "enter_detail"),
+    (KeyContext::List, &["l", "Right"], "expand_project"),
+    (KeyContext::List, &["h", "Left"], "collapse_project"),
+    (KeyContext::List, &["E"], "expand_all"),
+    (KeyContext::List, &["C"], "collapse_all"),
+    (KeyContext::List, &["/"], "start_search"),
+    (KeyContext::List, &["f"], "start_filter"),
+    (KeyContext::List, &["s"], "cycle_sort"),
+    (KeyContext::List, &["v"], "toggle_list_mode"),
+    (KeyContext::List, &["D"], "show_dashboard"),
+    (KeyContext::List, &["e"], "start_export"),
+    (KeyContext::List, &["u"], "toggle_currency"),
+    (KeyContext::List, &["Esc"], "clear_filter"),
+    (KeyContext::List, &["?"], "show_help"),
+    (KeyContext::List, &["q"], "quit"),
+    // 詳細
+    (KeyContext::Detail, &["j", "Down"], "scroll_down"),
+    (KeyContext::Detail, &["k", "Up"], "scroll_up"),
+    (KeyContext::Detail, &["/"], "find_in_session"),
+    (KeyContext::Detail, &["n"], "next_match"),
+    (KeyContext::Detail, &["N"], "prev_match"),
+    (KeyContext::Detail, &["o"], "toggle_tool_output"),
+    (KeyContext::Detail, &["t"], "toggle_thinking"),
+    (KeyContext::Detail, &["y"], "copy_message"),
+    (KeyContext::Detail, &["Y"], "copy_message_with_meta"),
+    (KeyContext::Detail, &["c"], "open_code_picker"),
+    (KeyContext::Detail, &["e"], "start_export"),
+    (KeyContext::Detail, &["u"], "toggle_currency"),
+    (KeyContext::Detail, &["?"], "show_help"),
+    (KeyContext::Detail, &["Esc", "q"], "back_to_list"),
+    // 検索
+    (KeyContext::Search, &["Tab"], "toggle_search_scope"),
+    (KeyContext::Search, &["ctrl+r"], "cycle_match_mode"),
+    (KeyContext::Search, &["Enter"], "confirm_search"),
+    (KeyContext::Search, &["Esc"], "cancel_search"),
+    (KeyContext::DetailSearch, &["Enter"], "confirm_find"),
+    (KeyContext::DetailSearch, &["Esc"], "cancel_find"),
+    // フィルタ
+    (KeyContext::Filter, &["Tab"], "filter_next_field"),
+    (KeyContext::Filter, &["j", "Down"], "filter_preset_next"),
+    (KeyContext::Filter, &["k", "Up"], "filter_preset_prev"),
+    (KeyContext::Filter, &["c"], "clear_filter"),
+    (KeyContext::Filter, &["Enter"], "apply_filter"),
+    (KeyContext::Filter, &["Esc"], "cancel_filter"),
+    // ヘルプ
+    (KeyContext::Help, &["j", "Down"], "help_scroll_down"),
+    (KeyContext::Help, &["k", "Up"], "help_scroll_up"),
+    (KeyContext::Help, &["Esc", "q", "?"], "close_help"),
+    // エクスポート
+    (
+        KeyContext::Export,
+        &["Tab", "j", "k", "Left", "Right"],
+        "toggle_export_format",
+    ),
+    (KeyContext::Export, &["t"], "toggle_export_thinking"),
+    (KeyContext::Export, &["Enter"], "confirm_export"),
+    (KeyContext::Export, &["Esc", "q"], "cancel_export"),
+    // コード片選択
+    (
+        KeyContext::CodePicker,
+        &["j", "Down", "Tab"],
+        "code_picker_next",
+    ),
+    (
+        KeyContext::CodePicker,
+        &["k", "Up", "shift+tab"],
+        "code_picker_prev",
+    ),
+    (KeyContext::CodePicker, &["Enter", "y"], "copy_code_snippet"),
+    (KeyContext::CodePicker, &["Esc", "q"], "close_code_picker"),
+    // ダッシュボード
+    (
+        KeyContext::Dashboard,
+        &["Tab", "m"],
+        "toggle_dashboard_metric",
+    ),
+    (KeyContext::Dashboard, &["u"], "toggle_currency"),
+    (KeyContext::Dashboard, &["?"], "show_help"),
+    (KeyContext::Dashboard, &["Esc", "q", "D"], "close_dashboard"),
+];
+
+/// キーと操作の対応
+#[derive(Debug, Clone, Copy)]
+pub struct KeyBinding {
+    pub context: KeyContext,
+    pub chord: KeyChord,
+    pub action: &'static Action,
+}
+
+/// ヘルプに表示する 1 行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HelpEntry {
+    /// キー（"j / Down" など）
+    pub keys: String,
+    /// 操作名
+    pub action: &'static str,
+    pub description: &'static str,
+    /// 有効な画面（"List/Detail" など）
+    pub contexts: String,
+}
+
+/// (画面, キー) → 操作 の対応表
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<KeyBinding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = DEFAULT_BINDINGS
+            .iter()
+            .flat_map(|(context, chords, action)| {
+                let action = Action::from_name(action).expect("default action exists");
+                chords.iter().map(move |chord| KeyBinding {
+                    context: *context,
+                    chord: chord.parse().expect("default key is valid"),
+                    action,
+                })
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// 既定のキーバインドに設定ファイルの割り当てを上書き
+    /// 不正な名前や衝突はまとめてエラーにする
+    pub fn from_config(config: &KeybindingsConfig) -> Result<Self, String> {
+        let mut keymap = Self::default();
+        let mut errors = Vec::new();
+
+        for (context_name, bindings) in config {
+            let Some(context) = KeyContext::from_name(context_name) else {
+                errors.push(format!("unknown context '{}'", context_name));
+                continue;
+            };
+            let mut configured: Vec<(KeyChord, &str)> = Vec::new();
+            for (key, action_name) in bindings {
+                let location = format!("{}.\"{}\"", context_name, key);
+                let chord = match key.parse::<KeyChord>() {
+                    Ok(chord) => chord,
+                    Err(e) => {
+                        errors.push(format!("{}: {}", location, e));
+                        continue;
+                    }
+                };
+                if let Some((_, other)) = configured.iter().find(|(c, _)| *c == chord) {
+                    errors.push(format!("{}: same key as \"{}\"", location, other));
+                    continue;
+                }
+                configured.push((chord, key));
+
+                let action = if action_name == UNBIND {
+                    None
+                } else {
+                    match Action::from_name(action_name) {
+                        Some(action) => Some(action),
+                        None => {
+                            errors.push(format!("{}: unknown action '{}'", location, action_name));
+                            continue;
+                        }
+                    }
+                };
+                if action.is_some() && context.accepts_text() && chord.is_text_input() {
+                    errors.push(format!("{}: would block typing '{}'", location, key));
+                    continue;
+                }
+                keymap.set(context, chord, action);
+            }
+        }
+
+        // 共通のキーは画面ごとの割り当てを隠してしまう
+        for binding in &keymap.bindings {
+            if binding.context != KeyContext::Global
+                && let Some(global) = keymap.find(KeyContext::Global, binding.chord)
+            {
+                errors.push(format!(
+                    "{}.\"{}\" ({}) conflicts with global {}",
+                    binding.context.name(),
+                    binding.chord,
+                    binding.action.name,
+                    global.action.name
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(keymap)
+        } else {
+            Err(format!("keybindings: {}", errors.join("; ")))
+        }
+    }
+
+    /// 割り当てを置き換え（None なら解除）
+    fn set(&mut self, context: KeyContext, chord: KeyChord, action: Option<&'static Action>) {
+        let existing = self
+            .bindings
+            .iter()
+            .position(|b| b.context == context && b.chord == chord);
+        match (existing, action) {
+            (Some(index), Some(action)) => self.bindings[index].action = action,
+            (Some(index), None) => {
+                self.bindings.remove(index);
+            }
+            (None, Some(action)) => self.bindings.push(KeyBinding {
+                context,
+                chord,
+                action,
+            }),
+            (None, None) => {}
+        }
+    }
+
+    fn find(&self, context: KeyContext, chord: KeyChord) -> Option<&KeyBinding> {
+        self.bindings
+            .iter()
+            .find(|b| b.context == context && b.chord == chord)
+    }
+
+    /// キーに対応する割り当て（共通の割り当てを優先）
+    pub fn lookup(&self, view_mode: ViewMode, key: KeyEvent) -> Option<&KeyBinding> {
+        let chord = KeyChord::from(key);
+        self.find(KeyContext::Global, chord)
+            .or_else(|| self.find(view_mode.into(), chord))
+    }
+
+    /// 操作に割り当てられたキーの表示名（最初のキー、共通の割り当てを優先）
+    /// 割り当てがなければ None
+    pub fn key_label(&self, context: KeyContext, action: &str) -> Option<String> {
+        [KeyContext::Global, context].iter().find_map(|context| {
+            self.bindings
+                .iter()
+                .find(|b| b.context == *context && b.action.name == action)
+                .map(|b| b.chord.to_string())
+        })
+    }
+
+    /// 全割り当て
+    pub fn bindings(&self) -> &[KeyBinding] {
+        &self.bindings
+    }
+
+    /// ヘルプ表示用の一覧
+    /// 同じ操作のキーは 1 行にまとめ、キーと操作が同じ行は画面をまとめる
+    pub fn help_entries(&self) -> Vec<HelpEntry> {
+        let mut groups: Vec<(KeyContext, &'static Action, Vec<String>)> = Vec::new();
+        for binding in &self.bindings {
+            let key = binding.chord.to_string();
+            match groups
+                .iter_mut()
+                .find(|(c, a, _)| *c == binding.context && a.name == binding.action.name)
+            {
+                Some((_, _, keys)) => keys.push(key),
+                None => groups.push((binding.context, binding.action, vec![key])),
+            }
+        }
+
+        let mut entries: Vec<HelpEntry> = Vec::new();
+        for (context, action, keys) in groups {
+            let keys = keys.join(" / ");
+            match entries
+                .iter_mut()
+                .find(|e| e.keys == keys && e.action == action.name)
+            {
+                Some(entry) => {
+                    entry.contexts.push('/');
+                    entry.contexts.push_str(context.label());
+                }
+                None => entries.push(HelpEntry {
+                    keys,
+                    action: action.name,
+                    description: action.description,
+                    contexts: context.label().to_string(),
+                }),
+            }
+        }
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn config(context: &str, bindings: &[(&str, &str)]) -> KeybindingsConfig {
+        BTreeMap::from([(
+            context.to_string(),
+            bindings
+                .iter()
+                .map(|(key, action)| (key.to_string(), action.to_string()))
+                .collect(),
+        )])
+    }
+
+    #[test]
+    fn test_parse_key_chord() {
+        let chord = |s: &str| s.parse::<KeyChord>().unwrap();
+        assert_eq!(
+            chord("ctrl+r"),
+            KeyChord::new(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(chord("Ctrl+R").to_string(), "Ctrl+R");
+        assert_eq!(chord("shift+g"), KeyChord::plain(KeyCode::Char('G')));
+        assert_eq!(chord("shift+tab"), KeyChord::plain(KeyCode::BackTab));
+        assert_eq!(chord("PageDown"), KeyChord::plain(KeyCode::PageDown));
+        assert_eq!(chord("+"), KeyChord::plain(KeyCode::Char('+')));
+        assert_eq!(
+            chord("alt++"),
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::ALT)
+        );
+        assert_eq!(chord("F5").to_string(), "F5");
+        assert!("hyper+x".parse::<KeyChord>().is_err());
+        assert!("F13".parse::<KeyChord>().is_err());
+
+        // 大文字は Shift 付きで届く
+        assert_eq!(
+            KeyChord::from(key(KeyCode::Char('E'), KeyModifiers::SHIFT)),
+            KeyChord::plain(KeyCode::Char('E'))
+        );
+    }
+
+    #[test]
+    fn test_keymap_lookup_and_override() {
+        let keymap = Keymap::from_config(&config(
+            "list",
+            &[("x", "quit"), ("q", "none"), ("ctrl+e", "expand_all")],
+        ))
+        .unwrap();
+        let lookup = |code, modifiers| {
+            keymap
+                .lookup(ViewMode::SessionList, key(code, modifiers))
+                .map(|binding| binding.action.name)
+        };
+
+        assert_eq!(lookup(KeyCode::Char('x'), KeyModifiers::NONE), Some("quit"));
+        assert_eq!(lookup(KeyCode::Char('q'), KeyModifiers::NONE), None);
+        assert_eq!(
+            lookup(KeyCode::Char('e'), KeyModifiers::CONTROL),
+            Some("expand_all")
+        );
+        assert_eq!(
+            lookup(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            Some("quit")
+        );
+        // 詳細画面の q はそのまま
+        assert_eq!(
+            keymap
+                .lookup(
+                    ViewMode::SessionDetail,
+                    key(KeyCode::Char('q'), KeyModifiers::NONE)
+                )
+                .map(|binding| binding.action.name),
+            Some("back_to_list")
+        );
+    }
+
+    #[test]
+    fn test_key_label() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.key_label(KeyContext::Detail, "toggle_tool_output"),
+            Some("o".to_string())
+        );
+        assert_eq!(
+            keymap.key_label(KeyContext::Detail, "quit"),
+            Some("Ctrl+c".to_string())
+        );
+
+        let remapped = Keymap::from_config(&config(
+            "detail",
+            &[("o", "none"), ("ctrl+o", "toggle_tool_output")],
+        ))
+        .unwrap();
+        assert_eq!(
+            remapped.key_label(KeyContext::Detail, "toggle_tool_output"),
+            Some("Ctrl+o".to_string())
+        );
+
+        let unbound = Keymap::from_config(&config("detail", &[("o", "none")])).unwrap();
+        assert_eq!(
+            unbound.key_label(KeyContext::Detail, "toggle_tool_output"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_keymap_conflicts() {
+        for (context, bindings) in [
+            ("list", &[("ctrl+t", "quit")][..]),
+            ("global", &[("q", "show_help")]),
+            ("search", &[("x", "cancel_search")]),
+            ("list", &[("ctrl+x", "quit"), ("Ctrl+x", "show_help")]),
+            ("list", &[("x", "launch")]),
+            ("list", &[("hyper+x", "quit")]),
+            ("sidebar", &[("x", "quit")]),
+        ] {
+            assert!(
+                Keymap::from_config(&config(context, bindings)).is_err(),
+                "{} {:?}",
+                context,
+                bindings
+            );
+        }
+        // 文字入力画面でも修飾キー付きなら割り当てられる
+        assert!(Keymap::from_config(&config("search", &[("ctrl+x", "cancel_search")])).is_ok());
+    }
+
+    #[test]
+    fn test_help_entries_from_keymap() {
+        let entries = Keymap::default().help_entries();
+        let find = |description: &str| {
+            entries
+                .iter()
+                .find(|entry| entry.description == description)
+                .unwrap()
+        };
+
+        assert_eq!(find("Move down").keys, "j / Down");
+        assert_eq!(find("Cycle currency").contexts, "List/Detail/Dashboard");
+        assert_eq!(find("Substring/regex/fuzzy").keys, "Ctrl+r");
+        assert_eq!(find("Quit").contexts, "All");
+
+        let keymap = Keymap::from_config(&config("detail", &[("x", "toggle_currency")])).unwrap();
+        let entries = keymap.help_entries();
+        assert!(entries.iter().any(|entry| {
+            entry.description == "Cycle currency"
+                && entry.keys == "u / x"
+                && entry.contexts == "Detail"
+        }));
+    }
+}
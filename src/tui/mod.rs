@@ -1,7 +1,9 @@
 pub mod app;
 pub mod event;
+pub mod keymap;
 pub mod terminal;
 
 pub use app::App;
 pub use event::EventHandler;
+pub use keymap::{KeyChord, KeyContext, KeybindingsConfig, Keymap};
 pub use terminal::Terminal;
@@ -0,0 +1,129 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::tea::Model;
+
+/// 一覧に表示するコード片の最大数（超える分はスクロール）
+const MAX_LIST_ROWS: usize = 8;
+/// プレビューに表示する最大行数
+const MAX_PREVIEW_LINES: usize = 10;
+
+/// コード片選択ポップアップをレンダリング
+pub fn render_code_picker(frame: &mut Frame, model: &Model) {
+    let Some(picker) = &model.code_picker else {
+        return;
+    };
+    let area = frame.area();
+    let palette = model.theme.palette;
+
+    let list_rows = picker.snippets.len().min(MAX_LIST_ROWS);
+    let preview_rows = picker
+        .selected_snippet()
+        .map(|snippet| snippet.text.lines().count().min(MAX_PREVIEW_LINES))
+        .unwrap_or(0)
+        .max(1);
+
+    // 中央にポップアップとして表示
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let popup_height = ((list_rows + preview_rows) as u16 + 4).min(area.height.saturating_sub(4));
+
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // 背景をクリア
+    frame.render_widget(Clear, popup_area);
+
+    // ポップアップブロック
+    let block = Block::default()
+        .title(" Copy Code ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(palette.border))
+        .style(Style::default().bg(palette.surface));
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    // 内部レイアウト
+    let layout = Layout::vertical([
+        Constraint::Length(list_rows as u16), // コード片一覧
+        Constraint::Length(1),                // 区切り
+        Constraint::Min(1),                   // プレビュー
+        Constraint::Length(1),                // フッター
+    ])
+    .split(inner);
+
+    // コード片一覧（選択中が見える位置までスクロール）
+    let offset = (picker.selected + 1).saturating_sub(list_rows);
+    let list_lines: Vec<Line> = picker
+        .snippets
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(list_rows)
+        .map(|(index, snippet)| {
+            let is_selected = index == picker.selected;
+            let marker = if is_selected { "▶ " } else { "  " };
+            let first_line = snippet.text.lines().next().unwrap_or_default().trim();
+            let line_count = snippet.text.lines().count();
+            let label_style = if is_selected {
+                Style::default()
+                    .fg(palette.accent_alt)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(palette.text_muted)
+            };
+            let text_style = if is_selected {
+                Style::default().fg(palette.text)
+            } else {
+                Style::default().fg(palette.text_dim)
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{}{}. {}", marker, index + 1, snippet.label),
+                    label_style,
+                ),
+                Span::styled(
+                    format!(" ({} lines)  ", line_count),
+                    Style::default().fg(palette.text_dim),
+                ),
+                Span::styled(first_line.to_string(), text_style),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(list_lines), layout[0]);
+
+    // 区切り
+    let separator = "─".repeat(layout[1].width as usize);
+    frame.render_widget(
+        Paragraph::new(separator).style(Style::default().fg(palette.border)),
+        layout[1],
+    );
+
+    // 選択中のプレビュー
+    if let Some(snippet) = picker.selected_snippet() {
+        let preview_lines: Vec<Line> = snippet
+            .text
+            .lines()
+            .take(layout[2].height as usize)
+            .map(|line| {
+                Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(palette.text_muted),
+                ))
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(preview_lines), layout[2]);
+    }
+
+    // フッター
+    let footer = Paragraph::new("Enter: Copy | j/k: Select | Esc: Cancel")
+        .style(Style::default().fg(palette.text_dim));
+    frame.render_widget(footer, layout[3]);
+}
@@ -0,0 +1,428 @@
+use chrono::{Local, NaiveDate};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph, Sparkline},
+};
+
+use crate::domain::billing::format_tokens;
+use crate::domain::{UsageReport, UsageTotals};
+use crate::tea::{DashboardMetric, Model};
+use crate::theme::Palette;
+use crate::widgets::StatusBar;
+
+/// 日ごとのスパークラインに表示する最大日数
+const MAX_DAYS: usize = 90;
+/// 週ごとの棒グラフに表示する最大週数
+const MAX_WEEKS: usize = 16;
+/// 週ごとの棒の幅
+const WEEK_BAR_WIDTH: u16 = 5;
+
+/// ダッシュボードビューをレンダリング
+pub fn render_dashboard(frame: &mut Frame, model: &Model) {
+    let area = frame.area();
+    let palette = model.theme.palette;
+
+    let base = Block::default().style(Style::default().bg(palette.bg));
+    frame.render_widget(base, area);
+
+    // 3分割レイアウト: ヘッダー(3行) | メイン(可変) | フッター(3行)
+    let layout = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(1),
+        Constraint::Length(3),
+    ])
+    .split(area);
+
+    render_header(frame, layout[0], model);
+    match &model.dashboard_report {
+        Some(report) => render_charts(frame, layout[1], model, report),
+        None => {
+            let message = Paragraph::new(Line::from(Span::styled(
+                format!(" Aggregating {} sessions...", model.filtered_count()),
+                Style::default().fg(palette.text_muted),
+            )))
+            .style(Style::default().bg(palette.bg));
+            frame.render_widget(message, layout[1]);
+        }
+    }
+    render_footer(frame, layout[2], model);
+}
+
+/// 金額を表示通貨で整形
+fn format_cost(model: &Model, usd: f64) -> String {
+    model.currency.format_cost(usd, &model.exchange_rates, None)
+}
+
+/// トークン数を短く整形（1.2k, 3.4M）
+fn format_tokens_short(tokens: u64) -> String {
+    match tokens {
+        0..1_000 => tokens.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", tokens as f64 / 1_000.0),
+        1_000_000..1_000_000_000 => format!("{:.1}M", tokens as f64 / 1_000_000.0),
+        _ => format!("{:.1}B", tokens as f64 / 1_000_000_000.0),
+    }
+}
+
+/// グラフの値（コストは 1/10000 USD 単位の整数）
+fn metric_value(metric: DashboardMetric, totals: &UsageTotals) -> u64 {
+    match metric {
+        DashboardMetric::Cost => (totals.cost.usd * 10_000.0).round() as u64,
+        DashboardMetric::Tokens => totals.usage.total_tokens,
+    }
+}
+
+/// グラフの値の表示テキスト
+fn metric_text(model: &Model, totals: &UsageTotals) -> String {
+    match model.dashboard_metric {
+        DashboardMetric::Cost => format_cost(model, totals.cost.usd),
+        DashboardMetric::Tokens => format_tokens_short(totals.usage.total_tokens),
+    }
+}
+
+/// ヘッダーをレンダリング（全体の集計）
+fn render_header(frame: &mut Frame, area: Rect, model: &Model) {
+    let palette = model.theme.palette;
+    let label =
+        |text: &str| Span::styled(text.to_string(), Style::default().fg(palette.text_muted));
+    let value = |text: String| {
+        Span::styled(
+            text,
+            Style::default()
+                .fg(palette.text)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let sep = || Span::styled(" | ", Style::default().fg(palette.text_dim));
+
+    let mut spans = vec![Span::styled(
+        " Usage ",
+        Style::default()
+            .fg(palette.accent)
+            .add_modifier(Modifier::BOLD),
+    )];
+    if let Some(report) = &model.dashboard_report {
+        let total = &report.total;
+        spans.push(sep());
+        spans.push(label("Sessions: "));
+        spans.push(value(total.sessions.to_string()));
+        spans.push(sep());
+        spans.push(label("Messages: "));
+        spans.push(value(total.messages.to_string()));
+        spans.push(sep());
+        spans.push(label("Tokens: "));
+        spans.push(Span::styled(
+            format!(
+                "{} (in {} / out {})",
+                format_tokens(total.usage.total_tokens),
+                format_tokens(total.usage.input_tokens),
+                format_tokens(total.usage.output_tokens)
+            ),
+            Style::default()
+                .fg(palette.accent_alt)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(sep());
+        spans.push(label("Cost: "));
+        let suffix = if total.cost.has_unknown { "+" } else { "" };
+        spans.push(Span::styled(
+            format!("{}{}", format_cost(model, total.cost.usd), suffix),
+            Style::default()
+                .fg(palette.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+        let breakdown = total.cost.breakdown;
+        if breakdown.total() > 0.0 {
+            spans.push(Span::styled(
+                format!(
+                    " (in {} / cache {} / out {})",
+                    format_cost(model, breakdown.input),
+                    format_cost(model, breakdown.cache()),
+                    format_cost(model, breakdown.output)
+                ),
+                Style::default().fg(palette.text_dim),
+            ));
+        }
+        if !total.cost.unpriced_models.is_empty() {
+            spans.push(sep());
+            spans.push(label("Unpriced: "));
+            spans.push(Span::styled(
+                total.cost.unpriced_models.join(", "),
+                Style::default().fg(palette.text_dim),
+            ));
+        }
+        if report.failed_sessions > 0 {
+            spans.push(sep());
+            spans.push(Span::styled(
+                format!("{} unreadable", report.failed_sessions),
+                Style::default().fg(palette.error),
+            ));
+        }
+    }
+
+    let block = Block::default()
+        .borders(Borders::BOTTOM)
+        .border_style(Style::default().fg(palette.border))
+        .style(Style::default().bg(palette.surface));
+    let header = Paragraph::new(Line::from(spans))
+        .block(block)
+        .style(Style::default().fg(palette.text).bg(palette.surface));
+    frame.render_widget(header, area);
+}
+
+/// グラフ領域の枠
+fn chart_block(title: String, palette: Palette) -> Block<'static> {
+    Block::default()
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(palette.text)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(palette.border))
+        .style(Style::default().bg(palette.bg))
+}
+
+/// 日ごと・週ごと・プロジェクトごと・モデルごとのグラフをレンダリング
+fn render_charts(frame: &mut Frame, area: Rect, model: &Model, report: &UsageReport) {
+    let rows = Layout::vertical([
+        Constraint::Length(5),
+        Constraint::Length(9),
+        Constraint::Min(4),
+    ])
+    .split(area);
+    let today = Local::now().date_naive();
+
+    render_daily(frame, rows[0], model, report, today);
+    render_weekly(frame, rows[1], model, report, today);
+
+    let columns =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).split(rows[2]);
+    let count = columns[0].height.saturating_sub(2) as usize;
+    render_ranking(
+        frame,
+        columns[0],
+        model,
+        " By project ",
+        report.top_projects(count),
+    );
+    render_ranking(
+        frame,
+        columns[1],
+        model,
+        " By model ",
+        report.top_models(count),
+    );
+}
+
+/// 日ごとのコストとトークン数のスパークライン
+fn render_daily(
+    frame: &mut Frame,
+    area: Rect,
+    model: &Model,
+    report: &UsageReport,
+    today: NaiveDate,
+) {
+    let palette = model.theme.palette;
+    let columns =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).split(area);
+    let days = (columns[0].width.saturating_sub(2) as usize).clamp(1, MAX_DAYS);
+    let recent = report.recent_days(today, days);
+
+    for (column, metric) in columns
+        .iter()
+        .zip([DashboardMetric::Cost, DashboardMetric::Tokens])
+    {
+        let total = match metric {
+            DashboardMetric::Cost => format_cost(
+                model,
+                recent.iter().map(|(_, totals)| totals.cost.usd).sum(),
+            ),
+            DashboardMetric::Tokens => format_tokens_short(
+                recent
+                    .iter()
+                    .map(|(_, totals)| totals.usage.total_tokens)
+                    .sum(),
+            ),
+        };
+        let data: Vec<u64> = recent
+            .iter()
+            .map(|(_, totals)| metric_value(metric, totals))
+            .collect();
+        let color = match metric {
+            DashboardMetric::Cost => palette.warning,
+            DashboardMetric::Tokens => palette.accent_alt,
+        };
+        let sparkline = Sparkline::default()
+            .block(chart_block(
+                format!(
+                    " Daily {} · {}d · {} ",
+                    metric.label().to_lowercase(),
+                    days,
+                    total
+                ),
+                palette,
+            ))
+            .data(&data)
+            .style(Style::default().fg(color).bg(palette.bg));
+        frame.render_widget(sparkline, *column);
+    }
+}
+
+/// 週ごとの棒グラフ
+fn render_weekly(
+    frame: &mut Frame,
+    area: Rect,
+    model: &Model,
+    report: &UsageReport,
+    today: NaiveDate,
+) {
+    let palette = model.theme.palette;
+    let weeks = (area.width.saturating_sub(2) / (WEEK_BAR_WIDTH + 1)).clamp(1, MAX_WEEKS as u16);
+    let recent = report.recent_weeks(today, weeks as usize);
+    let bars: Vec<Bar> = recent
+        .iter()
+        .map(|(week, totals)| {
+            let text = metric_text(model, totals);
+            Bar::default()
+                .value(metric_value(model.dashboard_metric, totals))
+                // 棒の幅に収まらない値は表示しない
+                .text_value(if text.chars().count() <= WEEK_BAR_WIDTH as usize {
+                    text
+                } else {
+                    String::new()
+                })
+                .label(Line::from(week.format("%m/%d").to_string()))
+        })
+        .collect();
+    let mut total = UsageTotals::default();
+    for (_, totals) in &recent {
+        total.usage.total_tokens += totals.usage.total_tokens;
+        total.cost.usd += totals.cost.usd;
+    }
+    let chart = BarChart::default()
+        .block(chart_block(
+            format!(
+                " Weekly {} · {}w · {} ",
+                model.dashboard_metric.label().to_lowercase(),
+                recent.len(),
+                metric_text(model, &total)
+            ),
+            palette,
+        ))
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(WEEK_BAR_WIDTH)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(palette.accent))
+        .value_style(Style::default().fg(palette.selection_fg).bg(palette.accent))
+        .label_style(Style::default().fg(palette.text_muted));
+    frame.render_widget(chart, area);
+}
+
+/// プロジェクト・モデルごとの横棒グラフ
+fn render_ranking(
+    frame: &mut Frame,
+    area: Rect,
+    model: &Model,
+    title: &str,
+    items: Vec<(&str, &UsageTotals)>,
+) {
+    let palette = model.theme.palette;
+    let name_width = (area.width / 3).clamp(8, 28) as usize;
+    let values: Vec<String> = items
+        .iter()
+        .map(|(_, totals)| {
+            let unit = if totals.sessions == 1 {
+                "session"
+            } else {
+                "sessions"
+            };
+            format!(
+                "{} · {} {}",
+                metric_text(model, totals),
+                totals.sessions,
+                unit
+            )
+        })
+        .collect();
+    let value_width = values.iter().map(|v| v.chars().count()).max().unwrap_or(0);
+    // 名前と値をラベルに並べ、棒はその右に描く
+    let bars: Vec<Bar> = items
+        .iter()
+        .zip(&values)
+        .map(|((name, totals), value)| {
+            let name = if name.chars().count() > name_width {
+                let head: String = name.chars().take(name_width.saturating_sub(1)).collect();
+                format!("{}…", head)
+            } else {
+                name.to_string()
+            };
+            Bar::default()
+                .value(metric_value(model.dashboard_metric, totals))
+                .text_value(String::new())
+                .label(Line::from(format!(
+                    "{:<name_width$} {:>value_width$}",
+                    name, value
+                )))
+        })
+        .collect();
+    let chart = BarChart::default()
+        .block(chart_block(title.to_string(), palette))
+        .direction(Direction::Horizontal)
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(1)
+        .bar_gap(0)
+        .bar_style(Style::default().fg(palette.accent_alt))
+        .label_style(Style::default().fg(palette.text_muted));
+    frame.render_widget(chart, area);
+}
+
+/// フッターをレンダリング（ステータスバー + キーバインド）
+fn render_footer(frame: &mut Frame, area: Rect, model: &Model) {
+    let palette = model.theme.palette;
+    let status_bar = StatusBar::new(model);
+    frame.render_widget(status_bar, area);
+
+    if area.height > 1 {
+        let keybind_area = Rect {
+            x: area.x,
+            y: area.y + 1,
+            width: area.width,
+            height: area.height.saturating_sub(1),
+        };
+
+        let keys = [
+            ("Esc/q", "Back"),
+            ("Tab", "Cost/Tokens"),
+            ("u", "Currency"),
+            ("Ctrl+t", "Theme"),
+            ("?", "Help"),
+        ];
+        let spans: Vec<Span> = keys
+            .iter()
+            .flat_map(|(key, action)| {
+                vec![
+                    Span::styled(
+                        format!(" {} ", key),
+                        Style::default()
+                            .fg(palette.badge_fg)
+                            .bg(palette.badge_bg)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!(" {} ", action),
+                        Style::default().fg(palette.text_dim),
+                    ),
+                    Span::raw(" "),
+                ]
+            })
+            .collect();
+
+        let keybinds = Paragraph::new(Line::from(spans))
+            .style(Style::default().fg(palette.text).bg(palette.surface));
+        frame.render_widget(keybinds, keybind_area);
+    }
+}
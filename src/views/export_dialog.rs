@@ -31,7 +31,7 @@ pub fn render_export_dialog(frame: &mut Frame, model: &Model) {
 
     // 中央にポップアップとして表示
     let popup_width = 50.min(area.width.saturating_sub(4));
-    let popup_height = 12.min(area.height.saturating_sub(4));
+    let popup_height = 14.min(area.height.saturating_sub(4));
 
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2;
@@ -60,6 +60,8 @@ pub fn render_export_dialog(frame: &mut Frame, model: &Model) {
         Constraint::Length(1), // Output ラベル
         Constraint::Length(1), // Output パス
         Constraint::Length(1), // 空行
+        Constraint::Length(1), // オプション
+        Constraint::Length(1), // 空行
         Constraint::Length(1), // ステータス
         Constraint::Min(1),    // フッター
     ])
@@ -124,6 +126,26 @@ pub fn render_export_dialog(frame: &mut Frame, model: &Model) {
         Paragraph::new(format!("  ./{}", filename)).style(Style::default().fg(palette.text_dim));
     frame.render_widget(output_path, layout[5]);
 
+    // オプション
+    let (thinking_marker, thinking_style) = if model.export_include_thinking {
+        (
+            "[x]",
+            Style::default()
+                .fg(palette.accent_alt)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        ("[ ]", Style::default().fg(palette.text_dim))
+    };
+    let option_line = Line::from(vec![
+        Span::styled(
+            format!("  {} Include thinking ", thinking_marker),
+            thinking_style,
+        ),
+        Span::styled("(t)", Style::default().fg(palette.text_dim)),
+    ]);
+    frame.render_widget(Paragraph::new(option_line), layout[7]);
+
     // ステータス表示
     if let Some(status) = &model.export_status {
         let status_line = match status {
@@ -159,7 +181,7 @@ pub fn render_export_dialog(frame: &mut Frame, model: &Model) {
                 Style::default().fg(palette.error),
             )),
         };
-        frame.render_widget(Paragraph::new(status_line), layout[7]);
+        frame.render_widget(Paragraph::new(status_line), layout[9]);
     }
 
     // フッター
@@ -168,5 +190,5 @@ pub fn render_export_dialog(frame: &mut Frame, model: &Model) {
         _ => "Enter: Export | Tab/j/k: Switch | Esc: Cancel",
     };
     let footer = Paragraph::new(footer_text).style(Style::default().fg(palette.text_dim));
-    frame.render_widget(footer, layout[8]);
+    frame.render_widget(footer, layout[10]);
 }
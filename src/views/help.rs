@@ -7,40 +7,43 @@ use ratatui::{
 };
 
 use crate::tea::Model;
+use crate::tui::keymap::HelpEntry;
 
-/// キーバインド一覧
-const KEYBINDINGS: &[(&str, &str, &str)] = &[
-    // Navigation
-    ("j / Down", "Move down / Scroll down", "List/Detail"),
-    ("k / Up", "Move up / Scroll up", "List/Detail"),
-    ("Enter", "View detail", "List"),
-    ("Esc", "Back / Clear filter", "All"),
-    // Search & Filter
-    ("/", "Search mode", "List"),
-    ("f", "Filter panel", "List"),
-    ("Tab", "Switch field", "Filter"),
-    ("c", "Clear filter", "Filter"),
-    // Detail actions
-    ("y", "Copy message", "Detail"),
-    ("Y", "Copy message with meta", "Detail"),
-    ("u", "Toggle currency", "List/Detail"),
-    ("Ctrl+t", "Toggle theme", "All"),
-    // Export
-    ("e", "Export session", "List/Detail"),
-    // Other
-    ("?", "Show help", "All"),
-    ("q", "Quit", "All"),
-    ("Ctrl+C", "Force quit", "All"),
-];
-
-/// ヘルプ画面をレンダリング
-pub fn render_help(frame: &mut Frame, model: &Model) {
+/// ヘルプのポップアップの高さ
+fn popup_height(area: Rect, rows: usize) -> u16 {
+    (rows as u16)
+        .saturating_add(6)
+        .min(area.height.saturating_sub(4))
+}
+
+/// ヘルプの最大スクロール位置（枠・ヘッダー・フッター分を除いた表示行数で計算）
+pub fn help_max_scroll(area: Rect, rows: usize) -> usize {
+    let visible = popup_height(area, rows).saturating_sub(4) as usize;
+    rows.saturating_sub(visible)
+}
+
+/// ヘルプ画面をレンダリング（現在のキーマップから生成）
+pub fn render_help(frame: &mut Frame, model: &Model, entries: &[HelpEntry]) {
     let area = frame.area();
     let palette = model.theme.palette;
 
+    let key_width = entries
+        .iter()
+        .map(|entry| entry.keys.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(12)
+        + 2;
+    let action_width = entries
+        .iter()
+        .map(|entry| entry.description.chars().count())
+        .max()
+        .unwrap_or(0)
+        + 2;
+
     // 中央にポップアップとして表示
-    let popup_width = 60.min(area.width.saturating_sub(4));
-    let popup_height = (KEYBINDINGS.len() as u16 + 6).min(area.height.saturating_sub(4));
+    let popup_width = ((key_width + action_width + 16) as u16).min(area.width.saturating_sub(4));
+    let popup_height = popup_height(area, entries.len());
 
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2;
@@ -71,13 +74,13 @@ pub fn render_help(frame: &mut Frame, model: &Model) {
     // ヘッダー
     let header = Line::from(vec![
         Span::styled(
-            format!("{:<15}", "Key"),
+            format!("{:<key_width$}", "Key"),
             Style::default()
                 .fg(palette.accent_alt)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
-            format!("{:<25}", "Action"),
+            format!("{:<action_width$}", "Action"),
             Style::default()
                 .fg(palette.accent_alt)
                 .add_modifier(Modifier::BOLD),
@@ -92,19 +95,24 @@ pub fn render_help(frame: &mut Frame, model: &Model) {
     frame.render_widget(Paragraph::new(header), layout[0]);
 
     // キーバインド一覧
-    let keybind_lines: Vec<Line> = KEYBINDINGS
+    let scroll = model.help_scroll.min(help_max_scroll(area, entries.len()));
+    let keybind_lines: Vec<Line> = entries
         .iter()
-        .map(|(key, action, context)| {
+        .skip(scroll)
+        .map(|entry| {
             Line::from(vec![
                 Span::styled(
-                    format!("{:<15}", key),
+                    format!("{:<key_width$}", entry.keys),
                     Style::default().fg(palette.accent),
                 ),
                 Span::styled(
-                    format!("{:<25}", action),
+                    format!("{:<action_width$}", entry.description),
                     Style::default().fg(palette.text),
                 ),
-                Span::styled(*context, Style::default().fg(palette.text_dim)),
+                Span::styled(
+                    entry.contexts.as_str(),
+                    Style::default().fg(palette.text_dim),
+                ),
             ])
         })
         .collect();
@@ -112,8 +120,19 @@ pub fn render_help(frame: &mut Frame, model: &Model) {
     frame.render_widget(Paragraph::new(keybind_lines), layout[1]);
 
     // フッター
+    let keys_for = |action: &str| {
+        entries
+            .iter()
+            .find(|entry| entry.action == action && entry.contexts.contains("Help"))
+            .map(|entry| entry.keys.as_str())
+    };
+    let footer_text = match (keys_for("help_scroll_down"), keys_for("close_help")) {
+        (Some(scroll), Some(close)) => format!("{} to scroll, {} to close", scroll, close),
+        (None, Some(close)) => format!("Press {} to close", close),
+        _ => String::new(),
+    };
     let footer = Line::from(vec![Span::styled(
-        "Press Esc or ? to close",
+        footer_text,
         Style::default().fg(palette.text_dim),
     )]);
     frame.render_widget(Paragraph::new(footer), layout[2]);
@@ -1,11 +1,15 @@
+pub mod code_picker;
+pub mod dashboard;
 pub mod export_dialog;
 pub mod help;
 pub mod preview_pane;
 pub mod session_detail;
 pub mod session_list;
 
+pub use code_picker::render_code_picker;
+pub use dashboard::render_dashboard;
 pub use export_dialog::render_export_dialog;
-pub use help::render_help;
+pub use help::{help_max_scroll, render_help};
 pub use preview_pane::render_preview_pane;
 pub use session_detail::render_session_detail;
 pub use session_list::render_session_list;
@@ -7,8 +7,8 @@ use ratatui::{
 };
 
 use crate::domain::billing::format_tokens;
-use crate::tea::Model;
-use crate::widgets::{LineHighlight, MessageBlock, StatusBar};
+use crate::tea::{Model, ViewMode};
+use crate::widgets::{LineHighlight, StatusBar, highlight_ranges};
 
 /// セッション詳細ビューをレンダリング
 pub fn render_session_detail(frame: &mut Frame, model: &Model) {
@@ -38,6 +38,13 @@ fn render_header(frame: &mut Frame, area: Rect, model: &Model) {
         let message_count = session.message_count();
         let usage_summary = session.usage_summary();
         let cost_summary = session.cost_summary();
+        // セッション開始日の為替レートで換算
+        let started_at = session.entries.iter().find_map(|entry| entry.datetime());
+        let format_cost = |usd: f64| {
+            model
+                .currency
+                .format_cost(usd, &model.exchange_rates, started_at)
+        };
         let tokens_value = if usage_summary.has_data {
             let suffix = if usage_summary.has_unknown { "+" } else { "" };
             Some(format!(
@@ -54,11 +61,7 @@ fn render_header(frame: &mut Frame, area: Rect, model: &Model) {
             } else {
                 let suffix = if cost_summary.has_unknown { "+" } else { "" };
                 (
-                    Some(format!(
-                        "{}{}",
-                        model.currency.format_cost(cost_summary.usd),
-                        suffix
-                    )),
+                    Some(format!("{}{}", format_cost(cost_summary.usd), suffix)),
                     false,
                 )
             }
@@ -99,6 +102,33 @@ fn render_header(frame: &mut Frame, area: Rect, model: &Model) {
                     .add_modifier(Modifier::BOLD)
             };
             meta_spans.push(Span::styled(cost_value, cost_style));
+
+            // 入力・キャッシュ・出力の内訳
+            let breakdown = cost_summary.breakdown;
+            if breakdown.total() > 0.0 {
+                meta_spans.push(Span::styled(
+                    format!(
+                        " (in {} / cache {} / out {})",
+                        format_cost(breakdown.input),
+                        format_cost(breakdown.cache()),
+                        format_cost(breakdown.output)
+                    ),
+                    Style::default().fg(palette.text_dim),
+                ));
+            }
+        }
+
+        // 料金表にないモデルはコストに含まれない
+        if !cost_summary.unpriced_models.is_empty() {
+            push_sep(&mut meta_spans);
+            meta_spans.push(Span::styled(
+                "Unpriced: ",
+                Style::default().fg(palette.text_muted),
+            ));
+            meta_spans.push(Span::styled(
+                cost_summary.unpriced_models.join(", "),
+                Style::default().fg(palette.text_dim),
+            ));
         }
 
         push_sep(&mut meta_spans);
@@ -195,14 +225,7 @@ fn render_content(frame: &mut Frame, area: Rect, model: &Model) {
         let inner_width = area.width.saturating_sub(2);
 
         // メッセージをテキストに変換
-        let mut lines: Vec<Line> = Vec::new();
-
-        for entry in messages.iter() {
-            let message_block =
-                MessageBlock::new(*entry, inner_width, model.currency, model.theme);
-            let block_lines = message_block.to_lines();
-            lines.extend(block_lines);
-        }
+        let lines = highlight_find_matches(model.detail_lines(inner_width), model);
 
         let total_lines = model.detail_total_lines();
         let visible_height = area.height.saturating_sub(2) as usize; // ボーダー分を除く
@@ -211,11 +234,7 @@ fn render_content(frame: &mut Frame, area: Rect, model: &Model) {
         let scroll_info = if total_lines > visible_height {
             let max_scroll = total_lines.saturating_sub(visible_height);
             let scroll_pos = model.detail_scroll_offset.min(max_scroll);
-            let percent = if max_scroll > 0 {
-                (scroll_pos * 100) / max_scroll
-            } else {
-                0
-            };
+            let percent = (scroll_pos * 100).checked_div(max_scroll).unwrap_or(0);
             format!(" [{}/{}] {}% ", scroll_pos + 1, total_lines, percent)
         } else {
             String::new()
@@ -254,6 +273,40 @@ fn render_content(frame: &mut Frame, area: Rect, model: &Model) {
     }
 }
 
+/// 詳細画面内検索の一致箇所をハイライト
+fn highlight_find_matches<'a>(lines: Vec<Line<'a>>, model: &Model) -> Vec<Line<'a>> {
+    let find = &model.detail_find;
+    if find.matches.is_empty() {
+        return lines;
+    }
+    let palette = model.theme.palette;
+    // 反転表示にしてカーソル行のハイライトと重なっても判別できるようにする
+    let match_style = Style::default()
+        .fg(palette.accent_alt)
+        .add_modifier(Modifier::REVERSED);
+    let current_style = Style::default()
+        .fg(palette.warning)
+        .add_modifier(Modifier::REVERSED | Modifier::BOLD);
+
+    let mut matches = find.matches.iter().enumerate().peekable();
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(line_index, line)| {
+            let mut ranges = Vec::new();
+            while let Some((index, m)) = matches.next_if(|(_, m)| m.line == line_index) {
+                let style = if find.current == Some(index) {
+                    current_style
+                } else {
+                    match_style
+                };
+                ranges.push((m.range.clone(), style));
+            }
+            highlight_ranges(line, &ranges)
+        })
+        .collect()
+}
+
 /// フッターをレンダリング（ステータスバー + キーバインド）
 fn render_footer(frame: &mut Frame, area: Rect, model: &Model) {
     let palette = model.theme.palette;
@@ -273,12 +326,49 @@ fn render_footer(frame: &mut Frame, area: Rect, model: &Model) {
         let keybind_bg = Block::default().style(Style::default().bg(palette.surface));
         frame.render_widget(keybind_bg, keybind_area);
 
+        // 検索入力中はキーバインドの代わりに入力欄を表示
+        if model.view_mode == ViewMode::DetailSearch {
+            let mut spans = vec![
+                Span::styled(
+                    " /",
+                    Style::default()
+                        .fg(palette.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    model.detail_find.query.as_str(),
+                    Style::default().fg(palette.input_fg),
+                ),
+                Span::styled(
+                    "_",
+                    Style::default()
+                        .fg(palette.cursor)
+                        .add_modifier(Modifier::SLOW_BLINK),
+                ),
+            ];
+            if let Some(position) = model.detail_find.position_label() {
+                spans.push(Span::styled(
+                    format!("  {}", position),
+                    Style::default().fg(palette.text_dim),
+                ));
+            }
+            let input = Paragraph::new(Line::from(spans))
+                .style(Style::default().fg(palette.text).bg(palette.surface));
+            frame.render_widget(input, keybind_area);
+            return;
+        }
+
         let keys = [
             ("Esc/q", "Back"),
             ("j/↓", "Down"),
             ("k/↑", "Up"),
+            ("/", "Find"),
+            ("n/N", "Next/Prev"),
+            ("o", "Tool output"),
+            ("t", "Thinking"),
             ("y", "Copy"),
             ("Y", "Copy+Meta"),
+            ("c", "Code"),
             ("u", "Currency"),
             ("Ctrl+t", "Theme"),
             ("e", "Export"),
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local, Utc};
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
@@ -6,11 +9,15 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
+use crate::domain::SessionStats;
 use crate::layout::TwoPane;
-use crate::tea::{Model, ViewMode};
+use crate::search::DateRange;
+use crate::tea::{ListMode, Model, SessionSource, ViewMode};
 use crate::theme::Palette;
 use crate::views::render_preview_pane;
-use crate::widgets::{ProjectTree, ProjectTreeState, SearchBar, StatusBar};
+use crate::widgets::{
+    ProjectTree, ProjectTreeState, SearchBar, SessionTable, SessionTableState, StatusBar,
+};
 
 /// セッション一覧ビューをレンダリング
 pub fn render_session_list(frame: &mut Frame, model: &Model) {
@@ -66,7 +73,7 @@ pub fn render_session_list(frame: &mut Frame, model: &Model) {
 
 /// メインコンテンツ領域をレンダリング（2ペイン）
 fn render_main_content(frame: &mut Frame, area: Rect, model: &Model) {
-    let two_pane = TwoPane::new(55, 45);
+    let two_pane = TwoPane::new(model.list_width_percent, 100 - model.list_width_percent);
     let (left, right) = two_pane.split(area);
 
     render_sessions(frame, left, model);
@@ -115,7 +122,10 @@ fn render_header(frame: &mut Frame, area: Rect, model: &Model) {
 
 /// 検索バーをレンダリング
 fn render_search_bar(frame: &mut Frame, area: Rect, model: &Model) {
-    let search_bar = SearchBar::new(&model.search_query.text, model.theme).cursor_visible(true);
+    let search_bar = SearchBar::new(&model.search_query.text, model.theme)
+        .scope(model.search_query.scope)
+        .error(model.search_error.as_ref())
+        .cursor_visible(true);
     frame.render_widget(search_bar, area);
 }
 
@@ -127,13 +137,12 @@ fn render_sessions(frame: &mut Frame, area: Rect, model: &Model) {
 
     let title = if model.is_filtered || !model.search_query.is_empty() {
         let filtered_sessions = model.filtered_sessions();
+        format!(" Sessions ({}/{}) ", filtered_sessions.len(), session_count)
+    } else {
         format!(
-            " Sessions ({}/{}) ",
-            filtered_sessions.len(),
-            session_count
+            " Projects ({}) / Sessions ({}) ",
+            project_count, session_count
         )
-    } else {
-        format!(" Projects ({}) / Sessions ({}) ", project_count, session_count)
     };
 
     let block = Block::default()
@@ -155,9 +164,31 @@ fn render_sessions(frame: &mut Frame, area: Rect, model: &Model) {
         return;
     }
 
+    if model.list_mode == ListMode::Flat {
+        // フラット表示は SessionTable ウィジェットを使用
+        let sessions: Vec<_> = model
+            .tree_items
+            .iter()
+            .filter_map(|item| item.session.as_ref())
+            .collect();
+        let table = SessionTable::new(&sessions, model.theme)
+            .stats(&model.session_stats)
+            .currency(model.currency, &model.exchange_rates)
+            .block(block);
+
+        let mut state = SessionTableState::new();
+        state.select(model.selected_index);
+
+        frame.render_stateful_widget(table, area, &mut state);
+        return;
+    }
+
     // ProjectTree ウィジェットを使用
-    let tree = ProjectTree::new(&model.tree_items, &model.expanded_projects, model.theme)
-        .block(block);
+    let mut tree =
+        ProjectTree::new(&model.tree_items, &model.expanded_projects, model.theme).block(block);
+    if let Some(result) = model.active_content_search() {
+        tree = tree.content_hits(result);
+    }
 
     let mut state = ProjectTreeState::new();
     state.select(model.selected_index);
@@ -185,7 +216,11 @@ fn render_footer(frame: &mut Frame, area: Rect, model: &Model) {
         frame.render_widget(keybind_bg, keybind_area);
 
         let keys: Vec<(&str, &str)> = match model.view_mode {
-            ViewMode::Search => vec![("Enter", "Confirm"), ("Esc", "Cancel")],
+            ViewMode::Search => vec![
+                ("Enter", "Confirm"),
+                ("Tab", "Title/Content"),
+                ("Esc", "Cancel"),
+            ],
             ViewMode::Filter => vec![
                 ("Tab", "Switch"),
                 ("Enter", "Apply"),
@@ -199,10 +234,16 @@ fn render_footer(frame: &mut Frame, area: Rect, model: &Model) {
                     ("l/h", "Expand/Fold"),
                     ("/", "Search"),
                     ("f", "Filter"),
+                    ("s", "Sort"),
+                    ("v", "Tree/Flat"),
+                    ("D", "Dashboard"),
                     ("Ctrl+t", "Theme"),
                     ("e", "Export"),
                     ("?", "Help"),
                 ];
+                if model.list_mode == ListMode::Flat {
+                    keys.retain(|(key, _)| *key != "l/h");
+                }
                 if model.is_filtered || !model.search_query.is_empty() {
                     keys.push(("Esc", "Clear"));
                 }
@@ -244,9 +285,14 @@ fn render_filter_panel(frame: &mut Frame, model: &Model) {
     let area = frame.area();
     let palette = model.theme.palette;
 
+    // 日付プリセット（先頭は All）
+    let date_presets: Vec<String> = std::iter::once("All".to_string())
+        .chain(model.date_presets.iter().map(|preset| preset.label()))
+        .collect();
+
     // 中央にパネルを配置
-    let panel_width = 50.min(area.width.saturating_sub(4));
-    let panel_height = 12.min(area.height.saturating_sub(4));
+    let panel_width = 56.min(area.width.saturating_sub(4));
+    let panel_height = (date_presets.len() as u16 + 17).min(area.height.saturating_sub(4));
 
     let panel_x = (area.width.saturating_sub(panel_width)) / 2;
     let panel_y = (area.height.saturating_sub(panel_height)) / 2;
@@ -269,13 +315,27 @@ fn render_filter_panel(frame: &mut Frame, model: &Model) {
 
     // 内部コンテンツ
     let lines_layout = Layout::vertical([
-        Constraint::Length(1), // Date Range label
-        Constraint::Length(4), // Date Range options
-        Constraint::Length(1), // Project label
-        Constraint::Length(1), // Project input
-        Constraint::Min(1),    // Help
+        Constraint::Length(1),                         // Date Range label
+        Constraint::Length(date_presets.len() as u16), // Date Range options
+        Constraint::Length(1),                         // From input
+        Constraint::Length(1),                         // To input
+        Constraint::Length(1),                         // Date input feedback
+        Constraint::Length(1),                         // Project input
+        Constraint::Length(1),                         // Source
+        Constraint::Length(7),                         // Metadata / usage inputs
+        Constraint::Length(1),                         // Metadata hint
+        Constraint::Min(1),                            // Help
     ])
     .split(inner);
+    let label_style = |field: FilterField| {
+        if model.filter_field == field {
+            Style::default()
+                .fg(palette.accent_alt)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(palette.text)
+        }
+    };
 
     // Date Range ラベル
     let date_style = if model.filter_field == FilterField::DateRange {
@@ -288,13 +348,15 @@ fn render_filter_panel(frame: &mut Frame, model: &Model) {
     let date_label = Paragraph::new("Date Range:").style(date_style);
     frame.render_widget(date_label, lines_layout[0]);
 
-    // Date Range オプション
-    let date_presets = ["All", "Today", "Last 7 days", "Last 30 days"];
+    // Date Range オプション（日付を入力中はどれも選択しない）
+    let custom_range = model.custom_date_range();
+    let has_custom_range = !matches!(custom_range, Ok(None));
+    let has_custom_range_error = custom_range.is_err();
     let date_lines: Vec<Line> = date_presets
         .iter()
         .enumerate()
         .map(|(i, preset)| {
-            let marker = if i == model.date_preset_index {
+            let marker = if i == model.date_preset_index && !has_custom_range {
                 "● "
             } else {
                 "○ "
@@ -311,31 +373,172 @@ fn render_filter_panel(frame: &mut Frame, model: &Model) {
     let date_options = Paragraph::new(date_lines);
     frame.render_widget(date_options, lines_layout[1]);
 
-    // Project ラベル
-    let project_style = if model.filter_field == FilterField::Project {
-        Style::default()
-            .fg(palette.accent_alt)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(palette.text)
+    // From / To 入力
+    for (i, (label, field, input)) in [
+        ("From", FilterField::From, &model.filter_from_input),
+        ("To", FilterField::To, &model.filter_to_input),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let focused = model.filter_field == field;
+        let line = Line::from(vec![
+            Span::styled(
+                format!("  {:<6}", format!("{}:", label)),
+                label_style(field),
+            ),
+            Span::styled(
+                format!("{}{}", input, if focused { "_" } else { "" }),
+                Style::default().fg(palette.input_fg),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(line), lines_layout[2 + i]);
+    }
+
+    // 入力エラー、または入力した範囲の確認
+    let feedback = match (&model.filter_error, custom_range) {
+        (Some(error), Err(_)) => {
+            Span::styled(format!("  {}", error), Style::default().fg(palette.error))
+        }
+        (None, Ok(Some(range))) => Span::styled(
+            format!("  {}", format_date_range(&range)),
+            Style::default().fg(palette.success),
+        ),
+        _ => Span::styled(
+            "  e.g. 2025-01-31, today, -3d, last monday",
+            Style::default().fg(palette.text_dim),
+        ),
+    };
+    frame.render_widget(Paragraph::new(Line::from(feedback)), lines_layout[4]);
+
+    let input_line = |label: &str, field: FilterField, input: &str| {
+        let cursor = if model.filter_field == field { "_" } else { "" };
+        Line::from(vec![
+            Span::styled(format!("{:<10}", format!("{}:", label)), label_style(field)),
+            Span::styled(
+                format!("{}{}", input, cursor),
+                Style::default().fg(palette.input_fg),
+            ),
+        ])
     };
-    let project_label = Paragraph::new("Project:").style(project_style);
-    frame.render_widget(project_label, lines_layout[2]);
 
     // Project 入力
-    let cursor = if model.filter_field == FilterField::Project {
-        "_"
-    } else {
-        ""
+    let project_line = input_line("Project", FilterField::Project, &model.filter_project_input);
+    frame.render_widget(Paragraph::new(project_line), lines_layout[5]);
+
+    // Source 選択（All / Claude / Codex）
+    let mut source_spans = vec![Span::styled(
+        format!("{:<10}", "Source:"),
+        label_style(FilterField::Source),
+    )];
+    for (label, source) in [
+        ("All", None),
+        ("Claude", Some(SessionSource::Claude)),
+        ("Codex", Some(SessionSource::Codex)),
+    ] {
+        let selected = model.filter_source == source;
+        let style = if selected && model.filter_field == FilterField::Source {
+            Style::default().fg(palette.accent_alt)
+        } else {
+            Style::default().fg(palette.text_dim)
+        };
+        let marker = if selected { "● " } else { "○ " };
+        source_spans.push(Span::styled(format!("{}{}  ", marker, label), style));
+    }
+    frame.render_widget(Paragraph::new(Line::from(source_spans)), lines_layout[6]);
+
+    // Model / Branch / Version / Tool / 使用量の入力
+    let metadata_lines: Vec<Line> = [
+        ("Model", FilterField::Model, &model.filter_model_input),
+        ("Branch", FilterField::Branch, &model.filter_branch_input),
+        ("Version", FilterField::Version, &model.filter_version_input),
+        ("Tool", FilterField::Tool, &model.filter_tool_input),
+        ("Cost", FilterField::Cost, &model.filter_cost_input),
+        ("Tokens", FilterField::Tokens, &model.filter_tokens_input),
+        (
+            "Messages",
+            FilterField::Messages,
+            &model.filter_messages_input,
+        ),
+    ]
+    .into_iter()
+    .map(|(label, field, input)| input_line(label, field, input))
+    .collect();
+    frame.render_widget(Paragraph::new(metadata_lines), lines_layout[7]);
+
+    // しきい値の入力エラー、または入力できる値の例
+    let hint = match (&model.filter_error, has_custom_range_error) {
+        (Some(error), false) => {
+            Span::styled(format!("  {}", error), Style::default().fg(palette.error))
+        }
+        _ => Span::styled(
+            metadata_hint(model, inner.width as usize),
+            Style::default().fg(palette.text_dim),
+        ),
     };
-    let project_input = Paragraph::new(format!("  {}{}", model.filter_project_input, cursor))
-        .style(Style::default().fg(palette.input_fg));
-    frame.render_widget(project_input, lines_layout[3]);
+    frame.render_widget(Paragraph::new(Line::from(hint)), lines_layout[8]);
 
     // ヘルプ
     let help = Paragraph::new("Tab: Switch | j/k: Select | Enter: Apply | Esc: Cancel")
         .style(Style::default().fg(palette.text_dim));
-    frame.render_widget(help, lines_layout[4]);
+    frame.render_widget(help, lines_layout[9]);
+}
+
+/// 選択中の欄に入力できる値の例（集計メタデータはインデックス構築前はその旨を表示）
+fn metadata_hint(model: &Model, width: usize) -> String {
+    use crate::search::FilterField;
+
+    let values = |select: fn(&SessionStats) -> &Vec<String>| {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for stats in model.session_stats.values() {
+            for value in select(stats) {
+                *counts.entry(value.as_str()).or_default() += 1;
+            }
+        }
+        // よく使われている順
+        let mut values: Vec<(&str, usize)> = counts.into_iter().collect();
+        values.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        values
+            .into_iter()
+            .map(|(value, _)| value.to_string())
+            .collect::<Vec<_>>()
+    };
+    let values = match model.filter_field {
+        FilterField::Model => values(|stats| &stats.models),
+        FilterField::Branch => values(|stats| &stats.git_branches),
+        FilterField::Version => values(|stats| &stats.versions),
+        FilterField::Tool => values(|stats| &stats.tools),
+        FilterField::Cost => return "  USD, e.g. >0.5, <=2 (no operator: at least)".to_string(),
+        FilterField::Tokens => return "  e.g. >100k, <=1.5m".to_string(),
+        FilterField::Messages => return "  e.g. >=20, <5".to_string(),
+        _ => return String::new(),
+    };
+    if model.session_stats.is_empty() {
+        return "  (available after the search index is built)".to_string();
+    }
+
+    let mut hint = "  e.g.".to_string();
+    for value in values {
+        if hint.chars().count() + value.chars().count() + 2 > width {
+            break;
+        }
+        hint.push_str(if hint.ends_with('.') { " " } else { ", " });
+        hint.push_str(&value);
+    }
+    hint
+}
+
+/// 日付範囲をローカル時刻で表示
+fn format_date_range(range: &DateRange) -> String {
+    let format = |dt: Option<DateTime<Utc>>, open: &str| {
+        dt.map(|dt| {
+            dt.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| open.to_string())
+    };
+    format!("{} → {}", format(range.from, "…"), format(range.to, "now"))
 }
 
 /// エラーメッセージをレンダリング（画面下部にオーバーレイ）
@@ -346,7 +549,12 @@ fn render_error_message(frame: &mut Frame, error: &str, palette: Palette) {
     let error_height = 3;
     let error_y = area.height.saturating_sub(error_height + 3); // フッター（3行）の上
 
-    let error_area = Rect::new(area.x + 1, error_y, area.width.saturating_sub(2), error_height);
+    let error_area = Rect::new(
+        area.x + 1,
+        error_y,
+        area.width.saturating_sub(2),
+        error_height,
+    );
 
     // 背景をクリア
     let clear_block = Block::default().style(Style::default().bg(palette.surface));
@@ -1,7 +1,10 @@
+use std::ops::Range;
+
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::Style,
+    text::{Line, Span},
     widgets::Widget,
 };
 
@@ -30,3 +33,63 @@ impl Widget for LineHighlight {
         buf.set_style(line_area, self.style);
     }
 }
+
+/// 行内のバイト範囲（昇順・重なりなし）にスタイルを重ねる
+pub fn highlight_ranges<'a>(mut line: Line<'a>, ranges: &[(Range<usize>, Style)]) -> Line<'a> {
+    if ranges.is_empty() {
+        return line;
+    }
+    let mut spans = Vec::with_capacity(line.spans.len() + ranges.len() * 2);
+    let mut offset = 0;
+    for span in std::mem::take(&mut line.spans) {
+        let content = span.content.as_ref();
+        let span_end = offset + content.len();
+        let mut cursor = 0;
+        for (range, style) in ranges {
+            let start = range.start.clamp(offset, span_end) - offset;
+            let end = range.end.clamp(offset, span_end) - offset;
+            if start >= end {
+                continue;
+            }
+            if start > cursor {
+                spans.push(Span::styled(content[cursor..start].to_string(), span.style));
+            }
+            spans.push(Span::styled(
+                content[start..end].to_string(),
+                span.style.patch(*style),
+            ));
+            cursor = end;
+        }
+        if cursor == 0 {
+            spans.push(span);
+        } else if cursor < content.len() {
+            spans.push(Span::styled(content[cursor..].to_string(), span.style));
+        }
+        offset = span_end;
+    }
+    line.spans = spans;
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    #[test]
+    fn test_highlight_ranges_across_spans() {
+        let line = Line::from(vec![Span::raw("fix "), Span::raw("bug here")]);
+        let style = Style::default().bg(Color::Yellow);
+        let highlighted = highlight_ranges(line, &[(2..7, style)]);
+
+        let contents: Vec<&str> = highlighted
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(contents, vec!["fi", "x ", "bug", " here"]);
+        assert_eq!(highlighted.spans[1].style.bg, Some(Color::Yellow));
+        assert_eq!(highlighted.spans[2].style.bg, Some(Color::Yellow));
+        assert_eq!(highlighted.spans[3].style.bg, None);
+    }
+}
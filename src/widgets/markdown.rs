@@ -0,0 +1,813 @@
+use std::sync::OnceLock;
+
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::theme::{Palette, Theme, ThemeMode};
+
+/// コードブロック内のタブ幅
+const TAB_WIDTH: usize = 4;
+
+/// Markdown を幅に合わせて折り返したスタイル付きの行に変換
+/// 詳細表示ではメッセージ単位の結果を Model がキャッシュする
+pub fn render_markdown(text: &str, width: u16, theme: Theme) -> Vec<Line<'static>> {
+    MarkdownRenderer::new(width, theme).render(text)
+}
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme_set() -> &'static ThemeSet {
+    static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+    THEME_SET.get_or_init(ThemeSet::load_defaults)
+}
+
+/// 行頭に付ける装飾（1 行目と折り返し行で異なる）
+#[derive(Clone, Default)]
+struct Prefix {
+    first: Vec<Span<'static>>,
+    rest: Vec<Span<'static>>,
+}
+
+/// 表のセル内容（行ごと）
+#[derive(Default)]
+struct TableState {
+    alignments: Vec<Alignment>,
+    header: Option<Vec<Vec<Span<'static>>>>,
+    rows: Vec<Vec<Vec<Span<'static>>>>,
+    row: Vec<Vec<Span<'static>>>,
+    cell: Vec<Span<'static>>,
+}
+
+/// コードブロックの内容
+struct CodeState {
+    lang: String,
+    text: String,
+}
+
+struct MarkdownRenderer {
+    width: usize,
+    theme: Theme,
+    lines: Vec<Line<'static>>,
+    /// 組み立て中のインライン要素
+    spans: Vec<Span<'static>>,
+    styles: Vec<Style>,
+    /// リストの入れ子（番号付きリストは次の番号）
+    lists: Vec<Option<u64>>,
+    /// リスト項目の 1 行目か
+    item_pending: bool,
+    quote_depth: usize,
+    links: Vec<String>,
+    code: Option<CodeState>,
+    table: Option<TableState>,
+    needs_blank: bool,
+}
+
+impl MarkdownRenderer {
+    fn new(width: u16, theme: Theme) -> Self {
+        Self {
+            width: width as usize,
+            theme,
+            lines: Vec::new(),
+            spans: Vec::new(),
+            styles: vec![Style::default().fg(theme.palette.text)],
+            lists: Vec::new(),
+            item_pending: false,
+            quote_depth: 0,
+            links: Vec::new(),
+            code: None,
+            table: None,
+            needs_blank: false,
+        }
+    }
+
+    fn palette(&self) -> Palette {
+        self.theme.palette
+    }
+
+    fn render(mut self, text: &str) -> Vec<Line<'static>> {
+        let options =
+            Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+        for event in Parser::new_ext(text, options) {
+            self.handle(event);
+        }
+        self.flush();
+        self.lines
+    }
+
+    fn handle(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                if let Some(code) = &mut self.code {
+                    code.text.push_str(&text);
+                } else {
+                    self.push_text(&text, self.style());
+                }
+            }
+            Event::Code(code) => {
+                let style = self.style().fg(self.palette().accent_alt);
+                self.push_text(&code, style);
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                let style = Style::default().fg(self.palette().text_dim);
+                for (i, line) in html.lines().enumerate() {
+                    if i > 0 {
+                        self.flush();
+                    }
+                    self.push_text(line, style);
+                }
+            }
+            Event::InlineMath(math) | Event::DisplayMath(math) => {
+                let style = self.style().fg(self.palette().accent_alt);
+                self.push_text(&math, style);
+            }
+            Event::FootnoteReference(label) => {
+                let style = Style::default().fg(self.palette().text_dim);
+                self.push_text(&format!("[^{}]", label), style);
+            }
+            Event::SoftBreak => self.push_text(" ", self.style()),
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.begin_block();
+                let rule = "─".repeat(self.width.max(1));
+                self.lines.push(Line::from(Span::styled(
+                    rule,
+                    Style::default().fg(self.palette().border),
+                )));
+                self.needs_blank = true;
+            }
+            Event::TaskListMarker(checked) => {
+                let marker = if checked { "[x] " } else { "[ ] " };
+                let style = Style::default().fg(self.palette().text_muted);
+                self.push_text(marker, style);
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph => self.begin_block(),
+            Tag::Heading { level, .. } => {
+                self.begin_block();
+                let palette = self.palette();
+                let style = match level {
+                    HeadingLevel::H1 => Style::default()
+                        .fg(palette.accent)
+                        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                    HeadingLevel::H2 => Style::default()
+                        .fg(palette.accent)
+                        .add_modifier(Modifier::BOLD),
+                    _ => Style::default()
+                        .fg(palette.accent_alt)
+                        .add_modifier(Modifier::BOLD),
+                };
+                self.styles.push(style);
+            }
+            Tag::BlockQuote(_) => {
+                self.begin_block();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(kind) => {
+                self.begin_block();
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code = Some(CodeState {
+                    lang,
+                    text: String::new(),
+                });
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.begin_block();
+                } else {
+                    self.flush();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                self.item_pending = true;
+            }
+            Tag::Table(alignments) => {
+                self.begin_block();
+                self.table = Some(TableState {
+                    alignments,
+                    ..Default::default()
+                });
+            }
+            Tag::Emphasis => self.push_modifier(Modifier::ITALIC),
+            Tag::Strong => self.push_modifier(Modifier::BOLD),
+            Tag::Strikethrough => self.push_modifier(Modifier::CROSSED_OUT),
+            Tag::Link { dest_url, .. } => {
+                let style = self
+                    .style()
+                    .fg(self.palette().accent)
+                    .add_modifier(Modifier::UNDERLINED);
+                self.styles.push(style);
+                self.links.push(dest_url.to_string());
+            }
+            Tag::Image { dest_url, .. } => {
+                let style = Style::default().fg(self.palette().text_dim);
+                self.push_text("[image: ", style);
+                self.styles.push(style);
+                self.links.push(dest_url.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => {
+                self.flush();
+                self.needs_blank = true;
+            }
+            TagEnd::Heading(_) => {
+                self.flush();
+                self.styles.pop();
+                self.needs_blank = true;
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.needs_blank = true;
+            }
+            TagEnd::CodeBlock => {
+                if let Some(code) = self.code.take() {
+                    self.render_code_block(&code);
+                }
+                self.needs_blank = true;
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.needs_blank = true;
+                }
+            }
+            TagEnd::Item => {
+                self.flush();
+                self.item_pending = false;
+                if let Some(Some(number)) = self.lists.last_mut() {
+                    *number += 1;
+                }
+                // リスト項目間には空行を入れない
+                self.needs_blank = false;
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.render_table(table);
+                }
+                self.needs_blank = true;
+            }
+            TagEnd::TableHead => {
+                if let Some(table) = &mut self.table {
+                    table.header = Some(std::mem::take(&mut table.row));
+                }
+            }
+            TagEnd::TableRow => {
+                if let Some(table) = &mut self.table {
+                    let row = std::mem::take(&mut table.row);
+                    table.rows.push(row);
+                }
+            }
+            TagEnd::TableCell => {
+                if let Some(table) = &mut self.table {
+                    let cell = std::mem::take(&mut table.cell);
+                    table.row.push(cell);
+                }
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link => {
+                self.styles.pop();
+                if let Some(url) = self.links.pop() {
+                    let text: String = self.spans.iter().map(|s| s.content.as_ref()).collect();
+                    if !url.is_empty() && !text.ends_with(url.as_str()) {
+                        let style = Style::default().fg(self.palette().text_dim);
+                        self.push_text(&format!(" ({})", url), style);
+                    }
+                }
+            }
+            TagEnd::Image => {
+                self.styles.pop();
+                self.links.pop();
+                let style = Style::default().fg(self.palette().text_dim);
+                self.push_text("]", style);
+            }
+            _ => {}
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn push_modifier(&mut self, modifier: Modifier) {
+        let style = self.style().add_modifier(modifier);
+        self.styles.push(style);
+    }
+
+    fn push_text(&mut self, text: &str, style: Style) {
+        if text.is_empty() {
+            return;
+        }
+        if let Some(table) = &mut self.table {
+            table.cell.push(Span::styled(text.to_string(), style));
+        } else {
+            self.spans.push(Span::styled(text.to_string(), style));
+        }
+    }
+
+    /// ブロック要素の開始（必要なら前のブロックとの間に空行を入れる）
+    fn begin_block(&mut self) {
+        self.flush();
+        if self.needs_blank && !self.lines.is_empty() {
+            let prefix = self.prefix().rest;
+            self.lines.push(Line::from(prefix));
+        }
+        self.needs_blank = false;
+    }
+
+    /// 引用・リストの入れ子に応じた行頭装飾
+    fn prefix(&mut self) -> Prefix {
+        let mut prefix = Prefix::default();
+        if self.quote_depth > 0 {
+            let quote = Span::styled(
+                "│ ".repeat(self.quote_depth),
+                Style::default().fg(self.palette().text_dim),
+            );
+            prefix.first.push(quote.clone());
+            prefix.rest.push(quote);
+        }
+        if let Some(list) = self.lists.last() {
+            let indent = "  ".repeat(self.lists.len() - 1);
+            let marker = match list {
+                Some(number) => format!("{}. ", number),
+                None => "• ".to_string(),
+            };
+            let marker_width = marker.width();
+            if self.item_pending {
+                prefix.first.push(Span::raw(indent.clone()));
+                prefix.first.push(Span::styled(
+                    marker,
+                    Style::default().fg(self.palette().accent_alt),
+                ));
+            } else {
+                prefix
+                    .first
+                    .push(Span::raw(format!("{}{}", indent, " ".repeat(marker_width))));
+            }
+            prefix
+                .rest
+                .push(Span::raw(format!("{}{}", indent, " ".repeat(marker_width))));
+        }
+        prefix
+    }
+
+    /// 組み立て中のインライン要素を折り返して行に確定
+    fn flush(&mut self) {
+        if self.spans.is_empty() {
+            return;
+        }
+        let spans = std::mem::take(&mut self.spans);
+        let prefix = self.prefix();
+        self.item_pending = false;
+        let wrapped = wrap_spans(spans, &prefix, self.width);
+        self.lines.extend(wrapped);
+    }
+
+    fn render_code_block(&mut self, code: &CodeState) {
+        let palette = self.palette();
+        let border = Style::default().fg(palette.border);
+        let base = self.prefix().rest;
+        let label = if code.lang.is_empty() {
+            "┌─".to_string()
+        } else {
+            format!("┌─ {}", code.lang)
+        };
+        let mut header = base.clone();
+        header.push(Span::styled(label, border));
+        self.lines.push(Line::from(header));
+
+        let gutter = Prefix {
+            first: [base.clone(), vec![Span::styled("│ ", border)]].concat(),
+            rest: [base.clone(), vec![Span::styled("│ ", border)]].concat(),
+        };
+        let text = code.text.replace('\t', &" ".repeat(TAB_WIDTH));
+        for spans in highlight_code(&text, &code.lang, self.theme) {
+            self.lines
+                .extend(hard_wrap_spans(spans, &gutter, self.width));
+        }
+
+        let mut footer = base;
+        footer.push(Span::styled("└─", border));
+        self.lines.push(Line::from(footer));
+    }
+
+    fn render_table(&mut self, table: TableState) {
+        let palette = self.palette();
+        let border = Style::default().fg(palette.border);
+        let base = self.prefix().rest;
+        let base_width: usize = base.iter().map(|span| span.content.width()).sum();
+
+        let header = table.header.unwrap_or_default();
+        let columns = table
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain([header.len(), table.alignments.len()])
+            .max()
+            .unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+        let cell_width = |cell: &Vec<Span<'static>>| -> usize {
+            cell.iter().map(|span| span.content.width()).sum()
+        };
+        let mut widths = vec![1usize; columns];
+        for row in std::iter::once(&header).chain(&table.rows) {
+            for (column, cell) in row.iter().enumerate() {
+                widths[column] = widths[column].max(cell_width(cell));
+            }
+        }
+
+        // 区切り " │ " を含めて幅に収まるよう、最も広い列から縮める
+        let available = self
+            .width
+            .saturating_sub(base_width + (columns - 1) * 3)
+            .max(columns);
+        while widths.iter().sum::<usize>() > available {
+            let Some(widest) = (0..columns).max_by_key(|&column| widths[column]) else {
+                break;
+            };
+            if widths[widest] <= 1 {
+                break;
+            }
+            widths[widest] -= 1;
+        }
+
+        let render_row = |row: &[Vec<Span<'static>>], bold: bool| -> Line<'static> {
+            let mut spans = base.clone();
+            for (column, width) in widths.iter().enumerate() {
+                if column > 0 {
+                    spans.push(Span::styled(" │ ", border));
+                }
+                let cell = row.get(column).cloned().unwrap_or_default();
+                let alignment = table
+                    .alignments
+                    .get(column)
+                    .copied()
+                    .unwrap_or(Alignment::None);
+                let mut cell = truncate_spans(cell, *width);
+                if bold {
+                    cell = cell
+                        .into_iter()
+                        .map(|span| {
+                            let style = span.style.add_modifier(Modifier::BOLD);
+                            span.style(style)
+                        })
+                        .collect();
+                }
+                let padding = width.saturating_sub(cell_width(&cell));
+                let (left, right) = match alignment {
+                    Alignment::Right => (padding, 0),
+                    Alignment::Center => (padding / 2, padding - padding / 2),
+                    Alignment::Left | Alignment::None => (0, padding),
+                };
+                if left > 0 {
+                    spans.push(Span::raw(" ".repeat(left)));
+                }
+                spans.extend(cell);
+                if right > 0 && column + 1 < columns {
+                    spans.push(Span::raw(" ".repeat(right)));
+                }
+            }
+            Line::from(spans)
+        };
+
+        if !header.is_empty() {
+            self.lines.push(render_row(&header, true));
+            let mut separator = base.clone();
+            let rule = widths
+                .iter()
+                .map(|width| "─".repeat(*width))
+                .collect::<Vec<_>>()
+                .join("─┼─");
+            separator.push(Span::styled(rule, border));
+            self.lines.push(Line::from(separator));
+        }
+        for row in &table.rows {
+            self.lines.push(render_row(row, false));
+        }
+    }
+}
+
+/// コードを言語に応じてハイライト（未知の言語はそのまま）
+fn highlight_code(code: &str, lang: &str, theme: Theme) -> Vec<Vec<Span<'static>>> {
+    let plain = Style::default().fg(theme.palette.text_muted);
+    let syntaxes = syntax_set();
+    let syntax = (!lang.is_empty())
+        .then(|| syntaxes.find_syntax_by_token(lang))
+        .flatten();
+    let Some(syntax) = syntax else {
+        return code
+            .lines()
+            .map(|line| vec![Span::styled(line.to_string(), plain)])
+            .collect();
+    };
+
+    let theme_name = match theme.mode {
+        ThemeMode::Dark => "base16-ocean.dark",
+        ThemeMode::Light => "InspiredGitHub",
+    };
+    let Some(syntax_theme) = theme_set().themes.get(theme_name) else {
+        return code
+            .lines()
+            .map(|line| vec![Span::styled(line.to_string(), plain)])
+            .collect();
+    };
+
+    let mut highlighter = HighlightLines::new(syntax, syntax_theme);
+    LinesWithEndings::from(code)
+        .map(|line| match highlighter.highlight_line(line, syntaxes) {
+            Ok(ranges) => ranges
+                .into_iter()
+                .filter_map(|(style, text)| {
+                    let text = text.trim_end_matches(['\n', '\r']);
+                    if text.is_empty() {
+                        return None;
+                    }
+                    let fg = theme.color(Color::Rgb(
+                        style.foreground.r,
+                        style.foreground.g,
+                        style.foreground.b,
+                    ));
+                    Some(Span::styled(text.to_string(), Style::default().fg(fg)))
+                })
+                .collect(),
+            Err(_) => vec![Span::styled(
+                line.trim_end_matches(['\n', '\r']).to_string(),
+                plain,
+            )],
+        })
+        .collect()
+}
+
+/// 単語単位で折り返す（単語が 1 行に収まらない場合は文字単位）
+fn wrap_spans(spans: Vec<Span<'static>>, prefix: &Prefix, width: usize) -> Vec<Line<'static>> {
+    let first_width: usize = prefix.first.iter().map(|span| span.content.width()).sum();
+    let rest_width: usize = prefix.rest.iter().map(|span| span.content.width()).sum();
+
+    let mut lines = Vec::new();
+    let mut current = prefix.first.clone();
+    let mut used = 0usize;
+    let mut available = width.saturating_sub(first_width).max(1);
+
+    let mut new_line =
+        |current: &mut Vec<Span<'static>>, used: &mut usize, available: &mut usize| {
+            // 行末の空白を落とす（行頭装飾は残す）
+            if *used > 0
+                && let Some(last) = current.last_mut()
+            {
+                let trimmed = last.content.trim_end().len();
+                last.content.to_mut().truncate(trimmed);
+            }
+            lines.push(Line::from(std::mem::replace(current, prefix.rest.clone())));
+            *used = 0;
+            *available = width.saturating_sub(rest_width).max(1);
+        };
+
+    for span in spans {
+        for word in split_words(&span.content) {
+            let word_width = word.width();
+            if used + word_width <= available {
+                push_merged(&mut current, word, span.style);
+                used += word_width;
+                continue;
+            }
+            if word.trim().is_empty() {
+                // 行末の空白は捨てて改行
+                new_line(&mut current, &mut used, &mut available);
+                continue;
+            }
+            if used > 0 && word_width <= width.saturating_sub(rest_width).max(1) {
+                new_line(&mut current, &mut used, &mut available);
+                push_merged(&mut current, word, span.style);
+                used += word_width;
+                continue;
+            }
+            for ch in word.chars() {
+                let ch_width = ch.width().unwrap_or(0);
+                if used + ch_width > available && used > 0 {
+                    new_line(&mut current, &mut used, &mut available);
+                }
+                push_merged(&mut current, &ch.to_string(), span.style);
+                used += ch_width;
+            }
+        }
+    }
+    lines.push(Line::from(current));
+    lines
+}
+
+/// 文字単位で折り返す（コードブロック用）
+fn hard_wrap_spans(spans: Vec<Span<'static>>, prefix: &Prefix, width: usize) -> Vec<Line<'static>> {
+    let prefix_width: usize = prefix.rest.iter().map(|span| span.content.width()).sum();
+    let available = width.saturating_sub(prefix_width).max(1);
+
+    let mut lines = Vec::new();
+    let mut current = prefix.first.clone();
+    let mut used = 0usize;
+    for span in spans {
+        for ch in span.content.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if used + ch_width > available && used > 0 {
+                lines.push(Line::from(std::mem::replace(
+                    &mut current,
+                    prefix.rest.clone(),
+                )));
+                used = 0;
+            }
+            push_merged(&mut current, &ch.to_string(), span.style);
+            used += ch_width;
+        }
+    }
+    lines.push(Line::from(current));
+    lines
+}
+
+/// 空白と非空白の連続に分割
+fn split_words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut in_space = None;
+    for (index, ch) in text.char_indices() {
+        let is_space = ch.is_whitespace();
+        if in_space.is_some_and(|previous| previous != is_space) {
+            words.push(&text[start..index]);
+            start = index;
+        }
+        in_space = Some(is_space);
+    }
+    if start < text.len() {
+        words.push(&text[start..]);
+    }
+    words
+}
+
+/// 直前の span と同じスタイルなら連結して追加
+fn push_merged(spans: &mut Vec<Span<'static>>, text: &str, style: Style) {
+    if let Some(last) = spans.last_mut()
+        && last.style == style
+    {
+        last.content.to_mut().push_str(text);
+        return;
+    }
+    spans.push(Span::styled(text.to_string(), style));
+}
+
+/// 表示幅で切り詰める（切り詰めた場合は … を付与）
+fn truncate_spans(spans: Vec<Span<'static>>, width: usize) -> Vec<Span<'static>> {
+    let total: usize = spans.iter().map(|span| span.content.width()).sum();
+    if total <= width {
+        return spans;
+    }
+    let limit = width.saturating_sub(1);
+    let mut used = 0;
+    let mut result = Vec::new();
+    let mut last_style = Style::default();
+    'outer: for span in spans {
+        last_style = span.style;
+        for ch in span.content.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if used + ch_width > limit {
+                break 'outer;
+            }
+            push_merged(&mut result, &ch.to_string(), span.style);
+            used += ch_width;
+        }
+    }
+    push_merged(&mut result, "…", last_style);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render_markdown_inline_styles() {
+        let theme = Theme::default();
+        let lines = render_markdown("# Title\n\nSome **bold** and *italic* `code`.", 80, theme);
+        assert_eq!(
+            plain(&lines),
+            vec!["Title", "", "Some bold and italic code."]
+        );
+        assert!(
+            lines[0].spans[0]
+                .style
+                .add_modifier
+                .contains(Modifier::BOLD)
+        );
+
+        let body = &lines[2].spans;
+        let bold = body.iter().find(|span| span.content == "bold").unwrap();
+        assert!(bold.style.add_modifier.contains(Modifier::BOLD));
+        let italic = body.iter().find(|span| span.content == "italic").unwrap();
+        assert!(italic.style.add_modifier.contains(Modifier::ITALIC));
+        let code = body.iter().find(|span| span.content == "code").unwrap();
+        assert_eq!(code.style.fg, Some(theme.palette.accent_alt));
+    }
+
+    #[test]
+    fn test_render_markdown_lists_wrap_with_indent() {
+        let text = "- first item that is long enough to wrap\n- second\n  1. nested";
+        let lines = render_markdown(text, 20, Theme::default());
+        assert_eq!(
+            plain(&lines),
+            vec![
+                "• first item that is",
+                "  long enough to",
+                "  wrap",
+                "• second",
+                "  1. nested",
+            ]
+        );
+        assert!(lines.iter().all(|line| line.width() <= 20));
+    }
+
+    #[test]
+    fn test_render_markdown_table() {
+        let text = "| Name | Cost |\n|------|-----:|\n| opus | 1.5 |\n| haiku | 0.25 |";
+        let lines = render_markdown(text, 80, Theme::default());
+        assert_eq!(
+            plain(&lines),
+            vec![
+                "Name  │ Cost",
+                "──────┼─────",
+                "opus  │  1.5",
+                "haiku │ 0.25",
+            ]
+        );
+
+        let narrow = render_markdown(text, 10, Theme::default());
+        assert!(narrow.iter().all(|line| line.width() <= 10));
+    }
+
+    #[test]
+    fn test_render_markdown_code_block_highlight() {
+        let text = "```rust\nfn main() {\n    let x = 1;\n}\n```";
+        let lines = render_markdown(text, 80, Theme::default());
+        let texts = plain(&lines);
+        assert_eq!(texts[0], "┌─ rust");
+        assert_eq!(texts[1], "│ fn main() {");
+        assert_eq!(texts[3], "│ }");
+        assert_eq!(texts[4], "└─");
+        // キーワードと本文で色が分かれる
+        let colors: std::collections::HashSet<_> =
+            lines[1].spans.iter().map(|span| span.style.fg).collect();
+        assert!(colors.len() > 2);
+
+        let unknown = render_markdown("```nosuchlang\nplain text\n```", 80, Theme::default());
+        assert_eq!(plain(&unknown)[1], "│ plain text");
+    }
+
+    #[test]
+    fn test_render_markdown_code_block_hard_wrap() {
+        let text = "```\nabcdefghijklmnop\n```";
+        let lines = render_markdown(text, 10, Theme::default());
+        assert_eq!(plain(&lines), vec!["┌─", "│ abcdefgh", "│ ijklmnop", "└─"]);
+    }
+}
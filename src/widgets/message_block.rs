@@ -6,11 +6,20 @@ use ratatui::{
     widgets::Widget,
 };
 
-use crate::domain::SessionEntry;
 use crate::domain::billing::{Currency, estimate_cost_usd, format_tokens};
+use crate::domain::{
+    DiffLineKind, ExchangeRates, FileDiff, SessionEntry, ToolResult, ToolResults,
+    summarize_tool_input,
+};
 use crate::theme::Theme;
+use crate::widgets::markdown::render_markdown;
 use unicode_width::UnicodeWidthStr;
 
+/// 折りたたみ時に表示するツール結果の行数
+const TOOL_RESULT_PREVIEW_LINES: usize = 3;
+/// 折りたたみ時に表示する差分の行数
+const DIFF_PREVIEW_LINES: usize = 12;
+
 /// メッセージブロックのスタイル定義
 pub struct MessageStyles {
     palette: crate::theme::Palette,
@@ -51,6 +60,40 @@ impl MessageStyles {
     pub fn tool_use(&self) -> Style {
         Style::default().fg(self.palette.accent_alt)
     }
+
+    /// thinking ブロックのスタイル
+    pub fn thinking(&self) -> Style {
+        Style::default()
+            .fg(self.palette.text_dim)
+            .add_modifier(Modifier::ITALIC)
+    }
+
+    /// ツール入力サマリーのスタイル
+    pub fn tool_summary(&self) -> Style {
+        Style::default().fg(self.palette.text_muted)
+    }
+
+    /// 差分行のスタイル
+    pub fn diff_line(&self, kind: DiffLineKind) -> Style {
+        match kind {
+            DiffLineKind::FileHeader => Style::default()
+                .fg(self.palette.text)
+                .add_modifier(Modifier::BOLD),
+            DiffLineKind::HunkHeader => Style::default().fg(self.palette.accent_alt),
+            DiffLineKind::Context => Style::default().fg(self.palette.text_muted),
+            DiffLineKind::Added => Style::default().fg(self.palette.success),
+            DiffLineKind::Removed => Style::default().fg(self.palette.error),
+        }
+    }
+
+    /// ツール結果のスタイル
+    pub fn tool_result(&self, is_error: bool) -> Style {
+        if is_error {
+            Style::default().fg(self.palette.error)
+        } else {
+            Style::default().fg(self.palette.text_dim)
+        }
+    }
 }
 
 /// メッセージブロックウィジェット
@@ -58,7 +101,13 @@ pub struct MessageBlock<'a> {
     entry: &'a SessionEntry,
     width: u16,
     currency: Currency,
+    exchange_rates: Option<&'a ExchangeRates>,
     theme: Theme,
+    tool_results: Option<&'a ToolResults>,
+    tool_output_expanded: bool,
+    tool_output_key: Option<&'a str>,
+    thinking_expanded: bool,
+    thinking_key: Option<&'a str>,
 }
 
 impl<'a> MessageBlock<'a> {
@@ -68,10 +117,52 @@ impl<'a> MessageBlock<'a> {
             entry,
             width,
             currency,
+            exchange_rates: None,
             theme,
+            tool_results: None,
+            tool_output_expanded: false,
+            tool_output_key: None,
+            thinking_expanded: false,
+            thinking_key: None,
         }
     }
 
+    /// 為替レート表を設定（未設定なら既定のレート）
+    pub fn exchange_rates(mut self, rates: &'a ExchangeRates) -> Self {
+        self.exchange_rates = Some(rates);
+        self
+    }
+
+    /// ツール呼び出しと対応付けるツール実行結果を設定
+    pub fn tool_results(mut self, results: &'a ToolResults) -> Self {
+        self.tool_results = Some(results);
+        self
+    }
+
+    /// ツール結果を全行表示するか（false の場合は先頭のみ）
+    pub fn tool_output_expanded(mut self, expanded: bool) -> Self {
+        self.tool_output_expanded = expanded;
+        self
+    }
+
+    /// ツール結果を展開するキー（折りたたみ時の案内に表示、None なら案内なし）
+    pub fn tool_output_key(mut self, key: Option<&'a str>) -> Self {
+        self.tool_output_key = key;
+        self
+    }
+
+    /// thinking ブロックを全文表示するか（false の場合は行数のみ）
+    pub fn thinking_expanded(mut self, expanded: bool) -> Self {
+        self.thinking_expanded = expanded;
+        self
+    }
+
+    /// thinking ブロックを展開するキー（折りたたみ時の案内に表示、None なら案内なし）
+    pub fn thinking_key(mut self, key: Option<&'a str>) -> Self {
+        self.thinking_key = key;
+        self
+    }
+
     /// メッセージをレンダリング用の行に変換
     pub fn to_lines(&self) -> Vec<Line<'a>> {
         let mut lines = Vec::new();
@@ -80,13 +171,26 @@ impl<'a> MessageBlock<'a> {
         // ロールヘッダー
         lines.push(self.render_header());
 
-        // メッセージ本文
-        if let Some(text) = self.entry.display_text() {
-            for line in text.lines() {
-                lines.push(Line::from(Span::styled(
-                    line.to_string(),
-                    Style::default().fg(palette.text),
-                )));
+        // thinking ブロック（あれば）
+        let display_text = self.entry.display_text();
+        if let Some(thinking_lines) = self.render_thinking() {
+            lines.extend(thinking_lines);
+            if display_text.is_some() {
+                lines.push(Line::from(""));
+            }
+        }
+
+        // メッセージ本文（アシスタントは Markdown としてレンダリング）
+        if let Some(text) = display_text {
+            if self.entry.is_assistant() {
+                lines.extend(render_markdown(&text, self.width, self.theme));
+            } else {
+                for line in text.lines() {
+                    lines.push(Line::from(Span::styled(
+                        line.to_string(),
+                        Style::default().fg(palette.text),
+                    )));
+                }
             }
         }
 
@@ -163,6 +267,29 @@ impl<'a> MessageBlock<'a> {
         Line::from(spans)
     }
 
+    /// thinking ブロックをレンダリング（折りたたみ時は行数のみ）
+    fn render_thinking(&self) -> Option<Vec<Line<'a>>> {
+        let thinking = self.entry.thinking_text()?;
+        let style = MessageStyles::new(self.theme).thinking();
+        let line_count = thinking.lines().count();
+
+        if !self.thinking_expanded {
+            let text = match self.thinking_key {
+                Some(key) => format!("  ✻ Thinking ({} lines, {} to expand)", line_count, key),
+                None => format!("  ✻ Thinking ({} lines)", line_count),
+            };
+            return Some(vec![Line::from(Span::styled(text, style))]);
+        }
+
+        let mut lines = vec![Line::from(Span::styled("  ✻ Thinking", style))];
+        lines.extend(
+            thinking
+                .lines()
+                .map(|line| Line::from(Span::styled(format!("    {}", line), style))),
+        );
+        Some(lines)
+    }
+
     /// ツール呼び出し情報をレンダリング
     fn render_tool_use(&self) -> Option<Vec<Line<'a>>> {
         let message = self.entry.message.as_ref()?;
@@ -173,19 +300,102 @@ impl<'a> MessageBlock<'a> {
         }
 
         let mut lines = Vec::new();
-        lines.push(Line::from(""));
+        // 本文がある場合のみ空行で区切る
+        if self.entry.display_text().is_some() {
+            lines.push(Line::from(""));
+        }
         let styles = MessageStyles::new(self.theme);
 
-        for (_id, name, _input) in tool_uses {
-            lines.push(Line::from(vec![
+        for (id, name, input) in tool_uses {
+            let mut spans = vec![
                 Span::styled("  ⚙ ", styles.tool_use()),
-                Span::styled(name.to_string(), styles.tool_use()),
-            ]));
+                Span::styled(
+                    name.to_string(),
+                    styles.tool_use().add_modifier(Modifier::BOLD),
+                ),
+            ];
+            if let Some(summary) = summarize_tool_input(name, input) {
+                spans.push(Span::styled(
+                    format!("  {}", summary),
+                    styles.tool_summary(),
+                ));
+            }
+            lines.push(Line::from(spans));
+
+            if let Some(diff) = FileDiff::from_tool_use(name, input) {
+                lines.extend(self.render_diff(&diff, &styles));
+            }
+            if let Some(result) = self.tool_results.and_then(|results| results.get(id)) {
+                lines.extend(self.render_tool_result(result, &styles));
+            }
         }
 
         Some(lines)
     }
 
+    /// ツール実行結果をレンダリング（折りたたみ時は先頭数行のみ）
+    fn render_tool_result(&self, result: &ToolResult, styles: &MessageStyles) -> Vec<Line<'a>> {
+        let style = styles.tool_result(result.is_error);
+        let marker = if result.is_error {
+            "    ✗ "
+        } else {
+            "    │ "
+        };
+        let result_lines: Vec<&str> = result.text.lines().collect();
+        if result_lines.is_empty() {
+            return vec![Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled("(no output)", styles.separator()),
+            ])];
+        }
+
+        let lines = result_lines
+            .iter()
+            .map(|line| {
+                Line::from(vec![
+                    Span::styled(marker, style),
+                    Span::styled(line.to_string(), style),
+                ])
+            })
+            .collect();
+        self.collapse(lines, TOOL_RESULT_PREVIEW_LINES, styles)
+    }
+
+    /// ファイル変更の差分をレンダリング（折りたたみ時は先頭のみ）
+    fn render_diff(&self, diff: &FileDiff, styles: &MessageStyles) -> Vec<Line<'a>> {
+        let lines = diff
+            .lines
+            .iter()
+            .map(|line| {
+                Line::from(Span::styled(
+                    format!("    {}", line.text),
+                    styles.diff_line(line.kind),
+                ))
+            })
+            .collect();
+        self.collapse(lines, DIFF_PREVIEW_LINES, styles)
+    }
+
+    /// 折りたたみ時は先頭 preview 行に絞り、残りの行数を表示
+    fn collapse(
+        &self,
+        mut lines: Vec<Line<'a>>,
+        preview: usize,
+        styles: &MessageStyles,
+    ) -> Vec<Line<'a>> {
+        if self.tool_output_expanded || lines.len() <= preview {
+            return lines;
+        }
+        let hidden = lines.len() - preview;
+        lines.truncate(preview);
+        let text = match self.tool_output_key {
+            Some(key) => format!("    … {} more lines ({} to expand)", hidden, key),
+            None => format!("    … {} more lines", hidden),
+        };
+        lines.push(Line::from(Span::styled(text, styles.separator())));
+        lines
+    }
+
     fn usage_meta(&self) -> Option<UsageMeta> {
         let message = self.entry.message.as_ref()?;
         let usage = message.usage.as_ref()?;
@@ -198,8 +408,16 @@ impl<'a> MessageBlock<'a> {
         let cost_text = message
             .model
             .as_deref()
-            .and_then(|model| estimate_cost_usd(model, usage))
-            .map(|usd| self.currency.format_cost(usd))
+            .and_then(|model| estimate_cost_usd(model, usage, self.entry.datetime()))
+            .map(|usd| {
+                let at = self.entry.datetime();
+                match self.exchange_rates {
+                    Some(rates) => self.currency.format_cost(usd, rates, at),
+                    None => self
+                        .currency
+                        .format_cost(usd, &ExchangeRates::default(), at),
+                }
+            })
             .unwrap_or_else(|| "n/a".to_string());
         let cost_is_na = cost_text == "n/a";
 
@@ -259,7 +477,7 @@ mod tests {
     #[test]
     fn test_message_block_user() {
         let entry = create_test_entry("user");
-        let block = MessageBlock::new(&entry, 80, Currency::Usd, Theme::default());
+        let block = MessageBlock::new(&entry, 80, Currency::USD, Theme::default());
         let lines = block.to_lines();
 
         assert!(!lines.is_empty());
@@ -268,9 +486,172 @@ mod tests {
     #[test]
     fn test_message_block_assistant() {
         let entry = create_test_entry("assistant");
-        let block = MessageBlock::new(&entry, 80, Currency::Usd, Theme::default());
+        let block = MessageBlock::new(&entry, 80, Currency::USD, Theme::default());
         let lines = block.to_lines();
 
         assert!(!lines.is_empty());
     }
+
+    #[test]
+    fn test_message_block_tool_use_with_result() {
+        use crate::domain::message::{ContentBlock, Message, MessageContent};
+        use serde_json::json;
+
+        let entry = SessionEntry {
+            entry_type: Some("assistant".to_string()),
+            message: Some(Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Blocks(vec![ContentBlock::ToolUse {
+                    id: "toolu_1".to_string(),
+                    name: "Bash".to_string(),
+                    input: json!({"command": "cargo test"}),
+                }]),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        };
+        let mut results = ToolResults::new();
+        results.insert(
+            "toolu_1".to_string(),
+            ToolResult {
+                text: "line 1\nline 2\nline 3\nline 4\nline 5".to_string(),
+                is_error: true,
+            },
+        );
+        let theme = Theme::default();
+        let plain = |line: &Line| -> String {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        };
+
+        let lines = MessageBlock::new(&entry, 80, Currency::USD, theme)
+            .tool_results(&results)
+            .tool_output_key(Some("Ctrl+o"))
+            .to_lines();
+        // ヘッダー, ツール行, 結果 3 行, 残り行数, 空行
+        assert_eq!(lines.len(), 7);
+        assert_eq!(plain(&lines[1]), "  ⚙ Bash  cargo test");
+        assert_eq!(plain(&lines[2]), "    ✗ line 1");
+        assert_eq!(lines[2].spans[1].style.fg, Some(theme.palette.error));
+        assert_eq!(plain(&lines[5]), "    … 2 more lines (Ctrl+o to expand)");
+
+        // キーが割り当てられていなければ案内しない
+        let unbound = MessageBlock::new(&entry, 80, Currency::USD, theme)
+            .tool_results(&results)
+            .to_lines();
+        assert_eq!(plain(&unbound[5]), "    … 2 more lines");
+
+        let expanded = MessageBlock::new(&entry, 80, Currency::USD, theme)
+            .tool_results(&results)
+            .tool_output_expanded(true)
+            .to_lines();
+        assert_eq!(expanded.len(), 8);
+        assert_eq!(plain(&expanded[6]), "    ✗ line 5");
+    }
+
+    #[test]
+    fn test_message_block_edit_diff() {
+        use crate::domain::message::{ContentBlock, Message, MessageContent};
+        use serde_json::json;
+
+        let entry = SessionEntry {
+            entry_type: Some("assistant".to_string()),
+            message: Some(Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Blocks(vec![ContentBlock::ToolUse {
+                    id: "toolu_1".to_string(),
+                    name: "Edit".to_string(),
+                    input: json!({
+                        "file_path": "/src/lib.rs",
+                        "old_string": "old",
+                        "new_string": "new",
+                    }),
+                }]),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        };
+        let theme = Theme::default();
+        let lines = MessageBlock::new(&entry, 80, Currency::USD, theme).to_lines();
+
+        let diff_lines: Vec<(String, Option<ratatui::style::Color>)> = lines[2..7]
+            .iter()
+            .map(|line| (line.spans[0].content.to_string(), line.spans[0].style.fg))
+            .collect();
+        assert_eq!(
+            diff_lines,
+            vec![
+                ("    --- /src/lib.rs".to_string(), Some(theme.palette.text)),
+                ("    +++ /src/lib.rs".to_string(), Some(theme.palette.text)),
+                (
+                    "    @@ -1 +1 @@".to_string(),
+                    Some(theme.palette.accent_alt)
+                ),
+                ("    -old".to_string(), Some(theme.palette.error)),
+                ("    +new".to_string(), Some(theme.palette.success)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_message_block_thinking() {
+        use crate::domain::message::{ContentBlock, Message, MessageContent};
+
+        let entry = SessionEntry {
+            entry_type: Some("assistant".to_string()),
+            message: Some(Message {
+                role: "assistant".to_string(),
+                content: MessageContent::Blocks(vec![
+                    ContentBlock::Thinking {
+                        thinking: "first\nsecond".to_string(),
+                    },
+                    ContentBlock::Text {
+                        text: "Answer".to_string(),
+                    },
+                ]),
+                model: None,
+                id: None,
+                stop_reason: None,
+                usage: None,
+            }),
+            ..Default::default()
+        };
+        let theme = Theme::default();
+
+        let collapsed = MessageBlock::new(&entry, 80, Currency::USD, theme)
+            .thinking_key(Some("T"))
+            .to_lines();
+        // ヘッダー, thinking 要約, 空行, 本文, 空行
+        assert_eq!(collapsed.len(), 5);
+        assert_eq!(
+            collapsed[1].spans[0].content,
+            "  ✻ Thinking (2 lines, T to expand)"
+        );
+        assert!(
+            collapsed[1].spans[0]
+                .style
+                .add_modifier
+                .contains(Modifier::ITALIC)
+        );
+        assert_eq!(collapsed[3].spans[0].content, "Answer");
+
+        // キーが割り当てられていなければ案内しない
+        let unbound = MessageBlock::new(&entry, 80, Currency::USD, theme).to_lines();
+        assert_eq!(unbound[1].spans[0].content, "  ✻ Thinking (2 lines)");
+
+        let expanded = MessageBlock::new(&entry, 80, Currency::USD, theme)
+            .thinking_expanded(true)
+            .to_lines();
+        assert_eq!(expanded.len(), 7);
+        assert_eq!(expanded[2].spans[0].content, "    first");
+        assert_eq!(expanded[2].spans[0].style.fg, Some(theme.palette.text_dim));
+    }
 }
@@ -1,12 +1,14 @@
-pub mod message_block;
 pub mod line_highlight;
+pub mod markdown;
+pub mod message_block;
 pub mod project_tree;
 pub mod search_bar;
 pub mod session_table;
 pub mod status_bar;
 
+pub use line_highlight::{LineHighlight, highlight_ranges};
+pub use markdown::render_markdown;
 pub use message_block::MessageBlock;
-pub use line_highlight::LineHighlight;
 pub use project_tree::{ProjectTree, ProjectTreeState};
 pub use search_bar::SearchBar;
 pub use session_table::{SessionTable, SessionTableState};
@@ -8,6 +8,7 @@ use ratatui::{
     widgets::{Block, StatefulWidget, Widget},
 };
 
+use crate::search::ContentSearchResult;
 use crate::tea::{SessionSource, TreeItem, TreeNodeKind};
 use crate::theme::Theme;
 
@@ -57,6 +58,8 @@ pub struct ProjectTree<'a> {
     style: Style,
     /// 選択行のスタイル
     highlight_style: Style,
+    /// 本文検索のマッチ数
+    content_hits: Option<&'a ContentSearchResult>,
     /// テーマ
     theme: Theme,
 }
@@ -74,6 +77,7 @@ impl<'a> ProjectTree<'a> {
                 .bg(palette.selection_bg)
                 .fg(palette.selection_fg)
                 .add_modifier(Modifier::BOLD),
+            content_hits: None,
             theme,
         }
     }
@@ -84,6 +88,12 @@ impl<'a> ProjectTree<'a> {
         self
     }
 
+    /// 本文検索のマッチ数を設定（セッション行に表示）
+    pub fn content_hits(mut self, result: &'a ContentSearchResult) -> Self {
+        self.content_hits = Some(result);
+        self
+    }
+
     /// 通常行のスタイルを設定
     #[allow(dead_code)]
     pub fn style(mut self, style: Style) -> Self {
@@ -233,11 +243,31 @@ impl ProjectTree<'_> {
         let label_width = 8;
         let label = format!("{:width$}", label_text, width = label_width);
 
+        // 読み込み元ディレクトリ（複数指定時のみ）
+        let root_str = item
+            .session
+            .as_ref()
+            .and_then(|s| s.root.as_ref())
+            .map(|root| format!(" {}", root))
+            .unwrap_or_default();
+
+        // 本文検索のマッチ数
+        let hits_str = match (self.content_hits, item.session.as_ref()) {
+            (Some(result), Some(session)) => {
+                let count = result.hit_count(&session.key());
+                let unit = if count == 1 { "hit" } else { "hits" };
+                format!(" {} {}", count, unit)
+            }
+            _ => String::new(),
+        };
+
         // 表示幅を計算（文字単位）
         let fixed_width = indent.chars().count()
             + time_str.chars().count()
             + label_separator.chars().count()
             + label_width
+            + root_str.chars().count()
+            + hits_str.chars().count()
             + separator.chars().count();
         let display_width = width.saturating_sub(fixed_width);
 
@@ -249,6 +279,8 @@ impl ProjectTree<'_> {
             Span::styled(time_str.to_string(), Style::default().fg(palette.text_dim)),
             Span::styled(label_separator, Style::default().fg(palette.text_dim)),
             Span::styled(label, label_style),
+            Span::styled(root_str, Style::default().fg(palette.text_muted)),
+            Span::styled(hits_str, Style::default().fg(palette.warning)),
             Span::styled(separator, Style::default().fg(palette.text_dim)),
             Span::styled(display_text, Style::default().fg(palette.text)),
         ])
@@ -6,6 +6,7 @@ use ratatui::{
     widgets::{Block, Paragraph, Widget},
 };
 
+use crate::search::{QueryParseError, SearchScope};
 use crate::theme::Theme;
 
 /// 検索バーウィジェット
@@ -14,6 +15,10 @@ pub struct SearchBar<'a> {
     query: &'a str,
     /// カーソル表示
     cursor_visible: bool,
+    /// 検索範囲
+    scope: SearchScope,
+    /// クエリの構文エラー
+    error: Option<&'a QueryParseError>,
     /// ブロック
     block: Option<Block<'a>>,
     /// テーマ
@@ -26,6 +31,8 @@ impl<'a> SearchBar<'a> {
         Self {
             query,
             cursor_visible: true,
+            scope: SearchScope::default(),
+            error: None,
             block: None,
             theme,
         }
@@ -37,6 +44,18 @@ impl<'a> SearchBar<'a> {
         self
     }
 
+    /// 検索範囲を設定
+    pub fn scope(mut self, scope: SearchScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// 構文エラーを設定
+    pub fn error(mut self, error: Option<&'a QueryParseError>) -> Self {
+        self.error = error;
+        self
+    }
+
     /// ブロックを設定
     pub fn block(mut self, block: Block<'a>) -> Self {
         self.block = Some(block);
@@ -63,6 +82,15 @@ impl Widget for SearchBar<'_> {
                 .add_modifier(Modifier::BOLD),
         );
 
+        // 検索範囲
+        let scope_style = match self.scope {
+            SearchScope::Metadata => Style::default().fg(palette.text_dim),
+            SearchScope::Content => Style::default()
+                .fg(palette.accent_alt)
+                .add_modifier(Modifier::BOLD),
+        };
+        let scope = Span::styled(format!("[{}]", self.scope.label()), scope_style);
+
         // 検索テキスト
         let text = Span::styled(self.query, Style::default().fg(palette.input_fg));
 
@@ -78,7 +106,17 @@ impl Widget for SearchBar<'_> {
             Span::raw("")
         };
 
-        let line = Line::from(vec![prompt, Span::raw(" "), text, cursor]);
+        let mut spans = vec![prompt, Span::raw(" "), scope, Span::raw(" "), text, cursor];
+
+        // 構文エラー
+        if let Some(error) = self.error {
+            spans.push(Span::styled(
+                format!("  ✗ {}", error),
+                Style::default().fg(palette.error),
+            ));
+        }
+
+        let line = Line::from(spans);
         let mut paragraph = Paragraph::new(line);
 
         if let Some(block) = self.block {
@@ -107,6 +145,30 @@ mod tests {
         assert!(!search_bar.cursor_visible);
     }
 
+    #[test]
+    fn test_search_bar_scope() {
+        let search_bar = SearchBar::new("test", Theme::default()).scope(SearchScope::Content);
+        assert_eq!(search_bar.scope, SearchScope::Content);
+    }
+
+    #[test]
+    fn test_search_bar_error() {
+        let error = QueryParseError {
+            message: "Unterminated quote".to_string(),
+            position: 3,
+        };
+        let search_bar = SearchBar::new("ab \"c", Theme::default()).error(Some(&error));
+        assert_eq!(search_bar.error, Some(&error));
+
+        let area = Rect::new(0, 0, 60, 1);
+        let mut buf = Buffer::empty(area);
+        search_bar.render(area, &mut buf);
+        let rendered: String = (0..area.width)
+            .map(|x| buf[(x, 0)].symbol().to_string())
+            .collect();
+        assert!(rendered.contains("Unterminated quote (at 4)"));
+    }
+
     #[test]
     fn test_search_bar_block() {
         let block = Block::default();
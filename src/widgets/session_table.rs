@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -5,10 +7,151 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, StatefulWidget, Widget},
 };
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use crate::tea::{SessionListItem, SessionSource};
+use crate::domain::billing::format_tokens;
+use crate::domain::{Currency, ExchangeRates, SessionStats};
+use crate::tea::{SessionKey, SessionListItem, SessionSource};
 use crate::theme::Theme;
 
+/// 列の区切り
+const COLUMN_GAP: &str = "  ";
+/// プロジェクト列の最小幅
+const PROJECT_MIN_WIDTH: usize = 10;
+/// プロジェクト列の最大幅
+const PROJECT_MAX_WIDTH: usize = 24;
+/// プロンプト列の最小幅
+const PROMPT_MIN_WIDTH: usize = 16;
+
+/// テーブルの列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Time,
+    Source,
+    Project,
+    Messages,
+    Tokens,
+    Cost,
+    Prompt,
+}
+
+impl Column {
+    /// 表示順
+    const ALL: [Column; 7] = [
+        Column::Time,
+        Column::Source,
+        Column::Project,
+        Column::Messages,
+        Column::Tokens,
+        Column::Cost,
+        Column::Prompt,
+    ];
+
+    /// 幅が足りないときに外す順
+    const DROP_ORDER: [Column; 5] = [
+        Column::Tokens,
+        Column::Messages,
+        Column::Source,
+        Column::Cost,
+        Column::Project,
+    ];
+
+    /// 見出し
+    fn title(self) -> &'static str {
+        match self {
+            Column::Time => "Time",
+            Column::Source => "Source",
+            Column::Project => "Project",
+            Column::Messages => "Msgs",
+            Column::Tokens => "Tokens",
+            Column::Cost => "Cost",
+            Column::Prompt => "First prompt",
+        }
+    }
+
+    /// 固定幅（可変幅の列は最小幅）
+    fn min_width(self) -> usize {
+        match self {
+            Column::Time => 16,
+            Column::Source => 6,
+            Column::Project => PROJECT_MIN_WIDTH,
+            Column::Messages => 5,
+            Column::Tokens => 11,
+            Column::Cost => 9,
+            Column::Prompt => PROMPT_MIN_WIDTH,
+        }
+    }
+
+    /// 数値の列は右寄せ
+    fn right_aligned(self) -> bool {
+        matches!(self, Column::Messages | Column::Tokens | Column::Cost)
+    }
+}
+
+/// 端末幅に合わせて列と幅を決める
+/// 入り切らない場合は優先度の低い列から外し、余った幅はプロジェクト列とプロンプト列に回す
+fn layout_columns(width: usize) -> Vec<(Column, usize)> {
+    let mut columns = Column::ALL.to_vec();
+    let required = |columns: &[Column]| {
+        columns
+            .iter()
+            .map(|column| column.min_width())
+            .sum::<usize>()
+            + COLUMN_GAP.len() * columns.len().saturating_sub(1)
+    };
+    for column in Column::DROP_ORDER {
+        if required(&columns) <= width {
+            break;
+        }
+        columns.retain(|c| *c != column);
+    }
+
+    let mut spare = width.saturating_sub(required(&columns));
+    let project_extra = spare.min(PROJECT_MAX_WIDTH - PROJECT_MIN_WIDTH);
+    if columns.contains(&Column::Project) {
+        spare -= project_extra;
+    }
+    columns
+        .into_iter()
+        .map(|column| {
+            let width = match column {
+                Column::Project => PROJECT_MIN_WIDTH + project_extra,
+                Column::Prompt => PROMPT_MIN_WIDTH + spare,
+                _ => column.min_width(),
+            };
+            (column, width)
+        })
+        .collect()
+}
+
+/// 表示幅で切り詰めて、指定幅に揃える（UTF-8・全角対応）
+fn fit(s: &str, width: usize, right_aligned: bool) -> String {
+    let text = if s.width() <= width {
+        s.to_string()
+    } else if width == 0 {
+        String::new()
+    } else {
+        let mut truncated = String::new();
+        let mut used = 0;
+        for ch in s.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if used + ch_width > width - 1 {
+                break;
+            }
+            used += ch_width;
+            truncated.push(ch);
+        }
+        truncated.push('…');
+        truncated
+    };
+    let padding = " ".repeat(width.saturating_sub(text.width()));
+    if right_aligned {
+        format!("{padding}{text}")
+    } else {
+        format!("{text}{padding}")
+    }
+}
+
 /// セッションテーブルの状態
 #[derive(Debug, Default)]
 pub struct SessionTableState {
@@ -30,10 +173,16 @@ impl SessionTableState {
     }
 }
 
-/// セッション一覧テーブルウィジェット
+/// セッション一覧テーブルウィジェット（時系列のフラット表示）
 pub struct SessionTable<'a> {
     /// セッション一覧
-    sessions: &'a [SessionListItem],
+    sessions: &'a [&'a SessionListItem],
+    /// セッションごとの集計メタデータ
+    stats: Option<&'a HashMap<SessionKey, SessionStats>>,
+    /// コスト表示通貨
+    currency: Currency,
+    /// 為替レート表
+    exchange_rates: Option<&'a ExchangeRates>,
     /// ブロック（ボーダー）
     block: Option<Block<'a>>,
     /// 通常行のスタイル
@@ -46,10 +195,13 @@ pub struct SessionTable<'a> {
 
 impl<'a> SessionTable<'a> {
     /// 新規作成
-    pub fn new(sessions: &'a [SessionListItem], theme: Theme) -> Self {
+    pub fn new(sessions: &'a [&'a SessionListItem], theme: Theme) -> Self {
         let palette = theme.palette;
         Self {
             sessions,
+            stats: None,
+            currency: Currency::USD,
+            exchange_rates: None,
             block: None,
             style: Style::default(),
             highlight_style: Style::default()
@@ -60,6 +212,19 @@ impl<'a> SessionTable<'a> {
         }
     }
 
+    /// 集計メタデータを設定（メッセージ数・トークン数・コストの列に表示）
+    pub fn stats(mut self, stats: &'a HashMap<SessionKey, SessionStats>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// コスト表示通貨と為替レート表を設定
+    pub fn currency(mut self, currency: Currency, rates: &'a ExchangeRates) -> Self {
+        self.currency = currency;
+        self.exchange_rates = Some(rates);
+        self
+    }
+
     /// ブロックを設定
     pub fn block(mut self, block: Block<'a>) -> Self {
         self.block = Some(block);
@@ -98,7 +263,16 @@ impl StatefulWidget for SessionTable<'_> {
             return;
         }
 
-        let visible_height = inner_area.height as usize;
+        let columns = layout_columns(inner_area.width as usize);
+
+        // 見出し行
+        let header = self.render_header_line(&columns);
+        buf.set_line(inner_area.x, inner_area.y, &header, inner_area.width);
+
+        let visible_height = inner_area.height.saturating_sub(1) as usize;
+        if visible_height == 0 {
+            return;
+        }
 
         // オフセット調整（選択が見えるように）
         if state.selected < state.offset {
@@ -112,7 +286,7 @@ impl StatefulWidget for SessionTable<'_> {
         let end = (start + visible_height).min(self.sessions.len());
 
         for (i, session) in self.sessions[start..end].iter().enumerate() {
-            let y = inner_area.y + i as u16;
+            let y = inner_area.y + 1 + i as u16;
             let actual_index = start + i;
             let is_selected = actual_index == state.selected;
 
@@ -123,7 +297,7 @@ impl StatefulWidget for SessionTable<'_> {
             };
 
             // 行を構築
-            let line = self.render_session_line(session, inner_area.width as usize);
+            let line = self.render_session_line(session, &columns);
 
             // 行を描画
             buf.set_line(inner_area.x, y, &line, inner_area.width);
@@ -137,64 +311,134 @@ impl StatefulWidget for SessionTable<'_> {
 }
 
 impl SessionTable<'_> {
+    /// 見出し行をレンダリング
+    fn render_header_line(&self, columns: &[(Column, usize)]) -> Line<'static> {
+        let style = Style::default()
+            .fg(self.theme.palette.text_dim)
+            .add_modifier(Modifier::BOLD);
+        let cells = columns
+            .iter()
+            .map(|(column, width)| (fit(column.title(), *width, column.right_aligned()), style))
+            .collect();
+        self.join_cells(cells)
+    }
+
     /// セッション行をレンダリング
-    fn render_session_line(&self, session: &SessionListItem, width: usize) -> Line<'static> {
+    fn render_session_line(
+        &self,
+        session: &SessionListItem,
+        columns: &[(Column, usize)],
+    ) -> Line<'static> {
         let palette = self.theme.palette;
-        // 時刻（固定幅）
-        let time_width = 16;
-        let time = format!("{:width$}", session.formatted_time, width = time_width);
-
-        let (label_text, label_style) = match session.source {
-            SessionSource::Claude => (
-                "[Claude]",
-                Style::default()
-                    .fg(palette.accent)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            SessionSource::Codex => (
-                "[Codex]",
-                Style::default()
-                    .fg(palette.success)
-                    .add_modifier(Modifier::BOLD),
-            ),
-        };
-        let label_width = 8;
-        let label = format!("{:width$}", label_text, width = label_width);
+        let stats = self.stats.and_then(|stats| stats.get(&session.key()));
+        let dim = Style::default().fg(palette.text_dim);
+        let text = Style::default().fg(palette.text);
 
-        // プロジェクト名（固定幅）
-        let project_width = 20;
-        let project = if session.project_name.len() > project_width {
-            format!("{}…", &session.project_name[..project_width - 1])
-        } else {
-            format!("{:width$}", session.project_name, width = project_width)
-        };
+        let cells = columns
+            .iter()
+            .map(|(column, width)| {
+                let (value, style) = match column {
+                    Column::Time => (session.formatted_time.clone(), dim),
+                    Column::Source => match session.source {
+                        SessionSource::Claude => (
+                            "Claude".to_string(),
+                            Style::default()
+                                .fg(palette.accent)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        SessionSource::Codex => (
+                            "Codex".to_string(),
+                            Style::default()
+                                .fg(palette.success)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                    },
+                    Column::Project => (session.project_name.clone(), text),
+                    // 集計メタデータがまだないセッションは "-"
+                    Column::Messages => (
+                        stats.map_or("-".to_string(), |s| s.message_count.to_string()),
+                        dim,
+                    ),
+                    Column::Tokens => (
+                        stats.map_or("-".to_string(), |s| format_tokens(s.total_tokens)),
+                        dim,
+                    ),
+                    Column::Cost => (
+                        stats
+                            .and_then(|s| s.cost_usd)
+                            .map_or("-".to_string(), |usd| match self.exchange_rates {
+                                // セッション日時の為替レートで換算
+                                Some(rates) => {
+                                    self.currency
+                                        .format_cost(usd, rates, Some(session.datetime))
+                                }
+                                None => Currency::USD.format_amount(usd),
+                            }),
+                        Style::default().fg(palette.warning),
+                    ),
+                    Column::Prompt => (session.first_user_message.clone(), text),
+                };
+                (fit(&value, *width, column.right_aligned()), style)
+            })
+            .collect();
+        self.join_cells(cells)
+    }
 
-        // 表示テキスト（残りの幅）
-        let separator = " │ ";
-        let used_width =
-            time_width + separator.len() + label_width + separator.len() + project_width + separator.len();
-        let display_width = width.saturating_sub(used_width);
-        let display = if session.latest_user_message.len() > display_width {
-            if display_width > 1 {
-                format!(
-                    "{}…",
-                    &session.latest_user_message[..display_width - 1]
-                )
-            } else {
-                "…".to_string()
+    /// セルを区切りでつなげる
+    fn join_cells(&self, cells: Vec<(String, Style)>) -> Line<'static> {
+        let gap_style = Style::default().fg(self.theme.palette.text_dim);
+        let mut spans = Vec::with_capacity(cells.len() * 2);
+        for (i, (cell, style)) in cells.into_iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(COLUMN_GAP, gap_style));
             }
-        } else {
-            session.latest_user_message.clone()
-        };
+            spans.push(Span::styled(cell, style));
+        }
+        Line::from(spans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total_width(columns: &[(Column, usize)]) -> usize {
+        columns.iter().map(|(_, width)| width).sum::<usize>()
+            + COLUMN_GAP.len() * columns.len().saturating_sub(1)
+    }
+
+    #[test]
+    fn test_layout_columns_adapts_to_width() {
+        let wide = layout_columns(160);
+        assert_eq!(
+            wide.iter().map(|(column, _)| *column).collect::<Vec<_>>(),
+            Column::ALL.to_vec()
+        );
+        assert_eq!(total_width(&wide), 160);
+        assert!(wide.contains(&(Column::Project, PROJECT_MAX_WIDTH)));
+
+        // 狭い端末では優先度の低い列から外れる
+        let narrow = layout_columns(60);
+        let names: Vec<_> = narrow.iter().map(|(column, _)| *column).collect();
+        assert_eq!(
+            names,
+            vec![Column::Time, Column::Project, Column::Cost, Column::Prompt]
+        );
+        assert_eq!(total_width(&narrow), 60);
+
+        // 時刻とプロンプトは常に残る
+        let tiny = layout_columns(20);
+        assert_eq!(
+            tiny.iter().map(|(column, _)| *column).collect::<Vec<_>>(),
+            vec![Column::Time, Column::Prompt]
+        );
+    }
 
-        Line::from(vec![
-            Span::styled(time, Style::default().fg(palette.text_dim)),
-            Span::styled(separator, Style::default().fg(palette.text_dim)),
-            Span::styled(label, label_style),
-            Span::styled(separator, Style::default().fg(palette.text_dim)),
-            Span::styled(project, Style::default().fg(palette.text)),
-            Span::styled(separator, Style::default().fg(palette.text_dim)),
-            Span::styled(display, Style::default().fg(palette.text)),
-        ])
+    #[test]
+    fn test_fit_truncates_by_display_width() {
+        assert_eq!(fit("abc", 5, false), "abc  ");
+        assert_eq!(fit("12", 5, true), "   12");
+        assert_eq!(fit("日本語のテキスト", 7, false), "日本語…");
+        assert_eq!(fit("abcdef", 4, false), "abc…");
     }
 }
@@ -6,10 +6,20 @@ use ratatui::{
     widgets::{Block, Borders, Widget},
 };
 
-use crate::domain::Currency;
+use crate::domain::{BudgetLevel, BudgetStatus, Currency};
+use crate::search::{MatchMode, SearchScope, SortMode};
 use crate::tea::{Model, ViewMode};
 use crate::theme::Theme;
 
+/// 予算ゲージの幅（セル数）
+const BUDGET_GAUGE_WIDTH: usize = 8;
+
+/// 使用率を ▰▱ のゲージで表す（超過分は切り捨て）
+fn budget_gauge(ratio: f64, width: usize) -> String {
+    let filled = ((ratio.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+    format!("{}{}", "▰".repeat(filled), "▱".repeat(width - filled))
+}
+
 /// ステータスバーウィジェット
 /// フィルタ状態、セッション数、現在のモードを表示する
 pub struct StatusBar<'a> {
@@ -18,9 +28,18 @@ pub struct StatusBar<'a> {
     is_filtered: bool,
     view_mode: ViewMode,
     search_query: Option<&'a str>,
-    project_filter: Option<&'a str>,
+    search_scope: SearchScope,
+    match_mode: MatchMode,
+    content_search_pending: bool,
+    content_hits: Option<(usize, usize)>,
+    filters: Vec<(&'static str, String)>,
+    sort_mode: SortMode,
+    find_query: Option<&'a str>,
+    find_position: Option<String>,
+    find_has_matches: bool,
     error_message: Option<&'a str>,
     currency: Currency,
+    budget: Option<&'a BudgetStatus>,
     theme: Theme,
 }
 
@@ -33,7 +52,9 @@ impl<'a> StatusBar<'a> {
             Some(model.search_query.text.as_str())
         };
 
-        let project_filter = model.filter_criteria.project_filter.as_deref();
+        let content_hits = model
+            .active_content_search()
+            .map(|result| (result.total_hits(), result.session_count()));
 
         Self {
             filtered_count: model.filtered_count(),
@@ -41,9 +62,24 @@ impl<'a> StatusBar<'a> {
             is_filtered: model.is_filtered,
             view_mode: model.view_mode,
             search_query,
-            project_filter,
+            search_scope: model.search_query.scope,
+            match_mode: model.search_query.effective_match_mode(),
+            content_search_pending: model.content_search_pending,
+            content_hits,
+            filters: model.filter_criteria.labels(),
+            sort_mode: model.sort_mode,
+            find_query: model
+                .detail_find
+                .is_active()
+                .then_some(model.detail_find.query.as_str()),
+            find_position: model.detail_find.position_label(),
+            find_has_matches: !model.detail_find.matches.is_empty(),
             error_message: model.error_message.as_deref(),
             currency: model.currency,
+            budget: model
+                .budget_report
+                .as_deref()
+                .and_then(|report| report.most_used()),
             theme: model.theme,
         }
     }
@@ -53,10 +89,13 @@ impl<'a> StatusBar<'a> {
         match self.view_mode {
             ViewMode::SessionList => "List",
             ViewMode::SessionDetail => "Detail",
+            ViewMode::DetailSearch => "Find",
             ViewMode::Search => "Search",
             ViewMode::Filter => "Filter",
             ViewMode::Help => "Help",
             ViewMode::Export => "Export",
+            ViewMode::CodePicker => "Code",
+            ViewMode::Dashboard => "Dashboard",
         }
     }
 }
@@ -112,15 +151,31 @@ impl Widget for StatusBar<'_> {
                 .add_modifier(Modifier::BOLD),
         ));
 
+        // 並び順表示（一覧画面のみ）
+        if matches!(
+            self.view_mode,
+            ViewMode::SessionList | ViewMode::Search | ViewMode::Filter
+        ) {
+            push_sep(&mut spans);
+            spans.push(Span::styled(
+                "Sort: ",
+                Style::default().fg(palette.text_muted),
+            ));
+            spans.push(Span::styled(
+                self.sort_mode.label(),
+                Style::default().fg(palette.text),
+            ));
+        }
+
         // フィルタ状態表示
-        if let Some(project) = self.project_filter {
+        for (label, value) in &self.filters {
             push_sep(&mut spans);
             spans.push(Span::styled(
-                "Project: ",
+                format!("{}: ", label),
                 Style::default().fg(palette.text_muted),
             ));
             spans.push(Span::styled(
-                format!("\"{}\"", project),
+                format!("\"{}\"", value),
                 Style::default().fg(palette.accent),
             ));
         }
@@ -128,14 +183,63 @@ impl Widget for StatusBar<'_> {
         // 検索クエリ表示
         if let Some(query) = self.search_query {
             push_sep(&mut spans);
+            let label = match self.search_scope {
+                SearchScope::Metadata => "Search: ",
+                SearchScope::Content => "Content: ",
+            };
+            spans.push(Span::styled(label, Style::default().fg(palette.text_muted)));
             spans.push(Span::styled(
-                "Search: ",
+                format!("\"{}\"", query),
+                Style::default().fg(palette.accent_alt),
+            ));
+            if self.content_search_pending {
+                spans.push(Span::styled(
+                    " searching...",
+                    Style::default().fg(palette.warning),
+                ));
+            } else if let Some((hits, sessions)) = self.content_hits {
+                spans.push(Span::styled(
+                    format!(" ({} hits in {} sessions)", hits, sessions),
+                    Style::default().fg(palette.text_dim),
+                ));
+            }
+        }
+
+        // マッチ方式表示（検索中のみ）
+        if self.view_mode == ViewMode::Search || self.search_query.is_some() {
+            push_sep(&mut spans);
+            spans.push(Span::styled(
+                "Match: ",
                 Style::default().fg(palette.text_muted),
             ));
+            let mode_style = match self.match_mode {
+                MatchMode::Substring => Style::default().fg(palette.text),
+                MatchMode::Regex | MatchMode::Fuzzy => Style::default()
+                    .fg(palette.accent_alt)
+                    .add_modifier(Modifier::BOLD),
+            };
+            spans.push(Span::styled(self.match_mode.label(), mode_style));
+        }
+
+        // 詳細画面内検索の一致位置表示
+        if let (Some(query), Some(position)) = (self.find_query, self.find_position) {
+            push_sep(&mut spans);
             spans.push(Span::styled(
-                format!("\"{}\"", query),
+                "Find: ",
+                Style::default().fg(palette.text_muted),
+            ));
+            spans.push(Span::styled(
+                format!("\"{}\" ", query),
                 Style::default().fg(palette.accent_alt),
             ));
+            let position_style = if self.find_has_matches {
+                Style::default()
+                    .fg(palette.text)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(palette.error)
+            };
+            spans.push(Span::styled(position, position_style));
         }
 
         if let Some(error) = self.error_message {
@@ -150,7 +254,10 @@ impl Widget for StatusBar<'_> {
             ));
         }
 
-        if self.view_mode == ViewMode::SessionDetail {
+        if matches!(
+            self.view_mode,
+            ViewMode::SessionDetail | ViewMode::Dashboard
+        ) {
             push_sep(&mut spans);
             spans.push(Span::styled(
                 format!("Currency: {}", self.currency.label()),
@@ -158,6 +265,28 @@ impl Widget for StatusBar<'_> {
             ));
         }
 
+        // 使用率が最も高い予算のゲージ
+        if let Some(status) = self.budget {
+            let color = match status.level() {
+                BudgetLevel::Ok => palette.success,
+                BudgetLevel::Warning => palette.warning,
+                BudgetLevel::Exceeded => palette.error,
+            };
+            push_sep(&mut spans);
+            spans.push(Span::styled(
+                format!("Budget {}: ", status.budget.label()),
+                Style::default().fg(palette.text_muted),
+            ));
+            spans.push(Span::styled(
+                format!(
+                    "{} {:.0}%",
+                    budget_gauge(status.ratio(), BUDGET_GAUGE_WIDTH),
+                    status.ratio() * 100.0
+                ),
+                Style::default().fg(color),
+            ));
+        }
+
         let line = Line::from(spans);
 
         // 1行目に表示
@@ -187,9 +316,11 @@ mod tests {
             .map(|i| SessionListItem {
                 session_id: format!("session-{}", i),
                 source: crate::tea::SessionSource::Claude,
+                root: None,
                 project_name: format!("project-{}", i),
                 project_path: format!("/path/to/project-{}", i),
                 latest_user_message: format!("Message {}", i),
+                first_user_message: String::new(),
                 formatted_time: "2025-01-01 00:00".to_string(),
                 datetime: Utc::now(),
             })
@@ -202,4 +333,86 @@ mod tests {
         assert_eq!(status_bar.filtered_count, 10);
         assert!(!status_bar.is_filtered);
     }
+
+    #[test]
+    fn test_status_bar_match_mode() {
+        let mut model = Model::new();
+        model.view_mode = ViewMode::Search;
+        model.search_query.match_mode = MatchMode::Fuzzy;
+
+        let area = Rect::new(0, 0, 80, 2);
+        let mut buf = Buffer::empty(area);
+        StatusBar::new(&model).render(area, &mut buf);
+        let rendered: String = (0..area.width)
+            .map(|x| buf[(x, 1)].symbol().to_string())
+            .collect();
+        assert!(rendered.contains("Match: fuzzy"));
+
+        // 本文検索は部分一致のみ
+        model.search_query.scope = crate::search::SearchScope::Content;
+        let mut buf = Buffer::empty(area);
+        StatusBar::new(&model).render(area, &mut buf);
+        let rendered: String = (0..area.width)
+            .map(|x| buf[(x, 1)].symbol().to_string())
+            .collect();
+        assert!(rendered.contains("Match: substring"));
+    }
+
+    #[test]
+    fn test_status_bar_find_position() {
+        let mut model = Model::new();
+        model.view_mode = ViewMode::SessionDetail;
+        model.detail_find.query = "bug".to_string();
+        model.detail_find.matches = (0..17)
+            .map(|line| crate::tea::DetailMatch {
+                line,
+                range: 0..3,
+                row: line,
+            })
+            .collect();
+        model.detail_find.current = Some(2);
+
+        let area = Rect::new(0, 0, 80, 2);
+        let mut buf = Buffer::empty(area);
+        StatusBar::new(&model).render(area, &mut buf);
+        let rendered: String = (0..area.width)
+            .map(|x| buf[(x, 1)].symbol().to_string())
+            .collect();
+        assert!(rendered.contains("Find: \"bug\" 3/17"));
+    }
+
+    #[test]
+    fn test_status_bar_budget_gauge() {
+        use crate::domain::{Budget, BudgetReport};
+        use std::sync::Arc;
+
+        assert_eq!(budget_gauge(0.0, 4), "▱▱▱▱");
+        assert_eq!(budget_gauge(0.5, 4), "▰▰▱▱");
+        assert_eq!(budget_gauge(2.0, 4), "▰▰▰▰");
+
+        let budgets: Vec<Budget> = [("a", 100.0), ("b", 10.0)]
+            .into_iter()
+            .map(|(name, monthly_usd)| Budget {
+                name: Some(name.to_string()),
+                project: None,
+                model: None,
+                monthly_usd,
+                warn_percent: 80.0,
+            })
+            .collect();
+        let mut report = BudgetReport::new(&budgets, chrono::Local::now().date_naive());
+        for status in &mut report.statuses {
+            status.spent.usd = 5.0;
+        }
+        let mut model = Model::new();
+        model.budget_report = Some(Arc::new(report));
+
+        let area = Rect::new(0, 0, 80, 2);
+        let mut buf = Buffer::empty(area);
+        StatusBar::new(&model).render(area, &mut buf);
+        let rendered: String = (0..area.width)
+            .map(|x| buf[(x, 1)].symbol().to_string())
+            .collect();
+        assert!(rendered.contains("Budget b: ▰▰▰▰▱▱▱▱ 50%"), "{}", rendered);
+    }
 }